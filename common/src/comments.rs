@@ -31,6 +31,21 @@ impl Comments {
     pub fn leading_comments(&self, pos: BytePos) -> Option<ReadGuard<'_, BytePos, Vec<Comment>>> {
         self.leading.get(&pos)
     }
+
+    /// Removes comments for which `f` returns false.
+    pub fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&Comment) -> bool,
+    {
+        for map in &[&self.leading, &self.trailing] {
+            for (pos, mut cmts) in map.clear() {
+                cmts.retain(|cmt| f(cmt));
+                if !cmts.is_empty() {
+                    map.insert(pos, cmts);
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,8 +10,8 @@ use crate::{
     prop::Prop,
     stmt::BlockStmt,
     typescript::{
-        TsAsExpr, TsConstAssertion, TsNonNullExpr, TsOptChain, TsTypeAnn, TsTypeAssertion,
-        TsTypeCastExpr, TsTypeParamDecl, TsTypeParamInstantiation,
+        TsAsExpr, TsConstAssertion, TsNonNullExpr, TsTypeAnn, TsTypeAssertion, TsTypeCastExpr,
+        TsTypeParamDecl, TsTypeParamInstantiation,
     },
     Invalid,
 };
@@ -144,8 +144,8 @@ pub enum Expr {
     #[tag("PrivateName")]
     PrivateName(PrivateName),
 
-    #[tag("TsOptionalChainingExpression")]
-    TsOptChain(TsOptChain),
+    #[tag("OptionalChainingExpression")]
+    OptChain(OptChainExpr),
 
     #[tag("Invalid")]
     Invalid(Invalid),
@@ -418,6 +418,15 @@ pub struct ParenExpr {
     pub expr: Box<Expr>,
 }
 
+/// `a?.b`, `a?.[b]` or `a?.()`
+///
+/// `expr` is a member expression or a call expression.
+#[ast_node("OptionalChainingExpression")]
+pub struct OptChainExpr {
+    pub span: Span,
+    pub expr: Box<Expr>,
+}
+
 #[ast_node]
 #[allow(variant_size_differences)]
 pub enum ExprOrSuper {
@@ -4,12 +4,12 @@ use crate::{
     stmt::BlockStmt,
     typescript::{TsParamProp, TsTypeAnn, TsTypeParamDecl},
 };
-use swc_common::{ast_node, Span};
+use swc_common::{ast_node, Span, Spanned};
 
 /// Common parts of function and method.
 #[ast_node]
 pub struct Function {
-    pub params: Vec<Pat>,
+    pub params: Vec<Param>,
 
     #[serde(default)]
     pub decorators: Vec<Decorator>,
@@ -34,10 +34,30 @@ pub struct Function {
     pub return_type: Option<TsTypeAnn>,
 }
 
+#[ast_node("Parameter")]
+pub struct Param {
+    pub span: Span,
+
+    #[serde(default)]
+    pub decorators: Vec<Decorator>,
+
+    pub pat: Pat,
+}
+
+impl From<Pat> for Param {
+    fn from(pat: Pat) -> Self {
+        Param {
+            span: pat.span(),
+            decorators: Default::default(),
+            pat,
+        }
+    }
+}
+
 #[ast_node]
-pub enum PatOrTsParamProp {
+pub enum ParamOrTsParamProp {
     #[tag("TsParameterProperty")]
     TsParamProp(TsParamProp),
-    #[tag("*")]
-    Pat(Pat),
+    #[tag("Parameter")]
+    Param(Param),
 }
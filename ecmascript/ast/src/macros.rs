@@ -97,6 +97,9 @@ macro_rules! op {
     ("&&") => {
         $crate::BinaryOp::LogicalAnd
     };
+    ("??") => {
+        $crate::BinaryOp::NullishCoalescing
+    };
     ("in") => {
         $crate::BinaryOp::In
     };
@@ -75,6 +75,10 @@ pub enum BinaryOp {
     #[kind(precedence = "2")]
     LogicalAnd,
 
+    /// `??`
+    #[kind(precedence = "1")]
+    NullishCoalescing,
+
     /// `in`
     #[kind(precedence = "7")]
     In,
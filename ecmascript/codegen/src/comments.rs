@@ -1,5 +1,5 @@
 use super::*;
-use swc_common::comments::CommentKind;
+use swc_common::comments::{Comment, CommentKind};
 
 macro_rules! write_comments {
     ($e:expr, $prefix_space:expr, $cmts:expr) => {{
@@ -9,6 +9,10 @@ macro_rules! write_comments {
         };
 
         for cmt in cmts.iter() {
+            if !$e.should_emit_comment(cmt) {
+                continue;
+            }
+
             match cmt.kind {
                 CommentKind::Line => {
                     if $prefix_space {
@@ -16,6 +20,8 @@ macro_rules! write_comments {
                     }
                     $e.wr.write_comment(cmt.span, "//")?;
                     $e.wr.write_comment(cmt.span, &cmt.text)?;
+                    // Otherwise the following code becomes a part of the comment.
+                    $e.wr.write_line()?;
                 }
                 CommentKind::Block => {
                     if $prefix_space {
@@ -24,7 +30,11 @@ macro_rules! write_comments {
                     $e.wr.write_comment(cmt.span, "/*")?;
                     $e.wr.write_comment(cmt.span, &cmt.text)?;
                     $e.wr.write_comment(cmt.span, "*/")?;
-                    $e.wr.write_line()?;
+                    // A line break after `/*#__PURE__*/` may change the meaning of the code,
+                    // e.g. `return /*#__PURE__*/ foo()`.
+                    if !$e.cfg.minify || !is_annotation(cmt) {
+                        $e.wr.write_line()?;
+                    }
                 }
             }
         }
@@ -34,6 +44,14 @@ macro_rules! write_comments {
 }
 
 impl<'a> Emitter<'a> {
+    fn should_emit_comment(&self, cmt: &Comment) -> bool {
+        match self.cfg.retain_comments {
+            CommentRetention::All => true,
+            CommentRetention::Some => is_license(cmt) || is_annotation(cmt),
+            CommentRetention::None => false,
+        }
+    }
+
     pub(super) fn emit_trailing_comments_of_pos(
         &mut self,
         pos: BytePos,
@@ -71,3 +89,16 @@ impl<'a> Emitter<'a> {
         write_comments!(self, false, comments.leading_comments(pos))
     }
 }
+
+/// `/*! ... */`, `@license` and `@preserve` comments.
+fn is_license(cmt: &Comment) -> bool {
+    cmt.text.starts_with('!') || cmt.text.contains("@license") || cmt.text.contains("@preserve")
+}
+
+/// `/*#__PURE__*/` and `/*@__PURE__*/`.
+fn is_annotation(cmt: &Comment) -> bool {
+    cmt.kind == CommentKind::Block && {
+        let text = cmt.text.trim();
+        text == "#__PURE__" || text == "@__PURE__"
+    }
+}
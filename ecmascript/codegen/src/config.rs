@@ -1,4 +1,46 @@
+/// Note: Lines are never wrapped, so there's no option for the line width.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+
+    /// Quote used for string literals which contain neither or both quotes.
+    pub quote_style: QuoteStyle,
+
+    /// Escape non-ascii characters in strings, identifiers, templates and
+    /// regular expressions.
+    pub ascii_only: bool,
+
+    /// Emit the original text of numeric and string literals if it's
+    /// available.
+    pub keep_raw_literals: bool,
+
+    /// Comments to emit if the emitter has comments.
+    pub retain_comments: CommentRetention,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Single,
+    Double,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Single
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentRetention {
+    All,
+    /// License comments (`/*! ... */`, `@license`, `@preserve`) and
+    /// annotations like `/*#__PURE__*/`.
+    Some,
+    None,
+}
+
+impl Default for CommentRetention {
+    fn default() -> Self {
+        CommentRetention::All
+    }
 }
@@ -28,6 +28,14 @@ impl<'a> Emitter<'a> {
         for dec in &node.class.decorators {
             emit!(dec);
         }
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+        if node.class.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
         keyword!("class");
         space!();
         emit!(node.ident);
@@ -39,6 +47,10 @@ impl<'a> Emitter<'a> {
     pub fn emit_fn_decl(&mut self, node: &FnDecl) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
         if node.function.is_async {
             keyword!("async");
             space!();
@@ -59,6 +71,10 @@ impl<'a> Emitter<'a> {
     pub fn emit_var_decl(&mut self, node: &VarDecl) -> Result {
         self.emit_leading_comments_of_pos(node.span.lo())?;
 
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
         keyword!(node.kind.as_str());
         space!();
 
@@ -73,7 +89,22 @@ impl<'a> Emitter<'a> {
     pub fn emit_var_declator(&mut self, node: &VarDeclarator) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        emit!(node.name);
+        match node.name {
+            // `!` goes between the name and the type annotation.
+            Pat::Ident(ref i) if node.definite => {
+                emit!(Ident {
+                    type_ann: None,
+                    ..i.clone()
+                });
+                punct!("!");
+                if let Some(ref ty) = i.type_ann {
+                    punct!(":");
+                    formatting_space!();
+                    emit!(ty);
+                }
+            }
+            _ => emit!(node.name),
+        }
 
         if let Some(ref init) = node.init {
             formatting_space!();
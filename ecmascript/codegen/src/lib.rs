@@ -1,11 +1,11 @@
 #![recursion_limit = "1024"]
 #![allow(unused_variables)]
 
-pub use self::config::Config;
+pub use self::config::{CommentRetention, Config, QuoteStyle};
 use self::{
     list::ListFormat,
     text_writer::WriteJs,
-    util::{SourceMapperExt, SpanExt, StartsWithAlphaNum},
+    util::{escape_non_ascii, SourceMapperExt, SpanExt, StartsWithAlphaNum},
 };
 use hashbrown::HashSet;
 use std::{io, sync::Arc};
@@ -322,7 +322,12 @@ impl<'a> Emitter<'a> {
             Lit::Num(ref n) => emit!(n),
             Lit::Regex(ref n) => {
                 punct!("/");
-                self.wr.write_str(&n.exp)?;
+                if self.cfg.ascii_only {
+                    self.wr
+                        .write_str(&escape_non_ascii(&n.exp, n.flags.contains('u')))?;
+                } else {
+                    self.wr.write_str(&n.exp)?;
+                }
                 punct!("/");
                 self.wr.write_str(&n.flags)?;
             }
@@ -340,10 +345,13 @@ impl<'a> Emitter<'a> {
     pub fn emit_str_lit(&mut self, node: &Str) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        // if let Some(s) = get_text_of_node(&self.cm, node, false) {
-        //     self.wr.write_str_lit(node.span, &s)?;
-        //     return Ok(());
-        // }
+        if self.cfg.keep_raw_literals {
+            if let Some(raw) = self.raw_str_lit(node) {
+                self.wr.write_str_lit(node.span, &raw)?;
+                return Ok(());
+            }
+        }
+
         let value = node
             .value
             .replace("\\", "\\\\")
@@ -353,6 +361,9 @@ impl<'a> Emitter<'a> {
             .replace("\r", "\\r")
             .replace("\t", "\\t")
             .replace('\u{000B}', "\\v")
+            // Line terminators are not allowed in string literals before es2019.
+            .replace('\u{2028}', "\\u2028")
+            .replace('\u{2029}', "\\u2029")
             .replace("\00", "\\x000")
             .replace("\01", "\\x001")
             .replace("\02", "\\x002")
@@ -365,27 +376,68 @@ impl<'a> Emitter<'a> {
             .replace("\09", "\\x009")
             .replace("\0", "\\0");
         // let value = node.value.replace("\n", "\\n");
+        let value = if self.cfg.ascii_only {
+            escape_non_ascii(&value, false).into_owned()
+        } else {
+            value
+        };
 
-        if !node.value.contains('\'') {
-            punct!("'");
-            self.wr.write_str_lit(node.span, &value)?;
-            punct!("'");
-        } else if !node.value.contains('\"') {
-            punct!("\"");
-            self.wr.write_str_lit(node.span, &value)?;
-            punct!("\"");
+        let (quote, other) = match self.cfg.quote_style {
+            QuoteStyle::Single => ('\'', '"'),
+            QuoteStyle::Double => ('"', '\''),
+        };
+        let quote = if node.value.contains(quote) && !node.value.contains(other) {
+            other
         } else {
-            punct!("'");
-            self.wr
-                .write_str_lit(node.span, &value.replace("'", "\\'"))?;
-            punct!("'");
+            quote
+        };
+        let quote_str = if quote == '"' { "\"" } else { "'" };
+
+        punct!(quote_str);
+        if node.value.contains(quote) {
+            self.wr.write_str_lit(
+                node.span,
+                &value.replace(quote, if quote == '"' { "\\\"" } else { "\\'" }),
+            )?;
+        } else {
+            self.wr.write_str_lit(node.span, &value)?;
+        }
+        punct!(quote_str);
+    }
+
+    /// Returns the original text of a string literal, if it still represents
+    /// `node`.
+    fn raw_str_lit(&self, node: &Str) -> Option<String> {
+        let raw = get_text_of_node(&self.cm, node, false)?;
+        if raw.len() < 2 || (self.cfg.ascii_only && !raw.is_ascii()) {
+            return None;
+        }
+
+        let quote = raw.as_bytes()[0];
+        if (quote != b'\'' && quote != b'"') || raw.as_bytes()[raw.len() - 1] != quote {
+            return None;
         }
+        // The value differs from the raw text if the literal was modified by a
+        // transform, or if it was not parsed as a js string literal (e.g. a jsx
+        // attribute with html entities).
+        if unescape_str_lit(&raw[1..raw.len() - 1])? != *node.value {
+            return None;
+        }
+
+        Some(raw)
     }
 
     #[emitter]
     pub fn emit_num_lit(&mut self, num: &Number) -> Result {
         self.emit_leading_comments_of_pos(num.span().lo())?;
 
+        if self.cfg.keep_raw_literals {
+            if let Some(raw) = self.raw_num_lit(num) {
+                self.wr.write_str_lit(num.span, &raw)?;
+                return Ok(());
+            }
+        }
+
         // Handle infinity
         if num.value.is_infinite() {
             if num.value.is_sign_negative() {
@@ -401,6 +453,22 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    /// Returns the original text of a numeric literal, if it still represents
+    /// `num`.
+    fn raw_num_lit(&self, num: &Number) -> Option<String> {
+        let raw = get_text_of_node(&self.cm, num, false)?;
+        if !raw.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+
+        let value = parse_num_lit(&raw)?;
+        if value != num.value || value.is_sign_negative() != num.value.is_sign_negative() {
+            return None;
+        }
+
+        Some(raw)
+    }
+
     #[emitter]
     pub fn emit_big_lit(&mut self, v: &BigInt) -> Result {
         self.emit_leading_comments_of_pos(v.span.lo())?;
@@ -472,6 +540,7 @@ impl<'a> Emitter<'a> {
             Expr::Update(ref n) => emit!(n),
             Expr::Yield(ref n) => emit!(n),
             Expr::PrivateName(ref n) => emit!(n),
+            Expr::OptChain(ref n) => emit!(n),
 
             Expr::JSXMebmer(ref n) => emit!(n),
             Expr::JSXNamespacedName(ref n) => emit!(n),
@@ -484,7 +553,6 @@ impl<'a> Emitter<'a> {
             Expr::TsTypeAssertion(ref n) => emit!(n),
             Expr::TsConstAssertion(ref n) => emit!(n),
             Expr::TsTypeCast(ref n) => emit!(n),
-            Expr::TsOptChain(ref n) => emit!(n),
             Expr::Invalid(ref n) => emit!(n),
         }
     }
@@ -501,6 +569,7 @@ impl<'a> Emitter<'a> {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.callee);
+        emit!(node.type_args);
 
         punct!("(");
         self.emit_expr_or_spreads(node.span(), &node.args, ListFormat::CallExpressionArguments)?;
@@ -514,6 +583,7 @@ impl<'a> Emitter<'a> {
         keyword!("new");
         space!();
         emit!(node.callee);
+        emit!(node.type_args);
 
         if let Some(ref args) = node.args {
             punct!("(");
@@ -547,12 +617,54 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    #[emitter]
+    pub fn emit_opt_chain(&mut self, node: &OptChainExpr) -> Result {
+        self.emit_leading_comments_of_pos(node.span().lo())?;
+
+        match *node.expr {
+            Expr::Member(ref e) => {
+                emit!(e.obj);
+                punct!("?.");
+
+                if e.computed {
+                    punct!("[");
+                    emit!(e.prop);
+                    punct!("]");
+                } else {
+                    emit!(e.prop);
+                }
+            }
+            Expr::Call(ref e) => {
+                emit!(e.callee);
+                punct!("?.");
+                emit!(e.type_args);
+
+                punct!("(");
+                self.emit_expr_or_spreads(
+                    node.span(),
+                    &e.args,
+                    ListFormat::CallExpressionArguments,
+                )?;
+                punct!(")");
+            }
+            _ => emit!(node.expr),
+        }
+    }
+
     /// `1..toString` is a valid property access, emit a dot after the literal
     pub fn needs_2dots_for_property_access(&self, expr: &ExprOrSuper) -> bool {
         match *expr {
             ExprOrSuper::Expr(ref expr) => {
                 match **expr {
-                    Expr::Lit(Lit::Num(Number { span, value })) => {
+                    Expr::Lit(Lit::Num(ref num)) => {
+                        let Number { span, value } = *num;
+                        if self.cfg.keep_raw_literals {
+                            if let Some(raw) = self.raw_num_lit(num) {
+                                // Only plain decimal integers can be followed by a dot.
+                                return raw.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                                    && (raw.len() == 1 || !raw.starts_with('0'));
+                            }
+                        }
                         if value.fract() == 0.0 {
                             return true;
                         }
@@ -585,10 +697,17 @@ impl<'a> Emitter<'a> {
         if node.is_generator {
             punct!("*")
         }
+        emit!(node.type_params);
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
 
+        if let Some(ref ty) = node.return_type {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
+
         punct!("=>");
         emit!(node.body);
     }
@@ -686,6 +805,10 @@ impl<'a> Emitter<'a> {
             emit!(dec);
         }
 
+        if node.class.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
         keyword!("class");
 
         if let Some(ref i) = node.ident {
@@ -698,11 +821,25 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_class_trailing(&mut self, node: &Class) -> Result {
+        emit!(node.type_params);
+
         if node.super_class.is_some() {
             space!();
             keyword!("extends");
             space!();
             emit!(node.super_class);
+            emit!(node.super_type_params);
+        }
+
+        if !node.implements.is_empty() {
+            space!();
+            keyword!("implements");
+            space!();
+            self.emit_list(
+                node.span,
+                Some(&node.implements),
+                ListFormat::HeritageClauseTypes,
+            )?;
         }
 
         punct!("{");
@@ -726,10 +863,18 @@ impl<'a> Emitter<'a> {
     pub fn emit_private_method(&mut self, n: &PrivateMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        for dec in &n.function.decorators {
+            emit!(dec);
+        }
+        self.emit_accessibility(n.accessibility)?;
         if n.is_static {
             keyword!("static");
             space!();
         }
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
         match n.kind {
             MethodKind::Method => {
                 if n.function.is_async {
@@ -755,6 +900,9 @@ impl<'a> Emitter<'a> {
                 emit!(n.key);
             }
         }
+        if n.is_optional {
+            punct!("?");
+        }
 
         self.emit_fn_trailing(&n.function)?;
     }
@@ -763,10 +911,18 @@ impl<'a> Emitter<'a> {
     pub fn emit_class_method(&mut self, n: &ClassMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        for dec in &n.function.decorators {
+            emit!(dec);
+        }
+        self.emit_accessibility(n.accessibility)?;
         if n.is_static {
             keyword!("static");
             space!();
         }
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
         match n.kind {
             MethodKind::Method => {
                 if n.function.is_async {
@@ -792,6 +948,9 @@ impl<'a> Emitter<'a> {
                 emit!(n.key);
             }
         }
+        if n.is_optional {
+            punct!("?");
+        }
 
         self.emit_fn_trailing(&n.function)?;
     }
@@ -800,26 +959,106 @@ impl<'a> Emitter<'a> {
     pub fn emit_private_prop(&mut self, n: &PrivateProp) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
-        unimplemented!("emit_private_prop")
+        for dec in &n.decorators {
+            emit!(dec);
+        }
+        self.emit_accessibility(n.accessibility)?;
+        if n.is_static {
+            keyword!("static");
+            space!();
+        }
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
+        if n.readonly {
+            keyword!("readonly");
+            space!();
+        }
+
+        emit!(n.key);
+        if n.is_optional {
+            punct!("?");
+        }
+        if n.definite {
+            punct!("!");
+        }
+        if let Some(ref ty) = n.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
+        if let Some(ref value) = n.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
     }
 
     #[emitter]
     pub fn emit_class_prop(&mut self, node: &ClassProp) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        unimplemented!("emit_class_prop")
+        for dec in &node.decorators {
+            emit!(dec);
+        }
+        self.emit_accessibility(node.accessibility)?;
+        if node.is_static {
+            keyword!("static");
+            space!();
+        }
+        if node.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
+        if node.readonly {
+            keyword!("readonly");
+            space!();
+        }
+
+        if node.computed {
+            punct!("[");
+            emit!(node.key);
+            punct!("]");
+        } else {
+            emit!(node.key);
+        }
+        if node.is_optional {
+            punct!("?");
+        }
+        if node.definite {
+            punct!("!");
+        }
+        if let Some(ref ty) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
+        if let Some(ref value) = node.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
     }
 
     #[emitter]
     pub fn emit_class_constructor(&mut self, n: &Constructor) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        self.emit_accessibility(n.accessibility)?;
         keyword!("constructor");
         punct!("(");
         self.emit_list(n.span(), Some(&n.params), ListFormat::Parameters)?;
         punct!(")");
 
-        emit!(n.body);
+        match n.body {
+            Some(ref body) => emit!(body),
+            None => semi!(),
+        }
     }
 
     #[emitter]
@@ -875,15 +1114,35 @@ impl<'a> Emitter<'a> {
         self.emit_fn_trailing(&node.function)?;
     }
 
+    #[emitter]
+    pub fn emit_param(&mut self, node: &Param) -> Result {
+        for dec in &node.decorators {
+            emit!(dec);
+        }
+        emit!(node.pat);
+    }
+
     /// prints `(b){}` from `function a(b){}`
     #[emitter]
     pub fn emit_fn_trailing(&mut self, node: &Function) -> Result {
+        emit!(node.type_params);
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
 
-        formatting_space!();
-        emit!(node.body);
+        if let Some(ref ty) = node.return_type {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
+
+        match node.body {
+            Some(ref body) => {
+                formatting_space!();
+                emit!(body);
+            }
+            None => semi!(),
+        }
     }
 
     #[emitter]
@@ -954,7 +1213,12 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_quasi(&mut self, node: &TplElement) -> Result {
-        self.wr.write_str_lit(node.span, &node.raw.value)?;
+        if self.cfg.ascii_only {
+            self.wr
+                .write_str_lit(node.span, &escape_non_ascii(&node.raw.value, true))?;
+        } else {
+            self.wr.write_str_lit(node.span, &node.raw.value)?;
+        }
         return Ok(());
     }
 
@@ -1189,17 +1453,27 @@ impl<'a> Emitter<'a> {
             unimplemented!()
         } else {
             // TODO: span
-            self.wr.write_symbol(ident.span, &ident.sym)?
+            if self.cfg.ascii_only {
+                self.wr
+                    .write_symbol(ident.span, &escape_non_ascii(&ident.sym, true))?;
+            } else {
+                self.wr.write_symbol(ident.span, &ident.sym)?;
+            }
 
             // self.wr
             //     .write(get_text_of_node(&self.cm, &ident, /* includeTrivia */
             // false).as_bytes())?;
         }
 
-        // Call emitList directly since it could be an array of
-        // TypeParameterDeclarations _or_ type arguments
+        if ident.optional {
+            punct!("?");
+        }
 
-        // emitList(node, node.typeArguments, ListFormat::TypeParameters);
+        if let Some(ref ty) = ident.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
     }
 
     pub fn emit_list<N: Node>(
@@ -1460,6 +1734,12 @@ impl<'a> Emitter<'a> {
 
         punct!("...");
         emit!(node.arg);
+
+        if let Some(ref ty) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
     }
 
     #[emitter]
@@ -1497,6 +1777,12 @@ impl<'a> Emitter<'a> {
             ListFormat::ArrayBindingPatternElements,
         )?;
         punct!("]");
+
+        if let Some(ref ty) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
     }
 
     #[emitter]
@@ -1504,6 +1790,11 @@ impl<'a> Emitter<'a> {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.left);
+        if let Some(ref ty) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
         formatting_space!();
         punct!("=");
         formatting_space!();
@@ -1521,6 +1812,12 @@ impl<'a> Emitter<'a> {
             ListFormat::ObjectBindingPatternElements,
         )?;
         punct!("}");
+
+        if let Some(ref ty) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(ty);
+        }
     }
 
     #[emitter]
@@ -1951,7 +2248,6 @@ impl<'a> Emitter<'a> {
     }
 }
 
-#[allow(dead_code)]
 fn get_text_of_node<T: Spanned>(
     cm: &Arc<SourceMap>,
     node: &T,
@@ -1963,13 +2259,122 @@ fn get_text_of_node<T: Spanned>(
         return None;
     }
 
-    let s = cm.span_to_snippet(span).unwrap();
+    let s = cm.span_to_snippet(span).ok()?;
     if s == "" {
         return None;
     }
     Some(s)
 }
 
+/// Parses the text of a numeric literal. Returns `None` for unknown formats.
+fn parse_num_lit(raw: &str) -> Option<f64> {
+    let raw = raw.replace('_', "");
+    let is_legacy_octal =
+        raw.len() > 1 && raw.starts_with('0') && raw.bytes().all(|b| b'0' <= b && b <= b'7');
+
+    let radix = if raw.starts_with("0x") || raw.starts_with("0X") {
+        16
+    } else if raw.starts_with("0o") || raw.starts_with("0O") {
+        8
+    } else if raw.starts_with("0b") || raw.starts_with("0B") {
+        2
+    } else if is_legacy_octal {
+        // Legacy octal literal
+        return u64::from_str_radix(&raw[1..], 8).ok().map(|v| v as f64);
+    } else {
+        return raw.parse().ok();
+    };
+
+    u64::from_str_radix(&raw[2..], radix).ok().map(|v| v as f64)
+}
+
+/// Returns the value of a string literal, given its text without quotes.
+/// Returns `None` for legacy octal escapes and lone surrogates.
+fn unescape_str_lit(raw: &str) -> Option<String> {
+    fn hex(s: &str) -> Option<u32> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(s, 16).ok()
+    }
+
+    let mut buf = String::with_capacity(raw.len());
+    let mut high_surrogate = None;
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            if high_surrogate.is_some() {
+                return None;
+            }
+            buf.push(c);
+            continue;
+        }
+
+        let c = chars.next()?;
+        let code = match c {
+            'u' => {
+                let code = if chars.peek() == Some(&'{') {
+                    chars.next();
+                    let digits: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    hex(&digits)?
+                } else {
+                    let digits: String = chars.by_ref().take(4).collect();
+                    if digits.len() != 4 {
+                        return None;
+                    }
+                    hex(&digits)?
+                };
+
+                match (high_surrogate.take(), code) {
+                    (None, 0xd800..=0xdbff) => {
+                        high_surrogate = Some(code);
+                        continue;
+                    }
+                    (Some(high), 0xdc00..=0xdfff) => {
+                        0x10000 + ((high - 0xd800) << 10) + (code - 0xdc00)
+                    }
+                    (Some(..), _) | (None, 0xdc00..=0xdfff) => return None,
+                    (None, code) => code,
+                }
+            }
+            _ if high_surrogate.is_some() => return None,
+            'x' => {
+                let digits: String = chars.by_ref().take(2).collect();
+                if digits.len() != 2 {
+                    return None;
+                }
+                hex(&digits)?
+            }
+            '0' if chars.peek().map_or(true, |c| !c.is_ascii_digit()) => 0,
+            '0'..='9' => return None,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'n' => 0x0a,
+            'r' => 0x0d,
+            't' => 0x09,
+            'v' => 0x0b,
+            // Line continuation
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => continue,
+            _ => c as u32,
+        };
+
+        buf.push(std::char::from_u32(code)?);
+    }
+
+    if high_surrogate.is_some() {
+        return None;
+    }
+
+    Some(buf)
+}
+
 /// In some cases, we need to emit a space between the operator and the operand.
 /// One obvious case is when the operator is an identifier, like delete or
 /// typeof. We also need to do this for plus and minus expressions in certain
@@ -1,4 +1,4 @@
-use self::swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+use self::swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax, TsConfig};
 use super::*;
 use crate::config::Config;
 use std::{
@@ -49,7 +49,7 @@ impl Builder {
     }
 }
 
-fn parse_then_emit(from: &str, cfg: Config) -> String {
+fn parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> String {
     ::testing::run_test(false, |cm, handler| {
         let src = cm.new_source_file(FileName::Real("custom.js".into()), from.to_string());
         println!(
@@ -61,7 +61,7 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
         let res = {
             let mut parser = Parser::new(
                 Session { handler: &handler },
-                Syntax::default(),
+                syntax,
                 SourceFileInput::from(&*src),
                 Some(&comments),
             );
@@ -76,20 +76,67 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
     .unwrap()
 }
 
+fn es() -> Syntax {
+    Syntax::Es(EsConfig {
+        nullish_coalescing: true,
+        optional_chaining: true,
+        ..Default::default()
+    })
+}
+
+fn typescript() -> Syntax {
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    })
+}
+
 pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true });
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        es(),
+    );
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
 }
 
 pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false });
+    let out = parse_then_emit(from, Default::default(), es());
+
+    assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
+}
+
+pub(crate) fn assert_min_typescript(from: &str, to: &str) {
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        typescript(),
+    );
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
+}
+
+pub(crate) fn assert_pretty_typescript(from: &str, to: &str) {
+    let out = parse_then_emit(from, Default::default(), typescript());
 
     assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
 }
 
 fn test_from_to(from: &str, to: &str) {
-    let out = parse_then_emit(from, Default::default());
+    let out = parse_then_emit(from, Default::default(), es());
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
+}
+
+fn test_with_config(from: &str, to: &str, cfg: Config) {
+    let out = parse_then_emit(from, cfg, es());
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
 }
@@ -166,6 +213,11 @@ fn no_octal_escape() {
     );
 }
 
+#[test]
+fn escape_line_terminators() {
+    test_from_to("'\u{2028}\u{2029}'", r#"'\u2028\u2029';"#);
+}
+
 #[test]
 fn issue_450() {
     test_from_to(
@@ -182,6 +234,157 @@ fn issue_450() {
     );
 }
 
+#[test]
+fn nullish_coalescing() {
+    test_from_to("a ?? b", "a ?? b;");
+    assert_min("(a || b) ?? c", "(a||b)??c;");
+}
+
+#[test]
+fn optional_chaining() {
+    test_from_to("a?.b", "a?.b;");
+    test_from_to("a?.[b]", "a?.[b];");
+    test_from_to("a?.()", "a?.();");
+    test_from_to("a?.b.c(d)", "a?.b.c(d);");
+}
+
+#[test]
+fn quote_style() {
+    let cfg = Config {
+        quote_style: QuoteStyle::Double,
+        ..Default::default()
+    };
+
+    test_with_config(
+        r#"'a';
+'b"';
+"c'";
+'d"\'';"#,
+        r#""a";
+'b"';
+"c'";
+"d\"'";"#,
+        cfg,
+    );
+}
+
+#[test]
+fn ascii_only() {
+    let cfg = Config {
+        ascii_only: true,
+        ..Default::default()
+    };
+
+    test_with_config(
+        "var ü = '한😀';
+/é/;
+/😀/u;
+`é${ü}`;",
+        r#"var \u00FC = '\uD55C\uD83D\uDE00';
+/\u00E9/;
+/\u{1F600}/u;
+`\u00E9${\u00FC}`;"#,
+        cfg,
+    );
+}
+
+#[test]
+fn keep_raw_literals() {
+    let cfg = Config {
+        keep_raw_literals: true,
+        ..Default::default()
+    };
+
+    test_with_config(
+        r#"0x10;
+1e3;
+.5;
+"a";
+'b\x41';
+1.0.toString();
+10..toString();"#,
+        r#"0x10;
+1e3;
+.5;
+"a";
+'b\x41';
+1.0.toString();
+10..toString();"#,
+        cfg,
+    );
+}
+
+#[test]
+fn keep_raw_literals_decoded_value() {
+    let cfg = Config {
+        keep_raw_literals: true,
+        ..Default::default()
+    };
+
+    test_with_config(
+        r#"'\u{1F600}';
+"a\
+b";"#,
+        r#"'\u{1F600}';
+"a\
+b";"#,
+        cfg,
+    );
+
+    // Html entities are decoded by the parser, so the raw text can't be used.
+    let out = parse_then_emit(
+        r#"<a b="x&amp;y" />;"#,
+        cfg,
+        Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+    );
+    assert!(out.contains("'x&y'"), "{}", out);
+}
+
+#[test]
+fn retain_license_comments() {
+    let src = "/*! license */
+// comment
+/* comment */
+a = /*#__PURE__*/ foo();";
+
+    test_with_config(
+        src,
+        "/*! license */\na=/*#__PURE__*/foo();",
+        Config {
+            minify: true,
+            retain_comments: CommentRetention::Some,
+            ..Default::default()
+        },
+    );
+    test_with_config(
+        src,
+        "a=foo();",
+        Config {
+            minify: true,
+            retain_comments: CommentRetention::None,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn retain_license_line_comments() {
+    test_with_config(
+        "// @license MIT
+// comment
+a = 1;",
+        "// @license MIT\na=1;",
+        Config {
+            minify: true,
+            retain_comments: CommentRetention::Some,
+            ..Default::default()
+        },
+    );
+}
+
 #[derive(Debug, Clone)]
 struct Buf(Arc<RwLock<Vec<u8>>>);
 impl Write for Buf {
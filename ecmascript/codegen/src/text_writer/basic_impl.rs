@@ -4,7 +4,7 @@ use std::{
     io::{self, Write},
     sync::Arc,
 };
-use swc_common::{FileName, SourceMap, Span};
+use swc_common::{SourceMap, Span};
 
 ///
 /// -----
@@ -15,6 +15,7 @@ use swc_common::{FileName, SourceMap, Span};
 pub struct JsWriter<'a, W: Write> {
     cm: Arc<SourceMap>,
     indent: usize,
+    indent_str: &'a str,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
@@ -34,6 +35,7 @@ impl<'a, W: Write> JsWriter<'a, W> {
         JsWriter {
             cm,
             indent: Default::default(),
+            indent_str: "    ",
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
@@ -44,12 +46,19 @@ impl<'a, W: Write> JsWriter<'a, W> {
         }
     }
 
+    /// Sets the string used for one level of indentation. Defaults to four
+    /// spaces.
+    pub fn with_indent(mut self, indent_str: &'a str) -> Self {
+        self.indent_str = indent_str;
+        self
+    }
+
     fn write_indent_string(&mut self) -> io::Result<usize> {
-        const INDENT: &[u8] = b"    ";
+        let indent_str = self.indent_str;
 
         let mut cnt = 0;
         for _ in 0..self.indent {
-            cnt += self.raw_write(INDENT)?;
+            cnt += self.raw_write(indent_str.as_bytes())?;
         }
 
         Ok(cnt)
@@ -70,16 +79,13 @@ impl<'a, W: Write> JsWriter<'a, W> {
                 if let Some(ref mut srcmap) = self.srcmap {
                     let loc = self.cm.lookup_char_pos($byte_pos);
 
-                    let src = match loc.file.name {
-                        FileName::Real(ref p) => Some(p.display().to_string()),
-                        _ => None,
-                    };
+                    let src = loc.file.name.to_string();
                     srcmap.add(
                         self.line_count as _,
                         self.line_pos as _,
                         (loc.line - 1) as _,
                         loc.col.0 as _,
-                        src.as_ref().map(|s| &**s),
+                        Some(&src),
                         None,
                     );
                 }
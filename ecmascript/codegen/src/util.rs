@@ -1,5 +1,5 @@
 use super::list::ListFormat;
-use std::sync::Arc;
+use std::{borrow::Cow, fmt::Write, sync::Arc};
 use swc_common::{
     errors::SourceMapper, BytePos, SourceMap, SourceMapperDyn, Span, Spanned, SyntaxContext,
 };
@@ -201,7 +201,10 @@ impl StartsWithAlphaNum for Expr {
             Expr::JSXNamespacedName(..) => true,
             Expr::JSXMebmer(..) => true,
 
+            Expr::OptChain(OptChainExpr { ref expr, .. }) => expr.starts_with_alpha_num(),
+
             Expr::TsTypeAssertion(..) => false,
+
             Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
             | Expr::TsAs(TsAsExpr { ref expr, .. })
             | Expr::TsConstAssertion(TsConstAssertion { ref expr, .. }) => {
@@ -210,7 +213,6 @@ impl StartsWithAlphaNum for Expr {
 
             // TODO
             Expr::TsTypeCast(..) => true,
-            Expr::TsOptChain(ref e) => e.expr.starts_with_alpha_num(),
 
             Expr::Invalid(..) => true,
         }
@@ -297,3 +299,31 @@ impl StartsWithAlphaNum for Decl {
         }
     }
 }
+
+/// Replaces non-ascii characters in `s` with unicode escape sequences.
+///
+/// Characters outside of the basic multilingual plane are written as
+/// `\u{XXXXX}` if `braces` is true, and as a surrogate pair otherwise.
+pub fn escape_non_ascii(s: &str, braces: bool) -> Cow<str> {
+    if s.is_ascii() {
+        return Cow::Borrowed(s);
+    }
+
+    let mut buf = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        if c.is_ascii() {
+            buf.push(c);
+        } else if (c as u32) <= 0xffff {
+            write!(buf, "\\u{:04X}", c as u32).unwrap();
+        } else if braces {
+            write!(buf, "\\u{{{:X}}}", c as u32).unwrap();
+        } else {
+            let mut units = [0; 2];
+            for unit in c.encode_utf16(&mut units) {
+                write!(buf, "\\u{:04X}", unit).unwrap();
+            }
+        }
+    }
+
+    Cow::Owned(buf)
+}
@@ -0,0 +1,190 @@
+#![feature(box_syntax)]
+#![feature(specialization)]
+#![feature(test)]
+
+extern crate test;
+
+use std::{
+    env,
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, RwLock},
+};
+use swc_common::{FileName, Fold, FoldWith};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{self, Emitter};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax, TsConfig};
+use test::{
+    test_main, DynTestFn, Options, ShouldPanic::No, TestDesc, TestDescAndFn, TestName, TestType,
+};
+use walkdir::WalkDir;
+
+fn add_test<F: FnOnce() + Send + 'static>(
+    tests: &mut Vec<TestDescAndFn>,
+    name: String,
+    ignore: bool,
+    f: F,
+) {
+    tests.push(TestDescAndFn {
+        desc: TestDesc {
+            test_type: TestType::UnitTest,
+            name: TestName::DynTestName(name),
+            ignore,
+            should_panic: No,
+            allow_fail: false,
+        },
+        testfn: DynTestFn(box f),
+    });
+}
+
+struct MyHandlers;
+
+impl swc_ecma_codegen::Handlers for MyHandlers {}
+
+/// Emits each file in the typescript test suite of the parser, and checks
+/// that the output parses back to the same module.
+fn reparse_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("parser")
+        .join("tests")
+        .join("typescript");
+
+    eprintln!("Loading tests from {}", dir.display());
+
+    for entry in WalkDir::new(&dir) {
+        let entry = entry?;
+        let is_ts = {
+            let name = entry.file_name().to_string_lossy();
+            name.ends_with(".ts") || name.ends_with(".tsx")
+        };
+        if entry.file_type().is_dir() || !is_ts {
+            continue;
+        }
+
+        let file_name = entry
+            .path()
+            .strip_prefix(&dir)
+            .expect("failed to strip prefix")
+            .to_str()
+            .expect("to_str() failed")
+            .to_string();
+
+        let syntax = Syntax::Typescript(TsConfig {
+            tsx: file_name.contains("tsx"),
+            dynamic_import: true,
+            decorators: true,
+            ..Default::default()
+        });
+
+        let name = format!("typescript::reparse::{}", file_name);
+
+        add_test(tests, name, false, move || {
+            ::testing::run_test(false, |cm, handler| {
+                let src = cm.load_file(entry.path()).expect("failed to load file");
+
+                let expected = Parser::new(
+                    Session { handler: &handler },
+                    syntax,
+                    SourceFileInput::from(&*src),
+                    None,
+                )
+                .parse_module()
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+                let mut wr = Buf(Arc::new(RwLock::new(vec![])));
+                {
+                    let mut emitter = Emitter {
+                        cfg: Default::default(),
+                        cm: cm.clone(),
+                        wr: box swc_ecma_codegen::text_writer::JsWriter::new(
+                            cm.clone(),
+                            "\n",
+                            &mut wr,
+                            None,
+                        ),
+                        comments: None,
+                        handlers: box MyHandlers,
+                        pos_of_leading_comments: Default::default(),
+                    };
+                    emitter.emit_module(&expected).unwrap();
+                }
+                let code = String::from_utf8_lossy(&wr.0.read().unwrap()).into_owned();
+                eprintln!(
+                    "\n\n========== Running reparse test {}\nOutput:\n{}\n",
+                    file_name, code
+                );
+
+                let src = cm.new_source_file(FileName::Anon, code);
+                let actual = Parser::new(
+                    Session { handler: &handler },
+                    syntax,
+                    SourceFileInput::from(&*src),
+                    None,
+                )
+                .parse_module()
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+                let expected = ::testing::drop_span(expected.fold_with(&mut Normalizer));
+                let actual = ::testing::drop_span(actual.fold_with(&mut Normalizer));
+                assert_eq!(expected, actual);
+
+                Ok(())
+            })
+            .expect("failed to run test");
+        });
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reparse() {
+    let args: Vec<_> = env::args().collect();
+    let mut tests = Vec::new();
+    reparse_tests(&mut tests).expect("failed to load tests");
+    test_main(&args, tests, Some(Options::new()));
+}
+
+/// Removes differences which are not visible in the source code.
+struct Normalizer;
+
+impl Fold<Vec<ModuleItem>> for Normalizer {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        // The emitter appends `;` to `export default class A {}`.
+        items
+            .fold_children(self)
+            .into_iter()
+            .filter(|item| match *item {
+                ModuleItem::Stmt(Stmt::Empty(..)) => false,
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+impl Fold<Str> for Normalizer {
+    fn fold(&mut self, s: Str) -> Str {
+        Str {
+            has_escape: false,
+            ..s
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Buf(Arc<RwLock<Vec<u8>>>);
+impl Write for Buf {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.write().unwrap().write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.write().unwrap().flush()
+    }
+}
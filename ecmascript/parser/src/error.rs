@@ -85,6 +85,7 @@ pub enum SyntaxError {
         left: String,
         left_span: Span,
     },
+    NullishCoalescingWithLogicalOp,
     Hash,
     LineBreakInThrow,
     LineBreakBeforeArrow,
@@ -155,6 +156,7 @@ pub enum SyntaxError {
 
     TrailingCommaInsideImport,
     DynamicImport,
+    ImportMeta,
 
     ExportDefaultWithOutFrom,
     ExportNamespaceFrom,
@@ -262,6 +264,10 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
                                          identifier in strict mode"
                 .into(),
             UnaryInExp { .. } => "** cannot be applied to unary expression".into(),
+            NullishCoalescingWithLogicalOp => {
+                "Nullish coalescing operator(??) requires parens when mixing with logical operators"
+                    .into()
+            }
             Hash => "Unexpected token '#'".into(),
             LineBreakInThrow => "LineBreak cannot follow 'throw'".into(),
             LineBreakBeforeArrow => "Unexpected line break between arrow head and arrow".into(),
@@ -346,6 +352,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             DynamicImport => {
                 "import(...) expressions requires `jsc.parser.dynamicImport` to be true".into()
             }
+            ImportMeta => "import.meta requires `jsc.parser.importMeta` to be true and is only \
+                           allowed in modules"
+                .into(),
             ExportDefaultWithOutFrom => "export default statements required from '...';".into(),
             ExportNamespaceFrom => "export * as Foo from 'foo'; requires \
                                     `jsc.parser.exportNamespaceFrom` to be true"
@@ -179,7 +179,18 @@ impl<'a, I: Input> Lexer<'a, I> {
                 return Ok(Some(tok!('.')));
             }
 
-            '(' | ')' | ';' | ',' | '[' | ']' | '{' | '}' | '@' | '?' => {
+            '?' => {
+                self.input.bump();
+
+                if self.syntax.nullish_coalescing() && self.input.cur() == Some('?') {
+                    self.input.bump();
+                    return Ok(Some(tok!("??")));
+                }
+
+                return Ok(Some(tok!('?')));
+            }
+
+            '(' | ')' | ';' | ',' | '[' | ']' | '{' | '}' | '@' => {
                 // These tokens are emitted directly.
                 self.input.bump();
                 return Ok(Some(match c {
@@ -192,7 +203,6 @@ impl<'a, I: Input> Lexer<'a, I> {
                     '{' => LBrace,
                     '}' => RBrace,
                     '@' => At,
-                    '?' => QuestionMark,
                     _ => unreachable!(),
                 }));
             }
@@ -775,12 +785,15 @@ impl<'a, I: Input> Lexer<'a, I> {
         let mut out = String::new();
         let mut has_escape = false;
 
+        // U+2028 and U+2029 are allowed in string literals since es2019.
+        let is_line_break = |c: char| c == '\r' || c == '\n';
+
         while let Some(c) = {
             // Optimization
             {
                 let s = self
                     .input
-                    .uncons_while(|c| c != quote && c != '\\' && !c.is_line_break());
+                    .uncons_while(|c| c != quote && c != '\\' && !is_line_break(c));
                 out.push_str(s);
             }
             self.cur()
@@ -799,7 +812,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     }
                     has_escape = true
                 }
-                c if c.is_line_break() => self.error(start, SyntaxError::UnterminatedStrLit)?,
+                c if is_line_break(c) => self.error(start, SyntaxError::UnterminatedStrLit)?,
                 _ => {
                     out.push(c);
                     self.bump();
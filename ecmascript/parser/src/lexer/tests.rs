@@ -278,6 +278,17 @@ fn str_escape_2() {
     );
 }
 
+#[test]
+fn str_line_separator() {
+    assert_eq!(
+        lex_tokens(Syntax::default(), "'a\u{2028}b\u{2029}'"),
+        vec![Token::Str {
+            value: "a\u{2028}b\u{2029}".into(),
+            has_escape: false
+        }]
+    );
+}
+
 #[test]
 fn str_escape_hex() {
     assert_eq!(
@@ -1038,6 +1049,37 @@ fn issue_481() {
     );
 }
 
+#[test]
+fn nullish_coalescing() {
+    assert_eq!(
+        lex_tokens(
+            crate::Syntax::Es(crate::EsConfig {
+                nullish_coalescing: true,
+                ..Default::default()
+            }),
+            "a ?? b"
+        ),
+        vec![
+            Word(Word::Ident("a".into())),
+            tok!("??"),
+            Word(Word::Ident("b".into())),
+        ]
+    );
+}
+
+#[test]
+fn nullish_coalescing_disabled() {
+    assert_eq!(
+        lex_tokens(Default::default(), "a ?? b"),
+        vec![
+            Word(Word::Ident("a".into())),
+            tok!('?'),
+            tok!('?'),
+            Word(Word::Ident("b".into())),
+        ]
+    );
+}
+
 #[bench]
 fn lex_colors_js(b: &mut Bencher) {
     b.bytes = include_str!("../../colors.js").len() as _;
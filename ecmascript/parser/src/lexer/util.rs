@@ -229,6 +229,8 @@ impl<'a, I: Input> Lexer<'a, I> {
                 if let Some(ref comments) = self.comments {
                     let src = self.input.slice(slice_start, pos);
                     let s = &src[..src.len() - 2];
+                    // Annotations like `/*#__PURE__*/` belong to the next expression.
+                    let is_for_next = is_for_next || is_pure_annotation(s);
                     let cmt = Comment {
                         kind: CommentKind::Block,
                         span: Span::new(start, pos, SyntaxContext::empty()),
@@ -255,6 +257,14 @@ impl<'a, I: Input> Lexer<'a, I> {
     }
 }
 
+/// Returns true for `#__PURE__` and `@__PURE__`.
+fn is_pure_annotation(text: &str) -> bool {
+    match text.trim() {
+        "#__PURE__" | "@__PURE__" => true,
+        _ => false,
+    }
+}
+
 /// Implemented for `char`.
 pub trait CharExt: Copy {
     fn to_char(self) -> Option<char>;
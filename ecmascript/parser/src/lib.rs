@@ -151,6 +151,18 @@ impl Syntax {
         }
     }
 
+    pub fn import_meta(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                import_meta: true, ..
+            })
+            | Syntax::Typescript(TsConfig {
+                import_meta: true, ..
+            }) => true,
+            _ => false,
+        }
+    }
+
     pub fn fn_bind(self) -> bool {
         match self {
             Syntax::Es(EsConfig { fn_bind: true, .. }) => true,
@@ -247,6 +259,30 @@ impl Syntax {
             _ => false,
         }
     }
+
+    /// `a?.b`
+    pub fn optional_chaining(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                optional_chaining: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+            _ => false,
+        }
+    }
+
+    /// `a ?? b`
+    pub fn nullish_coalescing(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                nullish_coalescing: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -260,6 +296,9 @@ pub struct TsConfig {
 
     #[serde(default)]
     pub dynamic_import: bool,
+
+    #[serde(default)]
+    pub import_meta: bool,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
@@ -278,6 +317,8 @@ pub enum JscTarget {
     Es2018,
     #[serde(rename = "es2019")]
     Es2019,
+    #[serde(rename = "es2020")]
+    Es2020,
 }
 
 impl Default for JscTarget {
@@ -332,6 +373,18 @@ pub struct EsConfig {
 
     #[serde(default)]
     pub dynamic_import: bool,
+
+    /// Support `import.meta`.
+    #[serde(default)]
+    pub import_meta: bool,
+
+    /// Support nullish coalescing operator (`??`).
+    #[serde(default)]
+    pub nullish_coalescing: bool,
+
+    /// Support optional chaining (`a?.b`).
+    #[serde(default)]
+    pub optional_chaining: bool,
 }
 
 /// Syntactic context.
@@ -36,6 +36,9 @@ macro_rules! tok {
     ('?') => {
         crate::token::Token::QuestionMark
     };
+    ("??") => {
+        crate::token::Token::BinOp(crate::token::BinOpToken::NullishCoalescing)
+    };
     (':') => {
         crate::token::Token::Colon
     };
@@ -188,6 +191,9 @@ macro_rules! tok {
     ("let") => {
         crate::token::Token::Word(crate::token::Word::Keyword(crate::token::Keyword::Let))
     };
+    ("meta") => {
+        crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("meta")))
+    };
     ("new") => {
         crate::token::Token::Word(crate::token::Word::Keyword(crate::token::Keyword::New))
     };
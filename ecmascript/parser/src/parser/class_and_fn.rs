@@ -315,7 +315,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 )));
                 let is_optional = self.input.syntax().typescript() && eat!('?');
                 return self.make_method(
-                    |p| p.parse_unique_formal_params(),
+                    |p| p.parse_method_params(),
                     MakeMethodArgs {
                         start,
                         accessibility,
@@ -390,7 +390,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 unexpected!();
             }
             return self.make_method(
-                |p| p.parse_unique_formal_params(),
+                |p| p.parse_method_params(),
                 MakeMethodArgs {
                     start,
                     decorators,
@@ -470,8 +470,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
                         // TODO: Search deeply for assignment pattern using a Visitor
 
                         let span = match *p {
-                            PatOrTsParamProp::Pat(Pat::Assign(ref p)) => Some(p.span()),
-                            PatOrTsParamProp::TsParamProp(TsParamProp {
+                            ParamOrTsParamProp::Param(Param {
+                                pat: Pat::Assign(ref p),
+                                ..
+                            }) => Some(p.span()),
+                            ParamOrTsParamProp::TsParamProp(TsParamProp {
                                 param: TsParamPropParam::Assign(ref p),
                                 ..
                             }) => Some(p.span()),
@@ -498,7 +501,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }));
             } else {
                 return self.make_method(
-                    |p| p.parse_formal_params(),
+                    |p| p.parse_method_params(),
                     MakeMethodArgs {
                         start,
                         is_optional,
@@ -546,7 +549,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
             // handle async foo(){}
             return self.make_method(
-                |p| p.parse_unique_formal_params(),
+                |p| p.parse_method_params(),
                 MakeMethodArgs {
                     start,
                     is_static,
@@ -581,7 +584,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 return match i.sym {
                     js_word!("get") => self.make_method(
                         |p| {
-                            let params = p.parse_formal_params()?;
+                            let params = p.parse_method_params()?;
 
                             if params.len() != 0 {
                                 p.emit_err(key_span, SyntaxError::TS1094);
@@ -604,14 +607,14 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     ),
                     js_word!("set") => self.make_method(
                         |p| {
-                            let params = p.parse_formal_params()?;
+                            let params = p.parse_method_params()?;
 
                             if params.len() != 1 {
                                 p.emit_err(key_span, SyntaxError::TS1094);
                             }
 
                             if !params.is_empty() {
-                                if let Pat::Rest(..) = params[0] {
+                                if let Pat::Rest(..) = params[0].pat {
                                     p.emit_err(params[0].span(), SyntaxError::RestPatInSetter);
                                 }
                             }
@@ -810,7 +813,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         is_generator: bool,
     ) -> PResult<'a, Function>
     where
-        F: FnOnce(&mut Self) -> PResult<'a, Vec<Pat>>,
+        F: FnOnce(&mut Self) -> PResult<'a, Vec<Param>>,
     {
         let ctx = Context {
             in_async: is_async,
@@ -848,10 +851,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
             if p.syntax().typescript() && body.is_none() {
                 // Declare functions cannot have assignment pattern in parameters
-                for pat in &params {
+                for param in &params {
                     // TODO: Search deeply for assignment pattern using a Visitor
 
-                    let span = match *pat {
+                    let span = match param.pat {
                         Pat::Assign(ref p) => Some(p.span()),
                         _ => None,
                     };
@@ -929,7 +932,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }: MakeMethodArgs,
     ) -> PResult<'a, ClassMember>
     where
-        F: FnOnce(&mut Self) -> PResult<'a, Vec<Pat>>,
+        F: FnOnce(&mut Self) -> PResult<'a, Vec<Param>>,
     {
         let ctx = Context {
             span_of_fn_name: Some(key.span()),
@@ -205,6 +205,9 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         if eat!("import") {
+            if is!('.') {
+                return self.parse_import_meta(start);
+            }
             return self.parse_dynamic_import(start);
         }
 
@@ -863,13 +866,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         let is_optional_chaining =
-            self.input.syntax().typescript() && is!('?') && peeked_is!('.') && eat!('?');
+            self.input.syntax().optional_chaining() && is!('?') && peeked_is!('.') && eat!('?');
 
         /// Wrap with optional chaining
         macro_rules! wrap {
             ($e:expr) => {{
                 if is_optional_chaining {
-                    Expr::TsOptChain(TsOptChain {
+                    Expr::OptChain(OptChainExpr {
                         span: span!(self, start),
                         expr: Box::new($e),
                     })
@@ -1416,6 +1419,24 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.parse_subscripts(ExprOrSuper::Expr(import), true)
     }
 
+    /// Parses `import.meta`. `import` should be already consumed.
+    pub(super) fn parse_import_meta(&mut self, start: BytePos) -> PResult<'a, Box<Expr>> {
+        if !self.input.syntax().import_meta() || !self.ctx().module {
+            syntax_error!(span!(start), SyntaxError::ImportMeta);
+        }
+        let span_of_import = span!(start);
+        expect!('.');
+
+        let start_of_meta = cur_pos!();
+        if !eat!("meta") {
+            unexpected!()
+        }
+        Ok(Box::new(Expr::MetaProp(MetaPropExpr {
+            meta: Ident::new(js_word!("import"), span_of_import),
+            prop: Ident::new(js_word!("meta"), span!(start_of_meta)),
+        })))
+    }
+
     pub(super) fn check_assign_target(&mut self, expr: &Expr, deny_call: bool) {
         // We follow behavior of tsc
         if self.input.syntax().typescript() {
@@ -155,6 +155,28 @@ impl<'a, I: Tokens> Parser<'a, I> {
             )?
         };
 
+        // `??` cannot be mixed with `||` or `&&` without parentheses.
+        {
+            let is_logical = |op: BinaryOp| op == op!("||") || op == op!("&&");
+            let is_invalid = |e: &Expr| match *e {
+                Expr::Bin(BinExpr { op: child, .. }) => {
+                    if op == op!("??") {
+                        is_logical(child)
+                    } else {
+                        is_logical(op) && child == op!("??")
+                    }
+                }
+                _ => false,
+            };
+
+            if is_invalid(&*left) || is_invalid(&*right) {
+                self.emit_err(
+                    Span::new(left.span().lo(), right.span().hi(), Default::default()),
+                    SyntaxError::NullishCoalescingWithLogicalOp,
+                );
+            }
+        }
+
         let node = Box::new(Expr::Bin(BinExpr {
             span: Span::new(left.span().lo(), right.span().hi(), Default::default()),
             op,
@@ -315,6 +337,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EsConfig;
     use swc_common::DUMMY_SP as span;
 
     fn bin(s: &'static str) -> Box<Expr> {
@@ -350,4 +373,57 @@ mod tests {
             }))
         );
     }
+
+    fn nullish(s: &'static str) -> Box<Expr> {
+        test_parser(
+            s,
+            Syntax::Es(EsConfig {
+                nullish_coalescing: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.parse_expr().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        )
+    }
+
+    #[test]
+    fn nullish_coalescing() {
+        testing::assert_eq_ignore_span!(
+            nullish("a ?? b ?? c"),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("??"),
+                left: nullish("a ?? b"),
+                right: nullish("c"),
+            }))
+        );
+    }
+
+    #[test]
+    fn nullish_coalescing_with_parens() {
+        testing::assert_eq_ignore_span!(
+            nullish("(a || b) ?? c"),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("??"),
+                left: nullish("(a || b)"),
+                right: nullish("c"),
+            }))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Nullish coalescing operator(??) requires parens")]
+    fn nullish_coalescing_mixed_with_or() {
+        nullish("a || b ?? c");
+    }
+
+    #[test]
+    #[should_panic(expected = "Nullish coalescing operator(??) requires parens")]
+    fn nullish_coalescing_mixed_with_and() {
+        nullish("a ?? b && c");
+    }
 }
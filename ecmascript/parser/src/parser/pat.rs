@@ -213,7 +213,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(pat)
     }
 
-    pub(super) fn parse_constructor_params(&mut self) -> PResult<'a, Vec<PatOrTsParamProp>> {
+    pub(super) fn parse_constructor_params(&mut self) -> PResult<'a, Vec<ParamOrTsParamProp>> {
         let mut first = true;
         let mut params = vec![];
 
@@ -228,6 +228,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
+            let decorators = self.parse_decorators(false)?;
             let start = cur_pos!();
 
             if eat!("...") {
@@ -246,19 +247,20 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     arg: Box::new(pat),
                     type_ann,
                 });
-                params.push(PatOrTsParamProp::Pat(pat));
+                params.push(ParamOrTsParamProp::Param(make_param(decorators, pat)));
                 break;
             } else {
-                params.push(self.parse_constructor_param()?);
+                params.push(self.parse_constructor_param(decorators)?);
             }
         }
 
         Ok(params)
     }
 
-    fn parse_constructor_param(&mut self) -> PResult<'a, PatOrTsParamProp> {
-        let decorators = self.parse_decorators(false)?;
-
+    fn parse_constructor_param(
+        &mut self,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, ParamOrTsParamProp> {
         let start = cur_pos!();
         let (accessibility, readonly) = if self.input.syntax().typescript() {
             let accessibility = self.parse_access_modifier()?;
@@ -270,9 +272,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
             (None, false)
         };
         if accessibility == None && !readonly {
-            self.parse_formal_param().map(PatOrTsParamProp::from)
+            let pat = self.parse_formal_param()?;
+            Ok(ParamOrTsParamProp::Param(make_param(decorators, pat)))
         } else {
-            Ok(PatOrTsParamProp::TsParamProp(TsParamProp {
+            Ok(ParamOrTsParamProp::TsParamProp(TsParamProp {
                 span: span!(start),
                 accessibility,
                 readonly,
@@ -286,7 +289,16 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
     }
 
-    pub(super) fn parse_formal_params(&mut self) -> PResult<'a, Vec<Pat>> {
+    pub(super) fn parse_formal_params(&mut self) -> PResult<'a, Vec<Param>> {
+        self.parse_params(false)
+    }
+
+    /// Parses parameters of a class method, which may have decorators.
+    pub(super) fn parse_method_params(&mut self) -> PResult<'a, Vec<Param>> {
+        self.parse_params(true)
+    }
+
+    fn parse_params(&mut self, allow_decorators: bool) -> PResult<'a, Vec<Param>> {
         let mut first = true;
         let mut params = vec![];
         let mut dot3_token = Span::default();
@@ -309,6 +321,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
+            let decorators = if allow_decorators {
+                self.parse_decorators(false)?
+            } else {
+                vec![]
+            };
             let start = cur_pos!();
 
             if !dot3_token.is_dummy() {
@@ -345,7 +362,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     arg: Box::new(pat),
                     type_ann,
                 });
-                params.push(pat);
+                params.push(make_param(decorators, pat));
 
                 if self.syntax().typescript() && eat!('?') {
                     self.emit_err(self.input.prev_span(), SyntaxError::TS1047);
@@ -358,17 +375,34 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 continue;
             }
 
-            params.push(self.parse_formal_param()?);
+            let pat = self.parse_formal_param()?;
+            params.push(make_param(decorators, pat));
         }
 
         Ok(params)
     }
 
-    pub(super) fn parse_unique_formal_params(&mut self) -> PResult<'a, Vec<Pat>> {
+    pub(super) fn parse_unique_formal_params(&mut self) -> PResult<'a, Vec<Param>> {
         // FIXME: This is wrong
         self.parse_formal_params()
     }
 }
+
+/// Creates a parameter which spans from its first decorator to the end of
+/// `pat`.
+fn make_param(decorators: Vec<Decorator>, pat: Pat) -> Param {
+    let span = match decorators.first() {
+        Some(decorator) => pat.span().with_lo(decorator.span.lo()),
+        None => pat.span(),
+    };
+
+    Param {
+        span,
+        decorators,
+        pat,
+    }
+}
+
 ///
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PatType {
@@ -1081,7 +1081,16 @@ pub(super) trait StmtLikeParser<'a, Type: IsDirective> {
 
 #[parser]
 impl<'a, I: Tokens> StmtLikeParser<'a, Stmt> for Parser<'a, I> {
-    fn handle_import_export(&mut self, top_level: bool, _: Vec<Decorator>) -> PResult<'a, Stmt> {
+    fn handle_import_export(
+        &mut self,
+        top_level: bool,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, Stmt> {
+        // `import.meta` is an expression.
+        if is!("import") && peeked_is!('.') {
+            return self.parse_stmt_internal(true, top_level, decorators);
+        }
+
         let start = cur_pos!();
         if self.input.syntax().dynamic_import() && is!("import") {
             let expr = self.parse_primary_expr()?;
@@ -1624,4 +1633,24 @@ export default function waitUntil(callback, options = {}) {
             },
         );
     }
+
+    #[test]
+    fn import_meta() {
+        test_parser(
+            "import.meta.hot.accept();
+            const url = new URL('./foo', import.meta.url);
+            function f() {
+                import.meta.foo = 1;
+            }",
+            Syntax::Es(EsConfig {
+                import_meta: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.parse_module().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        );
+    }
 }
@@ -480,6 +480,13 @@ impl<'a, I: Tokens> StmtLikeParser<'a, ModuleItem> for Parser<'a, I> {
         top_level: bool,
         decorators: Vec<Decorator>,
     ) -> PResult<'a, ModuleItem> {
+        // `import.meta` is an expression.
+        if is!("import") && peeked_is!('.') {
+            return self
+                .parse_stmt_internal(true, top_level, decorators)
+                .map(ModuleItem::from);
+        }
+
         if !top_level {
             syntax_error!(SyntaxError::NonTopLevelImportExport);
         }
@@ -242,9 +242,12 @@ pub(super) trait ExprExt {
             | Expr::JSXElement(..)
             | Expr::JSXFragment(..) => false,
 
+            Expr::OptChain(OptChainExpr { ref expr, .. }) => {
+                expr.is_valid_simple_assignment_target(strict)
+            }
+
             // typescript
-            Expr::TsOptChain(TsOptChain { ref expr, .. })
-            | Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
+            Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
             | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
             | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. })
             | Expr::TsAs(TsAsExpr { ref expr, .. }) => {
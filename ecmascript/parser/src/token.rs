@@ -203,6 +203,8 @@ pub enum BinOpToken {
     LogicalOr,
     /// `&&`
     LogicalAnd,
+    /// `??`
+    NullishCoalescing,
 }
 
 impl BinOpToken {
@@ -538,6 +540,7 @@ impl From<BinOpToken> for BinaryOp {
             BinOpToken::BitAnd => BitAnd,
             BinOpToken::LogicalOr => LogicalOr,
             BinOpToken::LogicalAnd => LogicalAnd,
+            BinOpToken::NullishCoalescing => NullishCoalescing,
             BinOpToken::Exp => Exp,
         }
     }
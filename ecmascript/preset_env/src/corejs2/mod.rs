@@ -2,10 +2,12 @@ use self::{
     builtin::BUILTINS,
     data::{BUILTIN_TYPES, INSTANCE_PROPERTIES, STATIC_PROPERTIES},
 };
-use crate::Versions;
+use crate::{util::collect_bindings, Versions};
+use hashbrown::HashSet;
 use swc_atoms::{js_word, JsWord};
 use swc_common::{Visit, VisitWith};
 use swc_ecma_ast::*;
+use swc_ecma_transforms::util::{id, Id};
 
 mod builtin;
 mod data;
@@ -13,6 +15,8 @@ mod data;
 pub(super) struct UsageVisitor<'a> {
     is_any_target: bool,
     target: &'a Versions,
+    /// Local bindings, which shadow builtins.
+    bindings: HashSet<Id>,
     pub required: Vec<JsWord>,
 }
 
@@ -40,10 +44,16 @@ impl<'a> UsageVisitor<'a> {
         Self {
             is_any_target: target.is_any_target(),
             target,
+            bindings: Default::default(),
             required: vec![],
         }
     }
 
+    /// Returns true if `i` does not refer to a local binding.
+    fn is_global(&self, i: &Ident) -> bool {
+        !self.bindings.contains(&id(i))
+    }
+
     /// Add imports
     fn add(&mut self, features: &[&str]) {
         for f in features {
@@ -90,10 +100,19 @@ impl<'a> UsageVisitor<'a> {
 //      });
 //    },
 
+impl Visit<Module> for UsageVisitor<'_> {
+    fn visit(&mut self, m: &Module) {
+        self.bindings = collect_bindings(m);
+        m.visit_children(self);
+    }
+}
+
 /// Detects usage of types
 impl Visit<Ident> for UsageVisitor<'_> {
     fn visit(&mut self, node: &Ident) {
-        node.visit_children(self);
+        if !self.is_global(node) {
+            return;
+        }
 
         for (name, builtin) in BUILTIN_TYPES {
             if node.sym == **name {
@@ -108,7 +127,10 @@ impl Visit<Ident> for UsageVisitor<'_> {
 ///  - `Array.from`
 impl Visit<MemberExpr> for UsageVisitor<'_> {
     fn visit(&mut self, node: &MemberExpr) {
-        node.visit_children(self);
+        node.obj.visit_with(self);
+        if node.computed {
+            node.prop.visit_with(self);
+        }
         //enter(path: NodePath) {
         //    const { node } = path;
         //    const { object, property } = node;
@@ -192,7 +214,7 @@ impl Visit<MemberExpr> for UsageVisitor<'_> {
         }
 
         match node.obj {
-            ExprOrSuper::Expr(box Expr::Ident(ref obj)) => {
+            ExprOrSuper::Expr(box Expr::Ident(ref obj)) if self.is_global(obj) => {
                 for (ty, props) in STATIC_PROPERTIES {
                     if obj.sym == **ty {
                         match *node.prop {
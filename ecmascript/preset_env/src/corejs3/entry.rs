@@ -0,0 +1,209 @@
+use super::{
+    builtin::BUILTINS,
+    data::{BUILTIN_TYPES, STATIC_PROPERTIES},
+    is_required,
+};
+use crate::Versions;
+use swc_atoms::JsWord;
+use swc_common::{Fold, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Replaces `import 'core-js/stable'` and friends with imports of modules
+/// required by the targets.
+pub(crate) struct Entry<'a> {
+    is_any_target: bool,
+    target: &'a Versions,
+    /// Keep `import 'regenerator-runtime/runtime'`?
+    regenerator: bool,
+    imported: Vec<&'static str>,
+}
+
+impl<'a> Entry<'a> {
+    pub fn new(target: &'a Versions, regenerator: bool) -> Self {
+        Entry {
+            is_any_target: target.is_any_target(),
+            target,
+            regenerator,
+            imported: vec![],
+        }
+    }
+
+    /// Returns `None` if `src` is not an entry of core-js.
+    fn expand(&self, src: &str) -> Option<Vec<&'static str>> {
+        let src = normalize(src);
+        let mut parts = src.split('/');
+        if parts.next() != Some("core-js") {
+            return None;
+        }
+        let level = parts.next();
+        let ns = parts.next();
+        let method = parts.next();
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let mut modules: Vec<&'static str> = match (level, ns, method) {
+            (None, ..) | (Some("features"), None, _) => all().collect(),
+            (Some("stable"), None, _) => all().filter(|m| !m.starts_with("esnext.")).collect(),
+            (Some("es"), None, _) => all().filter(|m| m.starts_with("es.")).collect(),
+            (Some("web"), None, _) => all().filter(|m| m.starts_with("web.")).collect(),
+            (Some("proposals"), None, _) | (Some("stage"), ..) => {
+                all().filter(|m| m.starts_with("esnext.")).collect()
+            }
+            (Some("modules"), Some(name), None) => all().filter(|m| *m == name).collect(),
+            (Some(level @ "es"), Some(ns), method)
+            | (Some(level @ "stable"), Some(ns), method)
+            | (Some(level @ "features"), Some(ns), method) => {
+                let mut modules = namespace(ns, method);
+                if level != "features" {
+                    modules.retain(|m| !m.starts_with("esnext."));
+                }
+                modules
+            }
+            _ => vec![],
+        };
+
+        if modules.is_empty() {
+            return None;
+        }
+
+        modules.retain(|m| self.is_any_target || is_required(self.target, m));
+        modules.sort();
+        Some(modules)
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Entry<'_> {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let mut buf = Vec::with_capacity(items.len());
+
+        for item in items {
+            let (span, src) = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    ref specifiers,
+                    ref src,
+                })) if specifiers.is_empty() => (span, src.value.clone()),
+                _ => {
+                    buf.push(item);
+                    continue;
+                }
+            };
+
+            if &*src == "regenerator-runtime/runtime" && !self.regenerator {
+                continue;
+            }
+
+            let modules = match self.expand(&src) {
+                Some(modules) => modules,
+                None => {
+                    buf.push(item);
+                    continue;
+                }
+            };
+
+            for m in modules {
+                if self.imported.contains(&m) {
+                    continue;
+                }
+                self.imported.push(m);
+
+                let src: JsWord = format!("core-js/modules/{}", m).into();
+                buf.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    specifiers: vec![],
+                    src: Str {
+                        span: DUMMY_SP,
+                        value: src,
+                        has_escape: false,
+                    },
+                })));
+            }
+        }
+
+        buf
+    }
+}
+
+fn all() -> impl Iterator<Item = &'static str> {
+    BUILTINS.keys().map(|s| &**s)
+}
+
+/// Modules required by `core-js/es/map` or `core-js/es/array/from`.
+fn namespace(ns: &str, method: Option<&str>) -> Vec<&'static str> {
+    let mut modules = vec![];
+
+    match method {
+        None => {
+            let es = format!("es.{}", ns);
+            let esnext = format!("esnext.{}", ns);
+            modules.extend(all().filter(|m| {
+                *m == es
+                    || *m == esnext
+                    || m.starts_with(&format!("{}.", es))
+                    || m.starts_with(&format!("{}.", esnext))
+            }));
+
+            for (ty, deps) in BUILTIN_TYPES {
+                if kebab_case(ty) == ns {
+                    modules.extend(deps.iter().cloned());
+                }
+            }
+        }
+        Some(method) => {
+            let es = format!("es.{}.{}", ns, method);
+            let esnext = format!("esnext.{}.{}", ns, method);
+            modules.extend(all().filter(|m| *m == es || *m == esnext));
+
+            for (ty, props) in STATIC_PROPERTIES {
+                if kebab_case(ty) != ns {
+                    continue;
+                }
+                for (prop, deps) in *props {
+                    if kebab_case(prop) == method {
+                        modules.extend(deps.iter().cloned());
+                    }
+                }
+            }
+        }
+    }
+
+    modules.sort();
+    modules.dedup();
+    modules
+}
+
+/// `core-js\es\Map\index.js` => `core-js/es/map`
+fn normalize(src: &str) -> String {
+    let mut src = src.to_lowercase().replace('\\', "/");
+
+    loop {
+        let len = src.len();
+        for suffix in &["/", ".js", "/index"] {
+            if src.ends_with(suffix) {
+                src.truncate(src.len() - suffix.len());
+            }
+        }
+        if src.len() == len {
+            return src;
+        }
+    }
+}
+
+/// `WeakMap` => `weak-map`
+fn kebab_case(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 4);
+
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                buf.push('-');
+            }
+            buf.push(c.to_ascii_lowercase());
+        } else {
+            buf.push(c);
+        }
+    }
+
+    buf
+}
@@ -1 +1,260 @@
+pub(crate) use self::entry::Entry;
+use self::{
+    builtin::BUILTINS,
+    data::{
+        BUILTIN_TYPES, COMMON_ITERATORS, INSTANCE_PROPERTIES, SHIPPED_PROPOSALS, STATIC_PROPERTIES,
+    },
+};
+use crate::{util::collect_bindings, Versions};
+use hashbrown::HashSet;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::util::{id, Id};
 
+mod builtin;
+mod data;
+mod entry;
+
+pub(super) struct UsageVisitor<'a> {
+    is_any_target: bool,
+    target: &'a Versions,
+    /// Local bindings, which shadow builtins.
+    bindings: HashSet<Id>,
+    shipped_proposals: bool,
+    proposals: bool,
+    pub required: Vec<JsWord>,
+}
+
+impl<'a> UsageVisitor<'a> {
+    pub fn new(target: &'a Versions, shipped_proposals: bool, proposals: bool) -> Self {
+        Self {
+            is_any_target: target.is_any_target(),
+            target,
+            bindings: Default::default(),
+            shipped_proposals,
+            proposals,
+            required: vec![],
+        }
+    }
+
+    /// Returns true if `i` does not refer to a local binding.
+    fn is_global(&self, i: &Ident) -> bool {
+        !self.bindings.contains(&id(i))
+    }
+
+    /// Add imports
+    fn add(&mut self, features: &[&str]) {
+        for f in features {
+            if f.starts_with("esnext.")
+                && !self.proposals
+                && !(self.shipped_proposals && SHIPPED_PROPOSALS.contains(f))
+            {
+                continue;
+            }
+
+            if !self.is_any_target && !is_required(self.target, f) {
+                continue;
+            }
+
+            let v = format!("core-js/modules/{}", f);
+
+            if self.required.iter().all(|import| *import != *v) {
+                self.required.push(v.into())
+            }
+        }
+    }
+
+    fn add_property_deps(&mut self, obj: Option<&JsWord>, prop: &str) {
+        if let Some(obj) = obj {
+            for (ty, props) in STATIC_PROPERTIES {
+                if *obj == **ty {
+                    for (name, imports) in *props {
+                        if prop == *name {
+                            self.add(imports);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (name, imports) in INSTANCE_PROPERTIES {
+            if prop == *name {
+                self.add(imports);
+            }
+        }
+    }
+}
+
+/// Returns true if at least one of the targeted platforms does not ship
+/// `module`.
+pub(crate) fn is_required(target: &Versions, module: &str) -> bool {
+    let v = match BUILTINS.get(module) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    !v.iter().zip(target.iter()).all(|((_, fv), (_, tv))| {
+        // fv: feature's version
+        // tv: target's version
+
+        // We are not targeting the platform. So ignore it.
+        if tv.is_none() {
+            return true;
+        }
+
+        // Not supported by browser (even on latest version)
+        if fv.is_none() {
+            return false;
+        }
+
+        *fv <= *tv
+    })
+}
+
+impl Visit<Module> for UsageVisitor<'_> {
+    fn visit(&mut self, m: &Module) {
+        self.bindings = collect_bindings(m);
+        m.visit_children(self);
+    }
+}
+
+/// Detects usage of types
+impl Visit<Ident> for UsageVisitor<'_> {
+    fn visit(&mut self, node: &Ident) {
+        if !self.is_global(node) {
+            return;
+        }
+
+        for (name, builtin) in BUILTIN_TYPES {
+            if node.sym == **name {
+                self.add(builtin)
+            }
+        }
+    }
+}
+
+/// Detects usage of instance properties and static properties.
+///
+///  - `Array.from`
+///  - `a.includes()`
+///  - `b['find']`
+impl Visit<MemberExpr> for UsageVisitor<'_> {
+    fn visit(&mut self, node: &MemberExpr) {
+        node.obj.visit_with(self);
+        if node.computed {
+            node.prop.visit_with(self);
+        }
+
+        let obj = match node.obj {
+            ExprOrSuper::Expr(box Expr::Ident(ref obj)) if self.is_global(obj) => Some(&obj.sym),
+            _ => None,
+        };
+
+        match *node.prop {
+            Expr::Ident(ref i) if !node.computed => self.add_property_deps(obj, &i.sym),
+            Expr::Lit(Lit::Str(Str { ref value, .. })) if node.computed => {
+                self.add_property_deps(obj, value)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// - `var { repeat, startsWith } = String`
+impl Visit<VarDeclarator> for UsageVisitor<'_> {
+    fn visit(&mut self, v: &VarDeclarator) {
+        v.visit_children(self);
+
+        let props = match v.name {
+            Pat::Object(ObjectPat { ref props, .. }) => props,
+            _ => return,
+        };
+        let obj = match v.init {
+            Some(box Expr::Ident(ref obj)) if self.is_global(obj) => Some(&obj.sym),
+            _ => None,
+        };
+
+        for prop in props {
+            match *prop {
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(ref key),
+                    ..
+                }) => self.add_property_deps(obj, &key.sym),
+                ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => {
+                    self.add_property_deps(obj, &key.sym)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+///
+/// - `Symbol.iterator in arr`
+/// - `'defineProperty' in Object`
+impl Visit<BinExpr> for UsageVisitor<'_> {
+    fn visit(&mut self, e: &BinExpr) {
+        e.visit_children(self);
+
+        if e.op != op!("in") {
+            return;
+        }
+
+        if is_symbol_iterator(&e.left) {
+            self.add(COMMON_ITERATORS);
+            return;
+        }
+
+        match (&*e.left, &*e.right) {
+            (Expr::Lit(Lit::Str(Str { ref value, .. })), Expr::Ident(ref obj))
+                if self.is_global(obj) =>
+            {
+                self.add_property_deps(Some(&obj.sym), value)
+            }
+            _ => {}
+        }
+    }
+}
+
+///
+/// - `yield*`
+impl Visit<YieldExpr> for UsageVisitor<'_> {
+    fn visit(&mut self, e: &YieldExpr) {
+        e.visit_children(self);
+
+        if e.delegate {
+            self.add(COMMON_ITERATORS)
+        }
+    }
+}
+
+///
+/// - `for (const x of arr)`
+impl Visit<ForOfStmt> for UsageVisitor<'_> {
+    fn visit(&mut self, s: &ForOfStmt) {
+        s.visit_children(self);
+
+        self.add(COMMON_ITERATORS)
+    }
+}
+
+fn is_symbol_iterator(e: &Expr) -> bool {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj:
+                ExprOrSuper::Expr(box Expr::Ident(Ident {
+                    sym: js_word!("Symbol"),
+                    ..
+                })),
+            prop:
+                box Expr::Ident(Ident {
+                    sym: js_word!("iterator"),
+                    ..
+                }),
+            computed: false,
+            ..
+        }) => true,
+        _ => false,
+    }
+}
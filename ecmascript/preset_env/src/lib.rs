@@ -4,29 +4,53 @@
 
 pub use self::transform_data::{parse_version, Feature};
 use semver::Version;
-use serde::Deserialize;
+use serde::{de, Deserialize, Deserializer};
 use st_map::StaticMap;
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    env,
+    path::PathBuf,
+};
 use swc_atoms::JsWord;
-use swc_common::{chain, Fold, FromVariant, VisitWith, DUMMY_SP};
+use swc_common::{chain, Fold, FoldWith, FromVariant, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
-    compat::{es2015, es2016, es2017, es2018, es3},
+    compat::{es2015, es2016, es2017, es2018, es2019, es2020, es3},
     pass::{noop, Optional, Pass},
-    util::prepend_stmts,
+    util::{prepend_stmts, HANDLER},
 };
 
 mod corejs2;
 mod corejs3;
+pub mod query;
 mod transform_data;
+mod util;
 
+/// Invalid targets and browserslist config are reported through `HANDLER`, and
+/// all transforms are enabled in that case.
 pub fn preset_env(mut c: Config) -> impl Pass {
-    if c.core_js == 0 {
-        c.core_js = 2;
+    match c.core_js {
+        0 => c.core_js = 2,
+        2 | 3 => {}
+        v => {
+            HANDLER.with(|handler| handler.err(&format!("unsupported core-js version: {}", v)));
+            c.core_js = 2;
+        }
     }
     let loose = c.loose;
-    let targets: Versions = c.targets.try_into().expect("failed to parse targets");
+    let targets = match c.targets.take() {
+        None if !c.ignore_browserslist_config => browserslist_config(&c),
+        targets => targets,
+    };
+    let targets: Versions = targets.try_into().unwrap_or_else(|err| {
+        HANDLER.with(|handler| handler.err(&format!("failed to parse targets: {}", err)));
+        Versions::default()
+    });
     let is_any_target = targets.is_any_target();
+    let should_enable = |f: Feature, default| {
+        !c.exclude.contains(&f)
+            && (is_any_target || c.include.contains(&f) || f.should_enable(&targets, default))
+    };
 
     let pass = noop();
     macro_rules! add {
@@ -36,8 +60,7 @@ pub fn preset_env(mut c: Config) -> impl Pass {
         ($prev:expr, $feature:ident, $pass:expr, $default:expr) => {{
             let f = transform_data::Feature::$feature;
 
-            let enable = !c.exclude.contains(&f)
-                && (is_any_target || c.include.contains(&f) || f.should_enable(&targets, $default));
+            let enable = should_enable(f, $default);
             if c.debug {
                 println!("{}: {:?}", f.as_str(), enable);
             }
@@ -45,9 +68,24 @@ pub fn preset_env(mut c: Config) -> impl Pass {
         }};
     }
 
+    // ES2020
+    let pass = add!(pass, NullishCoalescing, es2020::nullish_coalescing());
+    let pass = add!(pass, OptionalChaining, es2020::optional_chaining());
+
+    // ES2019
+    let pass = add!(pass, JsonStrings, es2019::json_strings());
+
     // ES2018
+    let pass = add!(pass, AsyncGeneratorFunctions, es2018::async_generator());
     let pass = add!(pass, ObjectRestSpread, es2018::object_rest_spread());
     let pass = add!(pass, OptionalCatchBinding, es2018::optional_catch_binding());
+    let pass = add!(pass, UnicodePropertyRegex, es2018::unicode_property_regex());
+    let pass = add!(
+        pass,
+        NamedCapturingGroupsRegex,
+        es2018::named_capturing_groups_regex()
+    );
+    let pass = add!(pass, DotAllRegex, es2018::dotall_regex());
 
     // ES2017
     let pass = add!(pass, AsyncToGenerator, es2017::async_to_generator());
@@ -63,6 +101,8 @@ pub fn preset_env(mut c: Config) -> impl Pass {
         es2015::TemplateLiteral::default(),
         true
     );
+    let pass = add!(pass, ObjectSuper, es2015::object_super());
+    let pass = add!(pass, NewTarget, es2015::new_target());
     let pass = add!(pass, Classes, es2015::Classes::default());
     let pass = add!(
         pass,
@@ -73,8 +113,9 @@ pub fn preset_env(mut c: Config) -> impl Pass {
     let pass = add!(pass, FunctionName, es2015::function_name());
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
+    let pass = add!(pass, UnicodeRegex, es2015::unicode_regex());
     let pass = add!(pass, StickyRegex, es2015::StickyRegex);
-    // TODO:    InstanceOf,
+    let pass = add!(pass, InstanceOf, es2015::InstanceOf);
     let pass = add!(pass, TypeOfSymbol, es2015::TypeOfSymbol);
     let pass = add!(pass, ShorthandProperties, es2015::Shorthand);
     let pass = add!(pass, Parameters, es2015::parameters());
@@ -99,18 +140,8 @@ pub fn preset_env(mut c: Config) -> impl Pass {
         true
     );
     let pass = add!(pass, BlockScoping, es2015::block_scoping(), true);
-
-    // TODO:
-    //    Literals,
-    //    ObjectSuper,
-    //    DotAllRegex,
-    //    UnicodeRegex,
-    //    NewTarget,
-    //    Regenerator,
-    //    AsyncGeneratorFunctions,
-    //    UnicodePropertyRegex,
-    //    JsonStrings,
-    //    NamedCapturingGroupsRegex,
+    let pass = add!(pass, Regenerator, es2015::regenerator());
+    let pass = add!(pass, Literals, es2015::literals());
 
     // ES 3
     let pass = add!(pass, PropertyLiterals, es3::PropertyLiteral);
@@ -123,11 +154,16 @@ pub fn preset_env(mut c: Config) -> impl Pass {
         }
     );
 
+    let regenerator = should_enable(Feature::Regenerator, false);
     chain!(
         pass,
         Polyfills {
             mode: c.mode,
-            targets
+            core_js: c.core_js,
+            shipped_proposals: c.shipped_proposals,
+            proposals: c.proposals,
+            regenerator,
+            targets,
         }
     )
 }
@@ -183,6 +219,11 @@ impl<T> BrowserData<Option<T>> {
 
 struct Polyfills {
     mode: Option<Mode>,
+    core_js: usize,
+    shipped_proposals: bool,
+    proposals: bool,
+    /// `regenerator-runtime/runtime` is required.
+    regenerator: bool,
     targets: Versions,
 }
 
@@ -190,17 +231,37 @@ impl Fold<Module> for Polyfills {
     fn fold(&mut self, mut node: Module) -> Module {
         let span = node.span;
 
+        if self.mode == Some(Mode::Entry) && self.core_js == 3 {
+            let mut v = corejs3::Entry::new(&self.targets, self.regenerator);
+            return node.fold_with(&mut v);
+        }
+
         if self.mode == Some(Mode::Usage) {
-            let mut v = corejs2::UsageVisitor::new(&self.targets);
-            node.visit_with(&mut v);
+            let mut required = match self.core_js {
+                2 => {
+                    let mut v = corejs2::UsageVisitor::new(&self.targets);
+                    node.visit_with(&mut v);
+                    v.required
+                }
+                3 => {
+                    let mut v = corejs3::UsageVisitor::new(
+                        &self.targets,
+                        self.shipped_proposals,
+                        self.proposals,
+                    );
+                    node.visit_with(&mut v);
+                    v.required
+                }
+                v => unreachable!("core-js {} should be rejected by preset_env()", v),
+            };
 
             if cfg!(debug_assertions) {
-                v.required.sort();
+                required.sort();
             }
 
             prepend_stmts(
                 &mut node.body,
-                v.required.into_iter().map(|src| {
+                required.into_iter().map(|src| {
                     ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                         span,
                         specifiers: vec![],
@@ -270,11 +331,78 @@ pub struct Config {
     pub exclude: Vec<Feature>,
 
     /// The version of the used core js.
-    #[serde(default)]
+    ///
+    /// Only 2 and 3 are supported.
+    #[serde(default, deserialize_with = "deserialize_core_js")]
     pub core_js: usize,
 
+    /// Inject polyfills for proposals which are already shipped in browsers.
+    ///
+    /// Only used with core-js 3.
+    #[serde(default)]
+    pub shipped_proposals: bool,
+
+    /// Inject polyfills for all proposals supported by core-js.
+    ///
+    /// Only used with core-js 3.
+    #[serde(default)]
+    pub proposals: bool,
+
     #[serde(default)]
     pub targets: Option<Target>,
+
+    /// The directory or file used to search browserslist config.
+    ///
+    /// Defaults to the current working directory.
+    #[serde(default)]
+    pub config_path: Option<PathBuf>,
+
+    /// Don't load `.browserslistrc` or `browserslist` key of `package.json`
+    /// when `targets` is not specified.
+    #[serde(default)]
+    pub ignore_browserslist_config: bool,
+
+    /// Section of browserslist config to use.
+    ///
+    /// Defaults to `BROWSERSLIST_ENV`, `NODE_ENV` or `production`.
+    #[serde(default)]
+    pub browserslist_env: Option<String>,
+}
+
+fn deserialize_core_js<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match usize::deserialize(deserializer)? {
+        v @ 2 | v @ 3 => Ok(v),
+        v => Err(de::Error::custom(format!(
+            "unsupported core-js version: {} (expected 2 or 3)",
+            v
+        ))),
+    }
+}
+
+fn browserslist_config(c: &Config) -> Option<Target> {
+    let path = match c.config_path {
+        Some(ref path) => path.clone(),
+        None => env::current_dir().ok()?,
+    };
+    let env = c
+        .browserslist_env
+        .clone()
+        .or_else(|| env::var("BROWSERSLIST_ENV").ok())
+        .or_else(|| env::var("NODE_ENV").ok())
+        .unwrap_or_else(|| String::from("production"));
+
+    match query::load_config(&path, &env) {
+        Ok(queries) => queries.map(Target::Queries),
+        Err(err) => {
+            HANDLER.with(|handler| {
+                handler.err(&format!("failed to load browserslist config: {}", err))
+            });
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, FromVariant)]
@@ -286,13 +414,14 @@ pub enum Target {
 }
 
 impl TryFrom<Option<Target>> for Versions {
-    type Error = ();
+    type Error = query::QueryError;
 
     fn try_from(v: Option<Target>) -> Result<Self, Self::Error> {
         match v {
             None => Ok(Versions::default()),
             Some(Target::Versions(v)) => Ok(v),
-            _ => unimplemented!(),
+            Some(Target::Queries(q)) => query::query(&q).map(|b| query::to_versions(&b)),
+            Some(Target::Query(q)) => query::query(&[q]).map(|b| query::to_versions(&b)),
         }
     }
 }
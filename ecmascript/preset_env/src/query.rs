@@ -0,0 +1,941 @@
+//! Offline evaluation of [browserslist] queries.
+//!
+//! Usage statistics and release dates are bundled in `query_data.json`, which
+//! is a snapshot of `caniuse-lite`, `electron-to-chromium` and
+//! `node-releases`.
+//!
+//! [browserslist]:https://github.com/browserslist/browserslist
+
+use crate::{parse_version, Versions};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    env,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// `> 0.5%, last 2 versions, Firefox ESR, not dead`
+const DEFAULTS: &[&str] = &["> 0.5%", "last 2 versions", "Firefox ESR", "not dead"];
+
+const DEAD: &[&str] = &[
+    "ie <= 10",
+    "ie_mob <= 10",
+    "bb <= 10",
+    "op_mob <= 12.1",
+    "samsung 4",
+];
+
+const FIREFOX_ESR: &str = "68";
+
+/// Date of the bundled data (2020-01-15).
+///
+/// Date-based queries are evaluated at this date, because the data does not
+/// contain browsers released after it.
+const DATA_DATE: i64 = 1_579_046_400;
+
+const ONE_YEAR: f64 = 365.259_641 * 24.0 * 60.0 * 60.0;
+
+#[derive(Debug)]
+pub enum QueryError {
+    /// The query does not match any known syntax.
+    Unknown(String),
+    UnknownBrowser(String),
+    UnknownVersion {
+        browser: String,
+        version: String,
+    },
+    /// The query requires data which is not bundled, like regional usage
+    /// statistics or the version of the running node.
+    Unsupported(String),
+    /// `not` is used as the first query.
+    NotAtFirst(String),
+    /// Both of `.browserslistrc` and `package.json` define browserslist config.
+    DuplicateConfig(PathBuf),
+    Io(io::Error),
+    Json(serde_json::Error),
+    InvalidConfig(PathBuf),
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            QueryError::Unknown(ref q) => write!(f, "unknown browser query `{}`", q),
+            QueryError::UnknownBrowser(ref name) => write!(f, "unknown browser `{}`", name),
+            QueryError::UnknownVersion {
+                ref browser,
+                ref version,
+            } => write!(f, "unknown version `{}` of `{}`", version, browser),
+            QueryError::Unsupported(ref q) => write!(f, "unsupported browser query `{}`", q),
+            QueryError::NotAtFirst(ref q) => {
+                write!(f, "`{}` can't be used as the first query", q)
+            }
+            QueryError::DuplicateConfig(ref dir) => write!(
+                f,
+                "{} contains multiple browserslist configs",
+                dir.display()
+            ),
+            QueryError::Io(ref err) => Display::fmt(err, f),
+            QueryError::Json(ref err) => Display::fmt(err, f),
+            QueryError::InvalidConfig(ref path) => {
+                write!(f, "invalid browserslist config in {}", path.display())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Data {
+    /// Browser name to `[version, release date, global usage]`.
+    ///
+    /// Release date is `None` for unreleased versions.
+    agents: HashMap<String, Vec<(String, Option<i64>, f64)>>,
+    /// Electron version to chrome version.
+    electron: HashMap<String, String>,
+    node: Vec<NodeRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeRelease {
+    versions: Vec<String>,
+    /// Start of the support.
+    start: i64,
+    /// End of life.
+    end: i64,
+}
+
+static DATA: Lazy<Data> = Lazy::new(|| {
+    serde_json::from_str(include_str!("query_data.json")).expect("failed to parse json")
+});
+
+/// A browser selected by queries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Distrib {
+    pub name: String,
+    pub version: String,
+}
+
+impl Distrib {
+    fn new(name: &str, version: &str) -> Self {
+        Distrib {
+            name: name.into(),
+            version: version.into(),
+        }
+    }
+}
+
+/// Resolves `queries` like `browserslist(queries)`.
+///
+/// Queries relative to the current date, like `last 2 years`, are evaluated at
+/// the date of the bundled data.
+///
+/// Browsers are sorted by name, and then by version in descending order.
+pub fn query<S: AsRef<str>>(queries: &[S]) -> Result<Vec<Distrib>, QueryError> {
+    query_at(queries, DATA_DATE)
+}
+
+fn query_at<S: AsRef<str>>(queries: &[S], now: i64) -> Result<Vec<Distrib>, QueryError> {
+    let mut result: Vec<Distrib> = vec![];
+    let mut is_first = true;
+
+    for query in queries.iter().flat_map(|q| split(q.as_ref())) {
+        let (selection, is_and) = query;
+        let (selection, is_exclude) = match strip_prefix_ignore_case(selection, "not ") {
+            Some(s) => {
+                if is_first {
+                    return Err(QueryError::NotAtFirst(selection.into()));
+                }
+                (s.trim(), true)
+            }
+            None => (selection, false),
+        };
+        is_first = false;
+
+        let selected = select(selection, now)?;
+
+        if is_exclude {
+            result.retain(|d| !selected.contains(d));
+        } else if is_and {
+            result.retain(|d| selected.contains(d));
+        } else {
+            for d in selected {
+                if !result.contains(&d) {
+                    result.push(d);
+                }
+            }
+        }
+    }
+
+    result.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| cmp_version(&b.version, &a.version))
+    });
+    Ok(result)
+}
+
+/// Splits `a, b or c and d` into `[(a, false), (b, false), (c, false), (d,
+/// true)]`.
+///
+/// The second element is true if the query is joined with `and`.
+fn split(queries: &str) -> Vec<(&str, bool)> {
+    let mut buf = vec![];
+
+    for q in queries.split(',') {
+        for q in split_ignore_case(q, " or ") {
+            for (i, q) in split_ignore_case(q, " and ").into_iter().enumerate() {
+                let q = q.trim();
+                if !q.is_empty() {
+                    buf.push((q, i != 0));
+                }
+            }
+        }
+    }
+
+    buf
+}
+
+fn split_ignore_case<'a>(s: &'a str, sep: &str) -> Vec<&'a str> {
+    let lower = s.to_ascii_lowercase();
+    let mut buf = vec![];
+    let mut start = 0;
+
+    while let Some(pos) = lower[start..].find(sep) {
+        buf.push(&s[start..start + pos]);
+        start += pos + sep.len();
+    }
+    buf.push(&s[start..]);
+
+    buf
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    if s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Splits a query into words. Comparison operators are separated from other
+/// words, so `ie>=9` becomes `["ie", ">=", "9"]`.
+fn tokenize(q: &str) -> Vec<String> {
+    let mut buf = vec![];
+    let mut cur = String::new();
+    let mut chars = q.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if !cur.is_empty() {
+                    buf.push(cur.to_ascii_lowercase());
+                    cur.clear();
+                }
+            }
+            '<' | '>' => {
+                if !cur.is_empty() {
+                    buf.push(cur.to_ascii_lowercase());
+                    cur.clear();
+                }
+                let mut op = c.to_string();
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                    op.push('=');
+                }
+                buf.push(op);
+            }
+            _ => cur.push(c),
+        }
+    }
+    if !cur.is_empty() {
+        buf.push(cur.to_ascii_lowercase());
+    }
+
+    buf
+}
+
+fn select(q: &str, now: i64) -> Result<Vec<Distrib>, QueryError> {
+    let tokens = tokenize(q);
+    let words: Vec<&str> = tokens.iter().map(|s| &**s).collect();
+    let unknown = || QueryError::Unknown(q.into());
+    let unsupported = || QueryError::Unsupported(q.into());
+    let count = |s: &str| s.parse::<usize>().map_err(|_| unknown());
+
+    Ok(match &*words {
+        ["last", n, "versions"] | ["last", n, "version"] => {
+            let n = count(n)?;
+            all_agents(|name, versions| last_versions(name, versions, n))
+        }
+        ["last", n, "major", "versions"] | ["last", n, "major", "version"] => {
+            let n = count(n)?;
+            all_agents(|name, versions| last_major_versions(name, versions, n))
+        }
+        ["last", n, "electron", "versions"] | ["last", n, "electron", "version"] => {
+            let n = count(n)?;
+            let mut versions: Vec<_> = DATA.electron.keys().collect();
+            versions.sort_by(|a, b| cmp_version(b, a));
+            versions
+                .into_iter()
+                .take(n)
+                .map(|v| Distrib::new("chrome", &DATA.electron[v]))
+                .collect()
+        }
+        ["last", n, "node", "versions"] | ["last", n, "node", "version"] => {
+            let n = count(n)?;
+            node_versions().rev().take(n).map(node).collect()
+        }
+        ["last", n, name, "versions"] | ["last", n, name, "version"] => {
+            let n = count(n)?;
+            let (name, versions) = agent(name)?;
+            last_versions(name, versions, n)
+        }
+        ["last", n, name, "major", "versions"] | ["last", n, name, "major", "version"] => {
+            let n = count(n)?;
+            let (name, versions) = agent(name)?;
+            last_major_versions(name, versions, n)
+        }
+        ["unreleased", "versions"] => all_agents(|name, versions| {
+            versions
+                .iter()
+                .filter(|v| v.1.is_none())
+                .map(|v| Distrib::new(name, &v.0))
+                .collect()
+        }),
+        ["unreleased", "electron", "versions"] => vec![],
+        ["unreleased", name, "versions"] => {
+            let (name, versions) = agent(name)?;
+            versions
+                .iter()
+                .filter(|v| v.1.is_none())
+                .map(|v| Distrib::new(name, &v.0))
+                .collect()
+        }
+        ["last", n, "years"] | ["last", n, "year"] => {
+            let n: f64 = n.parse().map_err(|_| unknown())?;
+            released_since(now - (n * ONE_YEAR) as i64)
+        }
+        ["since", date] => {
+            let mut parts = date.split('-').map(|s| s.parse::<i64>());
+            let year = match parts.next() {
+                Some(Ok(v)) => v,
+                _ => return Err(unknown()),
+            };
+            let month = parts.next().unwrap_or(Ok(1)).map_err(|_| unknown())?;
+            let day = parts.next().unwrap_or(Ok(1)).map_err(|_| unknown())?;
+            released_since(days_from_civil(year, month, day) * 24 * 60 * 60)
+        }
+        [op @ ">", pct] | [op @ ">=", pct] | [op @ "<", pct] | [op @ "<=", pct]
+            if pct.ends_with('%') =>
+        {
+            let pct: f64 = pct[..pct.len() - 1].parse().map_err(|_| unknown())?;
+            all_agents(|name, versions| {
+                versions
+                    .iter()
+                    .filter(|v| v.1.is_some() && cmp_op(op, v.2.partial_cmp(&pct)))
+                    .map(|v| Distrib::new(name, &v.0))
+                    .collect()
+            })
+        }
+        [">", _, "in", ..] | [">=", _, "in", ..] | ["<", _, "in", ..] | ["<=", _, "in", ..] => {
+            return Err(unsupported())
+        }
+        ["cover", pct] if pct.ends_with('%') => {
+            let pct: f64 = pct[..pct.len() - 1].parse().map_err(|_| unknown())?;
+            let mut all: Vec<(&str, &str, f64)> = DATA
+                .agents
+                .iter()
+                .flat_map(|(name, versions)| {
+                    versions
+                        .iter()
+                        .filter(|v| v.1.is_some())
+                        .map(move |v| (&**name, &*v.0, v.2))
+                })
+                .collect();
+            all.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal));
+
+            let mut covered = 0.0;
+            let mut buf = vec![];
+            for (name, version, usage) in all {
+                if covered >= pct || usage == 0.0 {
+                    break;
+                }
+                covered += usage;
+                buf.push(Distrib::new(name, version));
+            }
+            buf
+        }
+        ["cover", ..] | ["supports", ..] | ["extends", ..] | ["current", "node"] => {
+            return Err(unsupported())
+        }
+        ["electron", range] if range.contains('-') => {
+            let mut parts = range.splitn(2, '-');
+            let from = parts.next().unwrap();
+            let to = parts.next().unwrap();
+            check_electron(from)?;
+            check_electron(to)?;
+            electron(|v| {
+                cmp_version(v, from) != Ordering::Less && cmp_version(v, to) != Ordering::Greater
+            })
+        }
+        ["electron", op, v] if is_op(op) => electron(|e| cmp_op(op, Some(cmp_version(e, v)))),
+        ["electron", v] => {
+            check_electron(v)?;
+            electron(|e| cmp_version(e, v) == Ordering::Equal)
+        }
+        ["maintained", "node", "versions"] => DATA
+            .node
+            .iter()
+            .filter(|r| r.start <= now && now < r.end)
+            .filter_map(|r| r.versions.last())
+            .map(|v| node(v))
+            .collect(),
+        ["node", op, v] if is_op(op) => node_versions()
+            .filter(|n| cmp_op(op, Some(cmp_version(n, v))))
+            .map(node)
+            .collect(),
+        ["node", v] => {
+            let prefix = format!("{}.", v);
+            match node_versions().find(|n| *n == *v || n.starts_with(&prefix)) {
+                Some(n) => vec![node(n)],
+                None => {
+                    return Err(QueryError::UnknownVersion {
+                        browser: "node".into(),
+                        version: (*v).into(),
+                    })
+                }
+            }
+        }
+        ["phantomjs", "1.9"] => vec![Distrib::new("safari", "5")],
+        ["phantomjs", "2.1"] => vec![Distrib::new("safari", "6")],
+        ["firefox", "esr"] | ["ff", "esr"] | ["fx", "esr"] => {
+            vec![Distrib::new("firefox", FIREFOX_ESR)]
+        }
+        ["opera", "mini", "all"] | ["operamini", "all"] | ["op_mini", "all"] => {
+            vec![Distrib::new("op_mini", "all")]
+        }
+        ["defaults"] => query_at(DEFAULTS, now)?,
+        ["dead"] => query_at(DEAD, now)?,
+        [name, range] if range.contains('-') && agent(name).is_ok() && !is_exact(name, range) => {
+            let (name, versions) = agent(name)?;
+            let mut parts = range.splitn(2, '-');
+            let from = parts.next().unwrap();
+            let to = parts.next().unwrap();
+            versions
+                .iter()
+                .filter(|v| v.1.is_some())
+                .filter(|v| {
+                    cmp_version(&v.0, from) != Ordering::Less
+                        && cmp_version(&v.0, to) != Ordering::Greater
+                })
+                .map(|v| Distrib::new(name, &v.0))
+                .collect()
+        }
+        [name, op, v] if is_op(op) => {
+            let (name, versions) = agent(name)?;
+            versions
+                .iter()
+                .filter(|e| e.1.is_some() && is_numeric(&e.0))
+                .filter(|e| cmp_op(op, Some(cmp_version(&e.0, v))))
+                .map(|e| Distrib::new(name, &e.0))
+                .collect()
+        }
+        [name, v] => {
+            let (name, versions) = agent(name)?;
+            vec![Distrib::new(name, &normalize_version(name, versions, v)?)]
+        }
+        _ => return Err(unknown()),
+    })
+}
+
+type Agent = &'static [(String, Option<i64>, f64)];
+
+fn agent(name: &str) -> Result<(&'static str, Agent), QueryError> {
+    let name = match name {
+        "fx" | "ff" => "firefox",
+        "explorer" => "ie",
+        "blackberry" => "bb",
+        "explorermobile" => "ie_mob",
+        "operamini" => "op_mini",
+        "operamobile" => "op_mob",
+        "chromeandroid" => "and_chr",
+        "firefoxandroid" => "and_ff",
+        "ucandroid" => "and_uc",
+        "qqandroid" => "and_qq",
+        "ios" => "ios_saf",
+        _ => name,
+    };
+
+    match DATA.agents.get_key_value(name) {
+        Some((name, versions)) => Ok((&**name, &**versions)),
+        None => Err(QueryError::UnknownBrowser(name.into())),
+    }
+}
+
+fn all_agents(mut op: impl FnMut(&str, Agent) -> Vec<Distrib>) -> Vec<Distrib> {
+    DATA.agents
+        .iter()
+        .flat_map(|(name, versions)| op(name, versions))
+        .collect()
+}
+
+fn last_versions(name: &str, versions: Agent, n: usize) -> Vec<Distrib> {
+    versions
+        .iter()
+        .filter(|v| v.1.is_some())
+        .rev()
+        .take(n)
+        .map(|v| Distrib::new(name, &v.0))
+        .collect()
+}
+
+fn last_major_versions(name: &str, versions: Agent, n: usize) -> Vec<Distrib> {
+    let major = |v: &str| v.split('.').next().unwrap_or(v).to_string();
+
+    let mut majors: Vec<String> = vec![];
+    for v in versions.iter().filter(|v| v.1.is_some()).rev() {
+        let m = major(&v.0);
+        if !majors.contains(&m) {
+            if majors.len() == n {
+                break;
+            }
+            majors.push(m);
+        }
+    }
+
+    versions
+        .iter()
+        .filter(|v| v.1.is_some() && majors.contains(&major(&v.0)))
+        .map(|v| Distrib::new(name, &v.0))
+        .collect()
+}
+
+fn released_since(time: i64) -> Vec<Distrib> {
+    all_agents(|name, versions| {
+        versions
+            .iter()
+            .filter(|v| v.1.map(|date| date >= time).unwrap_or(false))
+            .map(|v| Distrib::new(name, &v.0))
+            .collect()
+    })
+}
+
+/// `ios_saf 10.1` => `ios_saf 10.0-10.2`
+fn normalize_version(name: &str, versions: Agent, version: &str) -> Result<String, QueryError> {
+    if let Some(v) = versions.iter().find(|v| v.0 == version) {
+        return Ok(v.0.clone());
+    }
+
+    if let Some(v) = versions.iter().find(|v| {
+        let mut parts = v.0.splitn(2, '-');
+        match (parts.next(), parts.next()) {
+            (Some(from), Some(to)) => {
+                cmp_version(version, from) != Ordering::Less
+                    && cmp_version(version, to) != Ordering::Greater
+            }
+            _ => false,
+        }
+    }) {
+        return Ok(v.0.clone());
+    }
+
+    // Webview of android follows versions of chrome.
+    if name == "android" || name == "and_chr" {
+        let (_, chrome) = agent("chrome")?;
+        if chrome.iter().any(|v| v.0 == version) {
+            return Ok(version.into());
+        }
+    }
+
+    // Mobile browsers which have only one version in caniuse.
+    if versions.len() == 1 {
+        return Ok(versions[0].0.clone());
+    }
+
+    Err(QueryError::UnknownVersion {
+        browser: name.into(),
+        version: version.into(),
+    })
+}
+
+/// Returns true if `version` is a version of `name` instead of a range.
+fn is_exact(name: &str, version: &str) -> bool {
+    agent(name)
+        .map(|(_, versions)| versions.iter().any(|v| v.0 == version))
+        .unwrap_or(false)
+}
+
+fn check_electron(v: &str) -> Result<(), QueryError> {
+    if DATA.electron.contains_key(v) {
+        Ok(())
+    } else {
+        Err(QueryError::UnknownVersion {
+            browser: "electron".into(),
+            version: v.into(),
+        })
+    }
+}
+
+fn electron(mut filter: impl FnMut(&str) -> bool) -> Vec<Distrib> {
+    DATA.electron
+        .iter()
+        .filter(|(e, _)| filter(e))
+        .map(|(_, chrome)| Distrib::new("chrome", chrome))
+        .collect()
+}
+
+fn node_versions() -> impl DoubleEndedIterator<Item = &'static str> {
+    DATA.node
+        .iter()
+        .flat_map(|r| r.versions.iter())
+        .map(|s| &**s)
+}
+
+fn node(v: &str) -> Distrib {
+    Distrib::new("node", v)
+}
+
+fn is_op(s: &str) -> bool {
+    match s {
+        ">" | ">=" | "<" | "<=" => true,
+        _ => false,
+    }
+}
+
+fn cmp_op(op: &str, ord: Option<Ordering>) -> bool {
+    match (op, ord) {
+        (">", Some(Ordering::Greater)) => true,
+        (">=", Some(Ordering::Greater)) | (">=", Some(Ordering::Equal)) => true,
+        ("<", Some(Ordering::Less)) => true,
+        ("<=", Some(Ordering::Less)) | ("<=", Some(Ordering::Equal)) => true,
+        _ => false,
+    }
+}
+
+fn is_numeric(v: &str) -> bool {
+    v.chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+}
+
+/// Compares versions numerically. For ranges like `12.2-12.4`, the lower
+/// bound is used.
+fn cmp_version(a: &str, b: &str) -> Ordering {
+    fn parts(v: &str) -> impl Iterator<Item = u32> + '_ {
+        v.split('-')
+            .next()
+            .unwrap_or(v)
+            .split('.')
+            .map(|s| s.parse().unwrap_or(0))
+    }
+
+    let mut a = parts(a);
+    let mut b = parts(b);
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (x, y) => match x.unwrap_or(0).cmp(&y.unwrap_or(0)) {
+                Ordering::Equal => {}
+                ord => return ord,
+            },
+        }
+    }
+}
+
+/// Days since unix epoch.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Converts result of [query] to versions used by `preset_env`.
+///
+/// The lowest version is used if multiple versions of a browser are
+/// selected.
+pub fn to_versions(browsers: &[Distrib]) -> Versions {
+    let mut versions = Versions::default();
+
+    for d in browsers {
+        if !is_numeric(&d.version) {
+            continue;
+        }
+
+        let slot = match &*d.name {
+            "chrome" | "and_chr" => &mut versions.chrome,
+            "firefox" | "and_ff" => &mut versions.firefox,
+            "ie" | "ie_mob" => &mut versions.ie,
+            "edge" => &mut versions.edge,
+            "safari" => &mut versions.safari,
+            "ios_saf" => &mut versions.ios,
+            "samsung" => &mut versions.samsung,
+            "opera" | "op_mob" => &mut versions.opera,
+            "android" => &mut versions.android,
+            "node" => &mut versions.node,
+            _ => continue,
+        };
+
+        let v = parse_version(d.version.split('-').next().unwrap());
+        match *slot {
+            Some(ref prev) if *prev <= v => {}
+            _ => *slot = Some(v),
+        }
+    }
+
+    versions
+}
+
+/// Loads browserslist config for `path`.
+///
+/// Like browserslist, this checks the `BROWSERSLIST` environment variable
+/// first, and then searches `browserslist`, `.browserslistrc` and
+/// `package.json` from `path` to the root. `env` selects a section of the
+/// config, and the `defaults` section is used if it does not exist.
+pub fn load_config(path: &Path, env: &str) -> Result<Option<Vec<String>>, QueryError> {
+    if let Ok(queries) = env::var("BROWSERSLIST") {
+        return Ok(Some(vec![queries]));
+    }
+
+    let start = if path.is_file() {
+        path.parent()
+    } else {
+        Some(path)
+    };
+
+    for dir in start.into_iter().flat_map(|p| p.ancestors()) {
+        let mut found = None;
+
+        for name in &["browserslist", ".browserslistrc"] {
+            let file = dir.join(name);
+            if file.is_file() {
+                if found.is_some() {
+                    return Err(QueryError::DuplicateConfig(dir.to_path_buf()));
+                }
+                let s = fs::read_to_string(&file).map_err(QueryError::Io)?;
+                found = Some(pick_env(parse_rc(&s), env));
+            }
+        }
+
+        let pkg = dir.join("package.json");
+        if pkg.is_file() {
+            let s = fs::read_to_string(&pkg).map_err(QueryError::Io)?;
+            let json: Value = serde_json::from_str(&s).map_err(QueryError::Json)?;
+
+            if let Some(config) = json.get("browserslist") {
+                if found.is_some() {
+                    return Err(QueryError::DuplicateConfig(dir.to_path_buf()));
+                }
+                found = Some(
+                    parse_package_json(config, env)
+                        .ok_or_else(|| QueryError::InvalidConfig(pkg.clone()))?,
+                );
+            }
+        }
+
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses `.browserslistrc`.
+fn parse_rc(s: &str) -> HashMap<String, Vec<String>> {
+    let mut sections: HashMap<String, Vec<String>> = HashMap::new();
+    let mut current = vec![String::from("defaults")];
+
+    for line in s.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            current = line[1..line.len() - 1]
+                .split_whitespace()
+                .map(String::from)
+                .collect();
+            continue;
+        }
+
+        for name in &current {
+            sections
+                .entry(name.clone())
+                .or_default()
+                .push(line.to_string());
+        }
+    }
+
+    sections
+}
+
+fn pick_env(mut sections: HashMap<String, Vec<String>>, env: &str) -> Vec<String> {
+    sections
+        .remove(env)
+        .or_else(|| sections.remove("defaults"))
+        .unwrap_or_default()
+}
+
+/// Parses `browserslist` field of `package.json`.
+fn parse_package_json(v: &Value, env: &str) -> Option<Vec<String>> {
+    fn queries(v: &Value) -> Option<Vec<String>> {
+        match *v {
+            Value::String(ref s) => Some(vec![s.clone()]),
+            Value::Array(ref arr) => arr.iter().map(|v| v.as_str().map(String::from)).collect(),
+            _ => None,
+        }
+    }
+
+    match *v {
+        Value::Object(ref map) => match map.get(env).or_else(|| map.get("defaults")) {
+            Some(v) => queries(v),
+            None => Some(vec![]),
+        },
+        _ => queries(v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = DATA_DATE;
+
+    fn q(s: &str) -> Vec<String> {
+        query_at(&[s], NOW)
+            .unwrap()
+            .into_iter()
+            .map(|d| format!("{} {}", d.name, d.version))
+            .collect()
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!(q("ie 11"), vec!["ie 11"]);
+        assert_eq!(q("ios 10.1"), vec!["ios_saf 10.0-10.2"]);
+        assert_eq!(q("chromeandroid 59"), vec!["and_chr 59"]);
+    }
+
+    #[test]
+    fn ops() {
+        assert_eq!(q("ie >= 10"), vec!["ie 11", "ie 10"]);
+        assert_eq!(q("ie>=10"), vec!["ie 11", "ie 10"]);
+        assert_eq!(q("ie 9-10"), vec!["ie 10", "ie 9"]);
+        assert_eq!(q("edge > 18"), vec!["edge 79"]);
+    }
+
+    #[test]
+    fn last_versions() {
+        assert_eq!(q("last 2 chrome versions"), vec!["chrome 79", "chrome 78"]);
+        assert_eq!(q("last 1 ios versions"), vec!["ios_saf 13.3"]);
+        assert_eq!(
+            q("last 1 ios major versions"),
+            vec!["ios_saf 13.3", "ios_saf 13.2", "ios_saf 13.0-13.1"]
+        );
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(q("ie >= 9, not ie 10"), vec!["ie 11", "ie 9"]);
+        assert!(query_at(&["not ie 10"], NOW).is_err());
+    }
+
+    #[test]
+    fn and() {
+        assert_eq!(q("last 2 versions and ie > 0"), vec!["ie 11", "ie 10"]);
+    }
+
+    #[test]
+    fn defaults() {
+        let browsers = q("defaults");
+        assert!(browsers.contains(&"firefox 68".to_string()));
+        assert!(browsers.contains(&"ie 11".to_string()));
+        assert!(!browsers.contains(&"ie 10".to_string()));
+    }
+
+    #[test]
+    fn node_and_electron() {
+        assert_eq!(q("node 10.13"), vec!["node 10.13.0"]);
+        assert_eq!(q("electron 1.4"), vec!["chrome 53"]);
+        assert_eq!(
+            q("maintained node versions"),
+            vec!["node 13.6.0", "node 12.14.0", "node 10.18.0"]
+        );
+    }
+
+    #[test]
+    fn since() {
+        assert_eq!(q("since 2019-12-05 and safari > 0"), Vec::<String>::new());
+        assert_eq!(q("since 2019-12 and chrome > 0"), vec!["chrome 79"]);
+    }
+
+    #[test]
+    fn relative_dates() {
+        assert!(q("last 1 year").contains(&"chrome 79".to_string()));
+        assert_eq!(
+            query(&["last 1 year"]).unwrap(),
+            query_at(&["last 1 year"], NOW).unwrap()
+        );
+    }
+
+    #[test]
+    fn duplicate_config() {
+        let dir = env::temp_dir().join("swc-preset-env-duplicate-config");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("browserslist"), "ie 11").unwrap();
+        fs::write(dir.join(".browserslistrc"), "chrome 79").unwrap();
+
+        match load_config(&dir, "production") {
+            Err(QueryError::DuplicateConfig(..)) => {}
+            res => panic!("expected an error, got {:?}", res),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn versions() {
+        let v = to_versions(&query_at(&["ie 11", "chrome >= 78", "ios 12.2"], NOW).unwrap());
+        assert_eq!(v.ie, Some(parse_version("11")));
+        assert_eq!(v.chrome, Some(parse_version("78")));
+        assert_eq!(v.ios, Some(parse_version("12.2")));
+        assert_eq!(v.firefox, None);
+    }
+
+    #[test]
+    fn rc() {
+        let sections = parse_rc(
+            "# comment
+last 1 version
+> 1%
+
+[production staging]
+> 0.5% # trailing
+
+[development]
+last 1 chrome version
+",
+        );
+        assert_eq!(
+            pick_env(sections.clone(), "staging"),
+            vec![String::from("> 0.5%")]
+        );
+        assert_eq!(
+            pick_env(sections.clone(), "test"),
+            vec![String::from("last 1 version"), String::from("> 1%")]
+        );
+        assert_eq!(
+            pick_env(sections, "development"),
+            vec![String::from("last 1 chrome version")]
+        );
+    }
+}
@@ -122,6 +122,12 @@ pub enum Feature {
     /// `proposal-optional-catch-binding`
     OptionalCatchBinding,
 
+    /// `proposal-nullish-coalescing-operator`
+    NullishCoalescing,
+
+    /// `proposal-optional-chaining`
+    OptionalChaining,
+
     /// `transform-named-capturing-groups-regex`
     NamedCapturingGroupsRegex,
 
@@ -0,0 +1,131 @@
+use hashbrown::HashSet;
+use swc_common::{Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::util::{id, Id};
+
+/// Collects identifiers declared in `node`.
+///
+/// Usage of a builtin is detected only if the identifier does not refer to a
+/// local binding, like `let Promise` or `function Map() {}`.
+pub(crate) fn collect_bindings<N>(node: &N) -> HashSet<Id>
+where
+    N: VisitWith<BindingCollector>,
+{
+    let mut v = BindingCollector {
+        bindings: Default::default(),
+    };
+    node.visit_with(&mut v);
+    v.bindings
+}
+
+pub(crate) struct BindingCollector {
+    bindings: HashSet<Id>,
+}
+
+impl BindingCollector {
+    fn add_pat(&mut self, pat: &Pat) {
+        match *pat {
+            Pat::Ident(ref i) => {
+                self.bindings.insert(id(i));
+            }
+            Pat::Array(ArrayPat { ref elems, .. }) => {
+                for elem in elems.iter().flatten() {
+                    self.add_pat(elem);
+                }
+            }
+            Pat::Object(ObjectPat { ref props, .. }) => {
+                for prop in props {
+                    match *prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                            self.add_pat(value)
+                        }
+                        ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => {
+                            self.bindings.insert(id(key));
+                        }
+                        ObjectPatProp::Rest(RestPat { ref arg, .. }) => self.add_pat(arg),
+                    }
+                }
+            }
+            Pat::Rest(RestPat { ref arg, .. }) => self.add_pat(arg),
+            Pat::Assign(AssignPat { ref left, .. }) => self.add_pat(left),
+            Pat::Expr(..) | Pat::Invalid(..) => {}
+        }
+    }
+}
+
+impl Visit<VarDeclarator> for BindingCollector {
+    fn visit(&mut self, v: &VarDeclarator) {
+        v.visit_children(self);
+        self.add_pat(&v.name);
+    }
+}
+
+impl Visit<Function> for BindingCollector {
+    fn visit(&mut self, f: &Function) {
+        f.visit_children(self);
+        for param in &f.params {
+            self.add_pat(&param.pat);
+        }
+    }
+}
+
+impl Visit<ArrowExpr> for BindingCollector {
+    fn visit(&mut self, f: &ArrowExpr) {
+        f.visit_children(self);
+        for param in &f.params {
+            self.add_pat(param);
+        }
+    }
+}
+
+impl Visit<CatchClause> for BindingCollector {
+    fn visit(&mut self, c: &CatchClause) {
+        c.visit_children(self);
+        if let Some(ref param) = c.param {
+            self.add_pat(param);
+        }
+    }
+}
+
+impl Visit<FnDecl> for BindingCollector {
+    fn visit(&mut self, f: &FnDecl) {
+        f.visit_children(self);
+        self.bindings.insert(id(&f.ident));
+    }
+}
+
+impl Visit<FnExpr> for BindingCollector {
+    fn visit(&mut self, f: &FnExpr) {
+        f.visit_children(self);
+        if let Some(ref ident) = f.ident {
+            self.bindings.insert(id(ident));
+        }
+    }
+}
+
+impl Visit<ClassDecl> for BindingCollector {
+    fn visit(&mut self, c: &ClassDecl) {
+        c.visit_children(self);
+        self.bindings.insert(id(&c.ident));
+    }
+}
+
+impl Visit<ClassExpr> for BindingCollector {
+    fn visit(&mut self, c: &ClassExpr) {
+        c.visit_children(self);
+        if let Some(ref ident) = c.ident {
+            self.bindings.insert(id(ident));
+        }
+    }
+}
+
+impl Visit<ImportSpecifier> for BindingCollector {
+    fn visit(&mut self, s: &ImportSpecifier) {
+        let local = match *s {
+            ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+            | ImportSpecifier::Default(ImportDefault { ref local, .. })
+            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+        };
+        self.bindings.insert(id(local));
+    }
+}
@@ -0,0 +1,2 @@
+var Promise = function () {};
+Promise.resolve();
@@ -75,6 +75,27 @@ pub enum CoreJs {
     Val(HashMap<String, Value>),
 }
 
+impl CoreJs {
+    fn version(&self) -> usize {
+        match *self {
+            CoreJs::Ver(v) => v,
+            CoreJs::VerWithMinor(ref s) => parse_version(s).major as _,
+            CoreJs::Val(ref map) => match map.get("version") {
+                Some(Value::Number(v)) => v.as_u64().unwrap() as _,
+                Some(Value::String(s)) => parse_version(s).major as _,
+                v => unreachable!("invalid core-js version: {:?}", v),
+            },
+        }
+    }
+
+    fn proposals(&self) -> bool {
+        match *self {
+            CoreJs::Val(ref map) => map.get("proposals") == Some(&Value::Bool(true)),
+            _ => false,
+        }
+    }
+}
+
 impl Default for CoreJs {
     fn default() -> Self {
         Self::Ver(2)
@@ -218,8 +239,13 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
         dynamic_import: true,
         include: c.include.into_iter().map(|v| v.parse().unwrap()).collect(),
         exclude: c.exclude.into_iter().map(|v| v.parse().unwrap()).collect(),
-        core_js: 2,
+        core_js: c.corejs.version(),
+        shipped_proposals: c.shipped_proposals,
+        proposals: c.corejs.proposals(),
         targets: Some(Target::Versions(versions)),
+        config_path: None,
+        ignore_browserslist_config: true,
+        browserslist_env: None,
     });
 
     println!("Browsers: {:?}", browsers);
@@ -231,7 +257,7 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: swc_ecma_codegen::Config { minify: false },
+                        cfg: Default::default(),
                         comments: None,
                         cm: cm.clone(),
                         wr: box swc_ecma_codegen::text_writer::JsWriter::new(
@@ -2,8 +2,10 @@ pub use self::{
     arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
     computed_props::computed_properties, destructuring::destructuring,
     duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, shorthand_property::Shorthand, spread::spread,
-    sticky_regex::StickyRegex, template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    instanceof::InstanceOf, literals::literals, new_target::new_target, object_super::object_super,
+    parameters::parameters, regenerator::regenerator, shorthand_property::Shorthand,
+    spread::spread, sticky_regex::StickyRegex, template_literal::TemplateLiteral,
+    typeof_symbol::TypeOfSymbol, unicode_regex::unicode_regex,
 };
 use crate::pass::Pass;
 use ast::{Expr, Program};
@@ -21,18 +23,24 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod literals;
+mod new_target;
+mod object_super;
 mod parameters;
+mod regenerator;
 mod shorthand_property;
 pub mod spread;
 mod sticky_regex;
 mod template_literal;
 mod typeof_symbol;
+mod unicode_regex;
 
 fn exprs() -> impl Pass {
     chain_at!(
         Expr,
         arrow(),
         duplicate_keys(),
+        unicode_regex(),
         StickyRegex,
         InstanceOf,
         TypeOfSymbol,
@@ -46,6 +54,8 @@ pub fn es2015(c: Config) -> impl Pass {
         Program,
         BlockScopedFns,
         TemplateLiteral::default(),
+        object_super(),
+        new_target(),
         Classes::default(),
         spread(c.spread),
         function_name(),
@@ -55,6 +65,8 @@ pub fn es2015(c: Config) -> impl Pass {
         computed_properties(),
         destructuring(c.destructuring),
         block_scoping(),
+        regenerator(),
+        literals(),
     )
 }
 
@@ -89,7 +89,7 @@ impl Fold<Expr> for Arrow {
                     function: Function {
                         decorators: vec![],
                         span,
-                        params,
+                        params: params.into_iter().map(Param::from).collect(),
                         is_async,
                         is_generator,
                         body: Some(match body {
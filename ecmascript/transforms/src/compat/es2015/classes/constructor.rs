@@ -118,8 +118,8 @@ pub(super) fn constructor_fn(c: Constructor) -> Function {
         params: c
             .params
             .into_iter()
-            .map(|pat| match pat {
-                PatOrTsParamProp::Pat(p) => p,
+            .map(|param| match param {
+                ParamOrTsParamProp::Param(p) => p,
                 _ => unimplemented!("TsParamProp in constructor"),
             })
             .collect(),
@@ -149,7 +149,7 @@ impl Fold<Expr> for ObjectLitFolder {
                                             body,
                                             is_async: false,
                                             is_generator: false,
-                                            params: vec![param],
+                                            params: vec![param.into()],
                                             decorators: Default::default(),
                                             type_params: Default::default(),
                                             return_type: Default::default(),
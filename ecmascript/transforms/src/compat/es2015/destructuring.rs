@@ -1047,13 +1047,14 @@ fn can_be_null(e: &Expr) -> bool {
         | Expr::JSXElement(..)
         | Expr::JSXFragment(..) => unreachable!("destructuring jsx"),
 
+        Expr::OptChain(ref e) => can_be_null(&e.expr),
+
         // Trust user
         Expr::TsNonNull(..) => false,
         Expr::TsAs(TsAsExpr { ref expr, .. })
         | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
         | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. })
         | Expr::TsConstAssertion(TsConstAssertion { ref expr, .. }) => can_be_null(expr),
-        Expr::TsOptChain(ref e) => can_be_null(&e.expr),
 
         Expr::Invalid(..) => unreachable!(),
     }
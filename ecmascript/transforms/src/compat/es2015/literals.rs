@@ -0,0 +1,57 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::{Fold, Mark};
+
+/// `@babel/plugin-transform-literals`
+///
+/// The code generator prints numeric literals in decimal and string literals
+/// without es2015 escapes, but it prints the original text of a literal if
+/// `keep_raw_literals` is enabled. The original text is not available here, so
+/// this pass marks all numeric and string literals as modified, which makes the
+/// code generator ignore it.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var b = 0b11;
+/// var o = 0o7;
+/// var s = "\u{1F600}";
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var b = 3;
+/// var o = 7;
+/// var s = "😀";
+/// ```
+pub fn literals() -> impl Pass {
+    Literals {
+        mark: Mark::fresh(Mark::root()),
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Literals {
+    mark: Mark,
+}
+
+impl Fold<Number> for Literals {
+    fn fold(&mut self, n: Number) -> Number {
+        Number {
+            span: n.span.apply_mark(self.mark),
+            ..n
+        }
+    }
+}
+
+impl Fold<Str> for Literals {
+    fn fold(&mut self, s: Str) -> Str {
+        Str {
+            span: s.span.apply_mark(self.mark),
+            ..s
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    pass::Pass,
+    util::{undefined, ExprFactory},
+};
+use ast::*;
+use std::mem;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-new-target`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///   console.log(new.target);
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///   console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+/// ```
+pub fn new_target() -> impl Pass {
+    NewTarget { ctx: Ctx::Other }
+}
+
+#[derive(Clone)]
+enum Ctx {
+    /// `new.target` is invalid here.
+    Other,
+    /// Function declaration or expression named `.0`.
+    Fn(Ident),
+    Constructor,
+    /// Methods, getters, setters and class properties.
+    Method,
+}
+
+struct NewTarget {
+    ctx: Ctx,
+}
+
+impl NewTarget {
+    fn fold_in<T>(&mut self, ctx: Ctx, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old = mem::replace(&mut self.ctx, ctx);
+        let node = node.fold_children(self);
+        self.ctx = old;
+        node
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        let ctx = Ctx::Fn(f.ident.clone());
+        self.fold_in(ctx, f)
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let ident = match f.ident {
+            Some(ref ident) => ident.clone(),
+            None if uses_new_target(&f.function) => private_ident!("_target"),
+            None => return self.fold_in(Ctx::Other, f),
+        };
+
+        let ctx = Ctx::Fn(ident.clone());
+        self.fold_in(
+            ctx,
+            FnExpr {
+                ident: Some(ident),
+                ..f
+            },
+        )
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        self.fold_in(Ctx::Constructor, c)
+    }
+}
+
+macro_rules! method {
+    ($T:tt) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, n: $T) -> $T {
+                self.fold_in(Ctx::Method, n)
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(ClassProp);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::MetaProp(MetaPropExpr {
+                ref meta, ref prop, ..
+            }) if &*meta.sym == "new" && &*prop.sym == "target" => {
+                let span = meta.span.with_hi(prop.span.hi());
+                let this_ctor = || ThisExpr { span: DUMMY_SP }.member(quote_ident!("constructor"));
+
+                match self.ctx {
+                    Ctx::Other => e,
+                    Ctx::Fn(ref name) => Expr::Cond(CondExpr {
+                        span,
+                        test: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::This(ThisExpr { span: DUMMY_SP }),
+                            op: op!("instanceof"),
+                            right: box Expr::Ident(name.clone()),
+                        }),
+                        cons: box this_ctor(),
+                        alt: undefined(DUMMY_SP),
+                    }),
+                    Ctx::Constructor => this_ctor(),
+                    Ctx::Method => *undefined(span),
+                }
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+fn uses_new_target(f: &Function) -> bool {
+    struct Visitor {
+        found: bool,
+    }
+
+    impl Visit<Function> for Visitor {
+        fn visit(&mut self, _: &Function) {}
+    }
+
+    impl Visit<Class> for Visitor {
+        fn visit(&mut self, _: &Class) {}
+    }
+
+    impl Visit<MetaPropExpr> for Visitor {
+        fn visit(&mut self, e: &MetaPropExpr) {
+            if &*e.meta.sym == "new" && &*e.prop.sym == "target" {
+                self.found = true;
+            }
+        }
+    }
+
+    let mut v = Visitor { found: false };
+    f.params.visit_with(&mut v);
+    f.body.visit_with(&mut v);
+    v.found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        babel_function,
+        "function Foo() {
+  const a = () => new.target;
+  console.log(new.target, a());
+}",
+        "function Foo() {
+  const a = () => this instanceof Foo ? this.constructor : void 0;
+  console.log(this instanceof Foo ? this.constructor : void 0, a());
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        babel_anonymous_function,
+        "var Foo = function () {
+  return new.target;
+};",
+        "var Foo = function _target() {
+  return this instanceof _target ? this.constructor : void 0;
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        babel_class,
+        "class Foo {
+  constructor() {
+    this.a = new.target;
+  }
+
+  foo() {
+    return new.target;
+  }
+}",
+        "class Foo {
+  constructor() {
+    this.a = this.constructor;
+  }
+
+  foo() {
+    return void 0;
+  }
+}"
+    );
+}
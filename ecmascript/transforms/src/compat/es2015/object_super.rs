@@ -0,0 +1,516 @@
+use crate::{
+    pass::Pass,
+    util::{prepend, ExprFactory, StmtLike},
+};
+use ast::*;
+use std::{fmt::Debug, mem};
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-object-super`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// let obj = {
+///   foo() {
+///     return super.foo();
+///   }
+/// };
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _obj;
+/// let obj = _obj = {
+///   foo() {
+///     return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+///   }
+/// };
+/// ```
+pub fn object_super() -> impl Pass {
+    ObjectSuper::default()
+}
+
+#[derive(Debug, Default)]
+struct ObjectSuper {
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T> Fold<Vec<T>> for ObjectSuper
+where
+    T: Debug + StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let old = mem::replace(&mut self.vars, vec![]);
+
+        let mut stmts = stmts.fold_children(self);
+
+        if !self.vars.is_empty() {
+            prepend(
+                &mut stmts,
+                T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    declare: false,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut self.vars, vec![]),
+                }))),
+            );
+        }
+
+        self.vars = old;
+        stmts
+    }
+}
+
+impl Fold<Expr> for ObjectSuper {
+    fn fold(&mut self, e: Expr) -> Expr {
+        // Inner objects are handled first, so `super` in them is already replaced
+        // while folding the outer object.
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Object(ObjectLit { span, props }) => {
+                if !props.iter().any(uses_super) {
+                    return Expr::Object(ObjectLit { span, props });
+                }
+
+                let obj = private_ident!("_obj");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(obj.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                let mut folder = SuperReplacer {
+                    obj: &obj,
+                    vars: &mut self.vars,
+                };
+                let props = props
+                    .into_iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(box Prop::Method(MethodProp { key, function })) => {
+                            PropOrSpread::Prop(box Prop::Method(MethodProp {
+                                key,
+                                function: Function {
+                                    params: function.params.fold_with(&mut folder),
+                                    body: function.body.fold_with(&mut folder),
+                                    ..function
+                                },
+                            }))
+                        }
+                        PropOrSpread::Prop(box Prop::Getter(GetterProp { span, key, body })) => {
+                            PropOrSpread::Prop(box Prop::Getter(GetterProp {
+                                span,
+                                key,
+                                body: body.fold_with(&mut folder),
+                            }))
+                        }
+                        PropOrSpread::Prop(box Prop::Setter(SetterProp {
+                            span,
+                            key,
+                            param,
+                            body,
+                        })) => PropOrSpread::Prop(box Prop::Setter(SetterProp {
+                            span,
+                            key,
+                            param: param.fold_with(&mut folder),
+                            body: body.fold_with(&mut folder),
+                        })),
+                        _ => prop,
+                    })
+                    .collect();
+
+                Expr::Assign(AssignExpr {
+                    span,
+                    left: PatOrExpr::Pat(box Pat::Ident(obj)),
+                    op: op!("="),
+                    right: box Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props,
+                    }),
+                })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Returns true if `prop` is a method which accesses `super`.
+fn uses_super(prop: &PropOrSpread) -> bool {
+    struct Visitor {
+        found: bool,
+    }
+
+    impl Visit<Class> for Visitor {
+        fn visit(&mut self, _: &Class) {}
+    }
+
+    impl Visit<Function> for Visitor {
+        fn visit(&mut self, _: &Function) {}
+    }
+
+    impl Visit<Super> for Visitor {
+        fn visit(&mut self, _: &Super) {
+            self.found = true;
+        }
+    }
+
+    let mut v = Visitor { found: false };
+    match *prop {
+        PropOrSpread::Prop(box Prop::Method(MethodProp { ref function, .. })) => {
+            function.params.visit_with(&mut v);
+            function.body.visit_with(&mut v);
+        }
+        PropOrSpread::Prop(box Prop::Getter(GetterProp { ref body, .. })) => {
+            body.visit_with(&mut v)
+        }
+        PropOrSpread::Prop(box Prop::Setter(SetterProp {
+            ref param,
+            ref body,
+            ..
+        })) => {
+            param.visit_with(&mut v);
+            body.visit_with(&mut v);
+        }
+        _ => {}
+    }
+    v.found
+}
+
+struct SuperReplacer<'a> {
+    obj: &'a Ident,
+    vars: &'a mut Vec<VarDeclarator>,
+}
+
+/// `super` in a nested class or function refers to another home object.
+impl Fold<Class> for SuperReplacer<'_> {
+    fn fold(&mut self, c: Class) -> Class {
+        c
+    }
+}
+
+impl Fold<Function> for SuperReplacer<'_> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<Expr> for SuperReplacer<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            // super.foo(a) => _get(_getPrototypeOf(_obj), "foo", this).call(this, a)
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(Super { span: super_token }),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                args,
+                type_args,
+            }) => {
+                let prop = prop_name(prop.fold_with(self), computed);
+                let args = args.fold_with(self);
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: self.get(super_token, prop).member(quote_ident!("call")).as_callee(),
+                    args: Some(ThisExpr { span: super_token }.as_arg())
+                        .into_iter()
+                        .chain(args)
+                        .collect(),
+                    type_args,
+                })
+            }
+
+            // super.foo = v => _set(_getPrototypeOf(_obj), "foo", v, this, true)
+            Expr::Assign(AssignExpr {
+                span,
+                left:
+                    PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(Super { span: super_token }),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                op,
+                right,
+            })
+            | Expr::Assign(AssignExpr {
+                span,
+                left:
+                    PatOrExpr::Pat(box Pat::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(Super { span: super_token }),
+                        prop,
+                        computed,
+                        ..
+                    }))),
+                op,
+                right,
+            }) => {
+                let prop = prop_name(prop.fold_with(self), computed);
+                let right = right.fold_with(self);
+
+                let value = match op {
+                    op!("=") => right,
+                    _ => box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: box self.get(super_token, prop.clone()),
+                        op: to_bin_op(op),
+                        right,
+                    }),
+                };
+
+                self.set(span, super_token, prop, value)
+            }
+
+            // super.foo++ => (_ref = +_get(...), _set(..., _ref + 1, this, true), _ref)
+            Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg:
+                    box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(Super { span: super_token }),
+                        prop,
+                        computed,
+                        ..
+                    }),
+            }) => {
+                let prop = prop_name(prop.fold_with(self), computed);
+                let old = private_ident!("_ref");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(old.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                let get = box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!(unary, "+"),
+                    arg: box self.get(super_token, prop.clone()),
+                });
+                let value = box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box Expr::Ident(old.clone()),
+                    op: match op {
+                        op!("++") => op!(bin, "+"),
+                        op!("--") => op!(bin, "-"),
+                    },
+                    right: box Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: 1.0,
+                    })),
+                });
+
+                let mut exprs = vec![
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(old.clone())),
+                        op: op!("="),
+                        right: get,
+                    }),
+                    box self.set(DUMMY_SP, super_token, prop, value),
+                ];
+                if !prefix {
+                    exprs.push(box Expr::Ident(old));
+                }
+
+                Expr::Seq(SeqExpr { span, exprs })
+            }
+
+            // super.foo => _get(_getPrototypeOf(_obj), "foo", this)
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Super(Super { span: super_token }),
+                prop,
+                computed,
+                ..
+            }) => {
+                let prop = prop_name(prop.fold_with(self), computed);
+                self.get(super_token, prop)
+            }
+
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl SuperReplacer<'_> {
+    fn proto(&self) -> Expr {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(get_prototype_of, "getPrototypeOf"),
+            args: vec![self.obj.clone().as_arg()],
+            type_args: Default::default(),
+        })
+    }
+
+    fn get(&self, super_token: Span, prop: Expr) -> Expr {
+        Expr::Call(CallExpr {
+            span: super_token,
+            callee: helper!(get, "get"),
+            args: vec![
+                self.proto().as_arg(),
+                prop.as_arg(),
+                ThisExpr { span: super_token }.as_arg(),
+            ],
+            type_args: Default::default(),
+        })
+    }
+
+    fn set(&self, span: Span, super_token: Span, prop: Expr, value: Box<Expr>) -> Expr {
+        Expr::Call(CallExpr {
+            span,
+            callee: helper!(set, "set"),
+            args: vec![
+                self.proto().as_arg(),
+                prop.as_arg(),
+                value.as_arg(),
+                ThisExpr { span: super_token }.as_arg(),
+                // strict
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: true,
+                })
+                .as_arg(),
+            ],
+            type_args: Default::default(),
+        })
+    }
+}
+
+/// `super.foo` => `'foo'`
+fn prop_name(prop: Box<Expr>, computed: bool) -> Expr {
+    match *prop {
+        Expr::Ident(Ident { sym, span, .. }) if !computed => Expr::Lit(Lit::Str(Str {
+            span,
+            value: sym,
+            has_escape: false,
+        })),
+        e => e,
+    }
+}
+
+fn to_bin_op(op: AssignOp) -> BinaryOp {
+    match op {
+        op!("=") => unreachable!(),
+
+        op!("+=") => op!(bin, "+"),
+        op!("-=") => op!(bin, "-"),
+        op!("*=") => op!("*"),
+        op!("/=") => op!("/"),
+        op!("%=") => op!("%"),
+        op!("<<=") => op!("<<"),
+        op!(">>=") => op!(">>"),
+        op!(">>>=") => op!(">>>"),
+        op!("|=") => op!("|"),
+        op!("&=") => op!("&"),
+        op!("^=") => op!("^"),
+        op!("**=") => op!("**"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        babel_call,
+        "let obj = {
+  foo() {
+    return super.foo(1, 2);
+  }
+};",
+        "var _obj;
+let obj = _obj = {
+  foo() {
+    return _get(_getPrototypeOf(_obj), 'foo', this).call(this, 1, 2);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        babel_get_set,
+        "const obj = {
+  get foo() {
+    return super.foo;
+  },
+  set foo(v) {
+    super.foo = v;
+  },
+  bar() {
+    super['bar'] += 1;
+  }
+};",
+        "var _obj;
+const obj = _obj = {
+  get foo() {
+    return _get(_getPrototypeOf(_obj), 'foo', this);
+  },
+  set foo(v) {
+    _set(_getPrototypeOf(_obj), 'foo', v, this, true);
+  },
+  bar() {
+    _set(
+      _getPrototypeOf(_obj),
+      'bar',
+      _get(_getPrototypeOf(_obj), 'bar', this) + 1,
+      this,
+      true
+    );
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        arrow,
+        "const obj = {
+  foo() {
+    return () => super.foo;
+  }
+};",
+        "var _obj;
+const obj = _obj = {
+  foo() {
+    return () => _get(_getPrototypeOf(_obj), 'foo', this);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        ignore_class,
+        "const obj = {
+  foo() {
+    return class extends Foo {
+      bar() {
+        return super.bar();
+      }
+    };
+  }
+};",
+        "const obj = {
+  foo() {
+    return class extends Foo {
+      bar() {
+        return super.bar();
+      }
+    };
+  }
+};"
+    );
+}
@@ -0,0 +1,375 @@
+use self::{
+    emitter::Emitter,
+    hoist::{runtime, ArgumentsReplacer, Hoister},
+};
+use crate::{
+    pass::Pass,
+    util::{contains_this_expr, prepend, ExprFactory, StmtLike},
+};
+use ast::*;
+use fxhash::FxHashSet;
+use std::{fmt::Debug, mem};
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+mod emitter;
+mod hoist;
+mod leap;
+#[cfg(test)]
+mod tests;
+
+/// `@babel/plugin-transform-regenerator`
+///
+/// Generated code depends on `regeneratorRuntime`, which is provided by
+/// `regenerator-runtime`.
+///
+/// ## In
+///
+/// ```js
+/// function* foo() {
+///   yield 1;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _marked = regeneratorRuntime.mark(foo);
+/// function foo() {
+///   return regeneratorRuntime.wrap(function foo$(_context) {
+///     while (1) switch (_context.prev = _context.next) {
+///       case 0:
+///         _context.next = 2;
+///         return 1;
+///       case 2:
+///       case "end":
+///         return _context.stop();
+///     }
+///   }, _marked);
+/// }
+/// ```
+pub fn regenerator() -> impl Pass {
+    Regenerator::default()
+}
+
+#[derive(Debug, Default)]
+struct Regenerator {
+    /// `var _marked = regeneratorRuntime.mark(foo);`
+    top_level_vars: Vec<VarDeclarator>,
+}
+
+impl<T> Fold<Vec<T>> for Regenerator
+where
+    T: Debug + StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        // This is to support nested block statements
+        let old = mem::replace(&mut self.top_level_vars, vec![]);
+
+        let mut stmts = stmts.fold_children(self);
+
+        if !self.top_level_vars.is_empty() {
+            prepend(
+                &mut stmts,
+                T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    declare: false,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut self.top_level_vars, vec![]),
+                }))),
+            );
+        }
+
+        self.top_level_vars = old;
+        stmts
+    }
+}
+
+impl Fold<FnDecl> for Regenerator {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        if !is_generator(&f.function) {
+            return f.fold_children(self);
+        }
+
+        let function = f.function.fold_children(self);
+        let marked = self.mark(&f.ident);
+        let function = self.fold_generator(Some(&f.ident), marked, function);
+
+        FnDecl { function, ..f }
+    }
+}
+
+impl Fold<DefaultDecl> for Regenerator {
+    fn fold(&mut self, decl: DefaultDecl) -> DefaultDecl {
+        match decl {
+            DefaultDecl::Fn(FnExpr { ident, function }) => {
+                if !is_generator(&function) {
+                    return DefaultDecl::Fn(FnExpr {
+                        ident,
+                        function: function.fold_with(self),
+                    });
+                }
+
+                let ident = ident.unwrap_or_else(|| private_ident!("_callee"));
+
+                let function = function.fold_children(self);
+                let marked = self.mark(&ident);
+                let function = self.fold_generator(Some(&ident), marked, function);
+
+                DefaultDecl::Fn(FnExpr {
+                    ident: Some(ident),
+                    function,
+                })
+            }
+            _ => decl.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Expr> for Regenerator {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Fn(FnExpr { ident, function }) => {
+                if !is_generator(&function) {
+                    return Expr::Fn(FnExpr {
+                        ident,
+                        function: function.fold_with(self),
+                    });
+                }
+
+                let ident = ident.unwrap_or_else(|| private_ident!("_callee"));
+
+                let function = function.fold_children(self);
+                let function =
+                    self.fold_generator(Some(&ident), box Expr::Ident(ident.clone()), function);
+
+                // regeneratorRuntime.mark(function foo() {})
+                Expr::Call(CallExpr {
+                    span: function.span,
+                    callee: runtime("mark").as_callee(),
+                    args: vec![Expr::Fn(FnExpr {
+                        ident: Some(ident),
+                        function,
+                    })
+                    .as_arg()],
+                    type_args: Default::default(),
+                })
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for Regenerator {
+    /// Handles methods, which don't have a name to mark.
+    ///
+    /// The body of a method is moved into a marked function expression, which
+    /// is called with `this` and `arguments` of the method.
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+        if !is_generator(&f) {
+            return f;
+        }
+        let body = match f.body {
+            Some(body) => body,
+            None => return f,
+        };
+        let span = body.span;
+
+        let callee = private_ident!("_callee");
+        let function = self.fold_generator(
+            Some(&callee),
+            box Expr::Ident(callee.clone()),
+            Function {
+                span: DUMMY_SP,
+                params: vec![],
+                decorators: Default::default(),
+                body: Some(body),
+                is_generator: true,
+                is_async: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        );
+
+        // regeneratorRuntime.mark(function _callee() {}).apply(this, arguments)
+        let marked = CallExpr {
+            span: DUMMY_SP,
+            callee: runtime("mark").as_callee(),
+            args: vec![Expr::Fn(FnExpr {
+                ident: Some(callee),
+                function,
+            })
+            .as_arg()],
+            type_args: Default::default(),
+        };
+        let call = CallExpr {
+            span: DUMMY_SP,
+            callee: marked.member(quote_ident!("apply")).as_callee(),
+            args: vec![
+                ThisExpr { span: DUMMY_SP }.as_arg(),
+                quote_ident!(js_word!("arguments")).as_arg(),
+            ],
+            type_args: Default::default(),
+        };
+
+        Function {
+            body: Some(BlockStmt {
+                span,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box Expr::Call(call)),
+                })],
+            }),
+            is_generator: false,
+            ..f
+        }
+    }
+}
+
+impl Regenerator {
+    /// Adds `var _marked = regeneratorRuntime.mark(foo);` and returns
+    /// `_marked`.
+    fn mark(&mut self, ident: &Ident) -> Box<Expr> {
+        let marked = private_ident!("_marked");
+
+        self.top_level_vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(marked.clone()),
+            init: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: runtime("mark").as_callee(),
+                args: vec![ident.clone().as_arg()],
+                type_args: Default::default(),
+            })),
+            definite: false,
+        });
+
+        box Expr::Ident(marked)
+    }
+
+    /// Converts body of a generator function into
+    /// `return regeneratorRuntime.wrap(function foo$(_context) {}, outer_fn)`.
+    fn fold_generator(
+        &mut self,
+        name: Option<&Ident>,
+        outer_fn: Box<Expr>,
+        f: Function,
+    ) -> Function {
+        let body = match f.body {
+            Some(body) => body,
+            None => return f,
+        };
+
+        let mut hoister = Hoister::default();
+        let stmts = body.stmts.fold_with(&mut hoister);
+
+        let mut args_replacer = ArgumentsReplacer {
+            ident: private_ident!("_args"),
+            found: false,
+        };
+        let stmts = stmts.fold_with(&mut args_replacer);
+        let uses_this = contains_this_expr(&stmts);
+
+        let ctx = private_ident!("_context");
+        let mut emitter = Emitter::new(ctx.clone());
+        emitter.explode_stmts(stmts);
+
+        let mut vars = hoister.vars;
+        vars.extend(mem::replace(&mut emitter.vars, vec![]));
+        let (dispatch_loop, try_locs_list) = emitter.finish();
+
+        let inner_name = match name {
+            Some(name) => format!("{}$", name.sym),
+            None => String::from("_callee$"),
+        };
+        let inner_fn = Expr::Fn(FnExpr {
+            ident: Some(private_ident!(inner_name)),
+            function: Function {
+                params: vec![Pat::Ident(ctx).into()],
+                decorators: Default::default(),
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![dispatch_loop],
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        });
+
+        // regeneratorRuntime.wrap(innerFn, outerFn, self, tryLocsList)
+        let mut wrap_args = vec![
+            inner_fn.as_arg(),
+            ExprOrSpread {
+                spread: None,
+                expr: outer_fn,
+            },
+        ];
+        if uses_this {
+            wrap_args.push(ThisExpr { span: DUMMY_SP }.as_arg());
+        } else if try_locs_list.is_some() {
+            wrap_args.push(Expr::Lit(Lit::Null(Null { span: DUMMY_SP })).as_arg());
+        }
+        if let Some(try_locs_list) = try_locs_list {
+            wrap_args.push(Expr::Array(try_locs_list).as_arg());
+        }
+
+        let mut decls = vec![];
+        let mut declared = FxHashSet::default();
+        for var in vars {
+            if declared.insert((var.sym.clone(), var.span.ctxt())) {
+                decls.push(VarDeclarator {
+                    span: var.span,
+                    name: Pat::Ident(var),
+                    init: None,
+                    definite: false,
+                });
+            }
+        }
+        if args_replacer.found {
+            decls.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(args_replacer.ident),
+                init: Some(box Expr::Ident(quote_ident!(js_word!("arguments")))),
+                definite: false,
+            });
+        }
+
+        let mut stmts = vec![];
+        if !decls.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls,
+            })));
+        }
+        stmts.extend(hoister.fns.into_iter().map(|f| Stmt::Decl(Decl::Fn(f))));
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: runtime("wrap").as_callee(),
+                args: wrap_args,
+                type_args: Default::default(),
+            })),
+        }));
+
+        Function {
+            body: Some(BlockStmt {
+                span: body.span,
+                stmts,
+            }),
+            is_generator: false,
+            ..f
+        }
+    }
+}
+
+/// Async generators are handled by `es2018::async_generator`.
+fn is_generator(f: &Function) -> bool {
+    f.is_generator && !f.is_async
+}
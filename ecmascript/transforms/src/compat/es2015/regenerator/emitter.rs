@@ -0,0 +1,1613 @@
+use super::{
+    hoist::runtime,
+    leap::{Entry, LeapManager},
+};
+use crate::util::{extract_var_ids, undefined, ExprFactory, HANDLER};
+use ast::*;
+use fxhash::FxHashSet;
+use std::{iter, mem};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// A location in the generated code.
+///
+/// Locations are emitted as number literals and resolved after all statements
+/// are exploded.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Loc(usize);
+
+#[derive(Debug)]
+struct TryEntry {
+    first_loc: Loc,
+    catch_loc: Option<Loc>,
+    finally_loc: Option<Loc>,
+    after_loc: Loc,
+}
+
+/// Port of `Emitter` of regenerator.
+///
+/// The body of a generator function is exploded into a flat list of
+/// statements, which is split into the cases of `switch (_context.prev =
+/// _context.next) {}`.
+pub(super) struct Emitter {
+    ctx: Ident,
+    loc_mark: Mark,
+    /// Resolved value of each location.
+    locs: Vec<Option<usize>>,
+    /// Indices of `listing` where a new case starts.
+    marked: FxHashSet<usize>,
+    listing: Vec<Stmt>,
+    next_temp_id: usize,
+    leaps: LeapManager,
+    try_entries: Vec<TryEntry>,
+    /// Variables which should be declared in the outer function.
+    pub vars: Vec<Ident>,
+}
+
+impl Emitter {
+    pub fn new(ctx: Ident) -> Self {
+        let mut marked = FxHashSet::default();
+        marked.insert(0);
+
+        Emitter {
+            ctx,
+            loc_mark: Mark::fresh(Mark::root()),
+            locs: vec![],
+            marked,
+            listing: vec![],
+            next_temp_id: 0,
+            leaps: Default::default(),
+            try_entries: vec![],
+            vars: vec![],
+        }
+    }
+
+    /// Returns `(dispatch_loop, try_locs_list)`.
+    pub fn finish(mut self) -> (Stmt, Option<ArrayLit>) {
+        let final_loc = self.loc();
+        self.locs[final_loc.0] = Some(self.listing.len());
+
+        let mut cases: Vec<SwitchCase> = vec![];
+        let mut already_ended = false;
+
+        for (i, stmt) in mem::replace(&mut self.listing, vec![]).into_iter().enumerate() {
+            if self.marked.contains(&i) {
+                cases.push(SwitchCase {
+                    span: DUMMY_SP,
+                    test: Some(box Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: i as _,
+                    }))),
+                    cons: vec![],
+                });
+                already_ended = false;
+            }
+
+            if !already_ended {
+                already_ended = is_completion(&stmt);
+                cases.last_mut().unwrap().cons.push(stmt);
+            }
+        }
+
+        cases.push(SwitchCase {
+            span: DUMMY_SP,
+            test: Some(self.loc_expr(final_loc)),
+            cons: vec![],
+        });
+        // case "end":
+        //     return _context.stop();
+        cases.push(SwitchCase {
+            span: DUMMY_SP,
+            test: Some(box Expr::Lit(Lit::Str(quote_str!("end")))),
+            cons: vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: self.ctx_prop("stop").as_callee(),
+                    args: vec![],
+                    type_args: None,
+                })),
+            })],
+        });
+
+        // while (1) switch (_context.prev = _context.next) {}
+        let dispatch_loop = Stmt::While(WhileStmt {
+            span: DUMMY_SP,
+            test: box Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: 1.0,
+            })),
+            body: box Stmt::Switch(SwitchStmt {
+                span: DUMMY_SP,
+                discriminant: box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left: PatOrExpr::Expr(box self.ctx_prop("prev")),
+                    right: box self.ctx_prop("next"),
+                }),
+                cases,
+            }),
+        });
+
+        let try_locs_list = if self.try_entries.is_empty() {
+            None
+        } else {
+            let entries = mem::replace(&mut self.try_entries, vec![]);
+            Some(ArrayLit {
+                span: DUMMY_SP,
+                elems: entries
+                    .into_iter()
+                    .map(|e| Some(self.try_entry_to_array(e).as_arg()))
+                    .collect(),
+            })
+        };
+
+        let mut resolver = LocResolver {
+            mark: self.loc_mark,
+            locs: &self.locs,
+        };
+
+        (
+            dispatch_loop.fold_with(&mut resolver),
+            try_locs_list.fold_with(&mut resolver),
+        )
+    }
+
+    /// `[tryLoc, catchLoc, finallyLoc, afterLoc]`
+    fn try_entry_to_array(&self, e: TryEntry) -> Expr {
+        let mut elems = vec![
+            Some(to_arg(self.loc_expr(e.first_loc))),
+            e.catch_loc.map(|l| to_arg(self.loc_expr(l))),
+        ];
+
+        if let Some(finally_loc) = e.finally_loc {
+            elems.push(Some(to_arg(self.loc_expr(finally_loc))));
+            elems.push(Some(to_arg(self.loc_expr(e.after_loc))));
+        }
+
+        Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems,
+        })
+    }
+
+    fn loc(&mut self) -> Loc {
+        self.locs.push(None);
+        Loc(self.locs.len() - 1)
+    }
+
+    fn loc_expr(&self, loc: Loc) -> Box<Expr> {
+        box Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP.apply_mark(self.loc_mark),
+            value: loc.0 as _,
+        }))
+    }
+
+    /// Sets the exact location of the given location to the current location.
+    fn mark(&mut self, loc: Loc) {
+        let idx = self.listing.len();
+        debug_assert_eq!(self.locs[loc.0], None, "location is marked twice");
+        self.locs[loc.0] = Some(idx);
+        self.marked.insert(idx);
+    }
+
+    fn emit(&mut self, stmt: Stmt) {
+        self.listing.push(stmt)
+    }
+
+    fn emit_expr(&mut self, expr: Box<Expr>) {
+        self.emit(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr,
+        }))
+    }
+
+    /// Emits `lhs = rhs` and returns `lhs`.
+    fn emit_assign(&mut self, lhs: Box<Expr>, rhs: Box<Expr>) -> Box<Expr> {
+        self.emit_expr(box Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Expr(lhs.clone()),
+            right: rhs,
+        }));
+        lhs
+    }
+
+    /// `_context.name`
+    fn ctx_prop(&self, name: &str) -> Expr {
+        self.ctx.clone().member(quote_ident!(name))
+    }
+
+    fn make_temp_name(&mut self) -> String {
+        let name = format!("t{}", self.next_temp_id);
+        self.next_temp_id += 1;
+        name
+    }
+
+    /// `_context.tN`
+    fn make_temp_var(&mut self) -> Box<Expr> {
+        let name = self.make_temp_name();
+        box self.ctx_prop(&name)
+    }
+
+    /// `_context.next = loc; break;`
+    fn jump_stmts(&self, loc: Loc) -> Vec<Stmt> {
+        vec![
+            Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: PatOrExpr::Expr(box self.ctx_prop("next")),
+                right: self.loc_expr(loc),
+            })
+            .into_stmt(),
+            Stmt::Break(BreakStmt {
+                span: DUMMY_SP,
+                label: None,
+            }),
+        ]
+    }
+
+    fn jump(&mut self, loc: Loc) {
+        for stmt in self.jump_stmts(loc) {
+            self.emit(stmt)
+        }
+    }
+
+    /// Jumps to the location computed by `to`.
+    fn jump_to_expr(&mut self, to: Box<Expr>) {
+        self.emit_assign(box self.ctx_prop("next"), to);
+        self.emit(Stmt::Break(BreakStmt {
+            span: DUMMY_SP,
+            label: None,
+        }));
+    }
+
+    fn jump_if(&mut self, test: Box<Expr>, loc: Loc) {
+        let cons = self.jump_stmts(loc);
+        self.emit(Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test,
+            cons: box Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts: cons,
+            }),
+            alt: None,
+        }));
+    }
+
+    fn jump_if_not(&mut self, test: Box<Expr>, loc: Loc) {
+        let test = match *test {
+            Expr::Unary(UnaryExpr {
+                op: op!("!"), arg, ..
+            }) => arg,
+            _ => box Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("!"),
+                arg: test,
+            }),
+        };
+
+        self.jump_if(test, loc)
+    }
+
+    /// `_context.prev = loc`
+    fn update_ctx_prev_loc(&mut self, loc: Loc) {
+        let loc = self.loc_expr(loc);
+        self.emit_assign(box self.ctx_prop("prev"), loc);
+    }
+
+    /// `return _context.abrupt(type, arg);`
+    fn emit_abrupt_completion(&mut self, ty: &str, arg: Option<Box<Expr>>) {
+        let args = iter::once(quote_str!(ty).as_arg())
+            .chain(arg.map(to_arg))
+            .collect();
+
+        self.emit(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: self.ctx_prop("abrupt").as_callee(),
+                args,
+                type_args: None,
+            })),
+        }));
+    }
+
+    fn with_entry<F>(&mut self, entry: Entry, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.leaps.push(entry);
+        op(self);
+        self.leaps.pop();
+    }
+
+    pub fn explode_stmts(&mut self, stmts: Vec<Stmt>) {
+        for stmt in stmts {
+            self.explode_stmt(stmt, None)
+        }
+    }
+
+    fn explode_stmt(&mut self, s: Stmt, label: Option<JsWord>) {
+        match s {
+            // Statements in a block are exploded one by one, because the hoister
+            // removed declarations from them.
+            Stmt::Block(BlockStmt { stmts, .. }) => return self.explode_stmts(stmts),
+            Stmt::Empty(..) => return,
+            _ => {}
+        }
+
+        if !contains_leap(&s) {
+            return self.emit(s);
+        }
+
+        match s {
+            Stmt::Expr(ExprStmt { expr, .. }) => {
+                self.explode_expr(expr, true);
+            }
+
+            Stmt::Labeled(LabeledStmt { label, body, .. }) => {
+                let after = self.loc();
+
+                self.with_entry(
+                    Entry::Labeled {
+                        break_loc: after,
+                        label: label.sym.clone(),
+                    },
+                    |e| e.explode_stmt(*body, Some(label.sym)),
+                );
+
+                self.mark(after);
+            }
+
+            Stmt::While(WhileStmt { test, body, .. }) => {
+                let before = self.loc();
+                let after = self.loc();
+
+                self.mark(before);
+                let test = self.explode_expr(test, false);
+                self.jump_if_not(test, after);
+                self.with_entry(
+                    Entry::Loop {
+                        break_loc: after,
+                        continue_loc: before,
+                        label,
+                    },
+                    |e| e.explode_stmt(*body, None),
+                );
+                self.jump(before);
+                self.mark(after);
+            }
+
+            Stmt::DoWhile(DoWhileStmt { test, body, .. }) => {
+                let first = self.loc();
+                let test_loc = self.loc();
+                let after = self.loc();
+
+                self.mark(first);
+                self.with_entry(
+                    Entry::Loop {
+                        break_loc: after,
+                        continue_loc: test_loc,
+                        label,
+                    },
+                    |e| e.explode_stmt(*body, None),
+                );
+                self.mark(test_loc);
+                let test = self.explode_expr(test, false);
+                self.jump_if(test, first);
+                self.mark(after);
+            }
+
+            Stmt::For(ForStmt {
+                init,
+                test,
+                update,
+                body,
+                ..
+            }) => {
+                let head = self.loc();
+                let update_loc = self.loc();
+                let after = self.loc();
+
+                match init {
+                    Some(VarDeclOrExpr::Expr(init)) => {
+                        self.explode_expr(init, true);
+                    }
+                    Some(VarDeclOrExpr::VarDecl(..)) => {
+                        unreachable!("variable declarations should be hoisted")
+                    }
+                    None => {}
+                }
+
+                self.mark(head);
+                if let Some(test) = test {
+                    let test = self.explode_expr(test, false);
+                    self.jump_if_not(test, after);
+                }
+
+                self.with_entry(
+                    Entry::Loop {
+                        break_loc: after,
+                        continue_loc: update_loc,
+                        label,
+                    },
+                    |e| e.explode_stmt(*body, None),
+                );
+
+                self.mark(update_loc);
+                if let Some(update) = update {
+                    self.explode_expr(update, true);
+                }
+                self.jump(head);
+                self.mark(after);
+            }
+
+            Stmt::ForIn(ForInStmt {
+                left, right, body, ..
+            }) => {
+                let head = self.loc();
+                let after = self.loc();
+
+                // _context.t0 = regeneratorRuntime.keys(right);
+                let key_iter_next_fn = self.make_temp_var();
+                let right = self.explode_expr(right, false);
+                self.emit_assign(
+                    key_iter_next_fn.clone(),
+                    box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: runtime("keys").as_callee(),
+                        args: vec![to_arg(right)],
+                        type_args: None,
+                    }),
+                );
+
+                self.mark(head);
+
+                // if ((_context.t1 = _context.t0()).done) {}
+                let key_info = self.make_temp_var();
+                self.jump_if(
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(key_info.clone()),
+                        right: box Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: ExprOrSuper::Expr(key_iter_next_fn),
+                            args: vec![],
+                            type_args: None,
+                        }),
+                    })
+                    .wrap_with_paren()
+                    .member(quote_ident!("done")),
+                    after,
+                );
+
+                // left = _context.t1.value;
+                let left = match left {
+                    VarDeclOrPat::Pat(Pat::Expr(e)) => PatOrExpr::Expr(e),
+                    VarDeclOrPat::Pat(p) => PatOrExpr::Pat(box p),
+                    VarDeclOrPat::VarDecl(..) => {
+                        unreachable!("variable declarations should be hoisted")
+                    }
+                };
+                self.emit_expr(box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left,
+                    right: box (*key_info).member(quote_ident!("value")),
+                }));
+
+                self.with_entry(
+                    Entry::Loop {
+                        break_loc: after,
+                        continue_loc: head,
+                        label,
+                    },
+                    |e| e.explode_stmt(*body, None),
+                );
+
+                self.jump(head);
+                self.mark(after);
+            }
+
+            Stmt::Break(BreakStmt { label, .. }) => {
+                let loc = self.leaps.break_loc(label.as_ref().map(|l| &l.sym));
+                let loc = self.loc_expr(loc);
+                self.emit_abrupt_completion("break", Some(loc))
+            }
+
+            Stmt::Continue(ContinueStmt { label, .. }) => {
+                let loc = self.leaps.continue_loc(label.as_ref().map(|l| &l.sym));
+                let loc = self.loc_expr(loc);
+                self.emit_abrupt_completion("continue", Some(loc))
+            }
+
+            Stmt::Switch(SwitchStmt {
+                discriminant,
+                cases,
+                ..
+            }) => {
+                // _context.t0 = discriminant;
+                let disc = self.make_temp_var();
+                let discriminant = self.explode_expr(discriminant, false);
+                self.emit_assign(disc.clone(), discriminant);
+
+                let after = self.loc();
+                let default_loc = self.loc();
+
+                let mut case_locs = Vec::with_capacity(cases.len());
+                let mut bodies = Vec::with_capacity(cases.len());
+                let mut condition = self.loc_expr(default_loc);
+                let mut tests = vec![];
+
+                for case in cases {
+                    let loc = match case.test {
+                        Some(test) => {
+                            let loc = self.loc();
+                            tests.push((test, loc));
+                            loc
+                        }
+                        None => default_loc,
+                    };
+                    case_locs.push(loc);
+                    bodies.push(case.cons);
+                }
+
+                // _context.t0 === a ? 1 : _context.t0 === b ? 2 : default
+                for (test, loc) in tests.into_iter().rev() {
+                    condition = box Expr::Cond(CondExpr {
+                        span: DUMMY_SP,
+                        test: box (*disc).clone().make_eq(*test),
+                        cons: self.loc_expr(loc),
+                        alt: condition,
+                    });
+                }
+
+                self.jump_to_expr(condition);
+
+                self.with_entry(Entry::Switch { break_loc: after }, |e| {
+                    for (loc, body) in case_locs.into_iter().zip(bodies) {
+                        e.mark(loc);
+                        e.explode_stmts(body);
+                    }
+                });
+
+                self.mark(after);
+                if self.locs[default_loc.0].is_none() {
+                    self.mark(default_loc);
+                }
+            }
+
+            Stmt::If(IfStmt {
+                test, cons, alt, ..
+            }) => {
+                let else_loc = alt.as_ref().map(|_| self.loc());
+                let after = self.loc();
+
+                let test = self.explode_expr(test, false);
+                self.jump_if_not(test, else_loc.unwrap_or(after));
+                self.explode_stmt(*cons, None);
+
+                if let Some(alt) = alt {
+                    self.jump(after);
+                    self.mark(else_loc.unwrap());
+                    self.explode_stmt(*alt, None);
+                }
+
+                self.mark(after);
+            }
+
+            Stmt::Return(ReturnStmt { arg, .. }) => {
+                let arg = arg.map(|arg| self.explode_expr(arg, false));
+                self.emit_abrupt_completion("return", arg)
+            }
+
+            Stmt::Throw(ThrowStmt { span, arg }) => {
+                let arg = self.explode_expr(arg, false);
+                self.emit(Stmt::Throw(ThrowStmt { span, arg }))
+            }
+
+            Stmt::Try(s) => self.explode_try(s),
+
+            Stmt::ForOf(ForOfStmt { span, .. }) => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            span,
+                            "regenerator: for-of statement should be removed by es2015::for_of \
+                             before regenerator",
+                        )
+                        .emit()
+                });
+                self.emit(s)
+            }
+
+            _ => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(s.span(), "regenerator: unsupported statement")
+                        .emit()
+                });
+                self.emit(s)
+            }
+        }
+    }
+
+    fn explode_try(&mut self, s: TryStmt) {
+        let after = self.loc();
+        let catch_loc = s.handler.as_ref().map(|_| self.loc());
+        let finally_loc = s.finalizer.as_ref().map(|_| self.loc());
+
+        let first_loc = self.loc();
+        self.mark(first_loc);
+        self.try_entries.push(TryEntry {
+            first_loc,
+            catch_loc,
+            finally_loc,
+            after_loc: after,
+        });
+
+        self.update_ctx_prev_loc(first_loc);
+        self.explode_stmts(s.block.stmts);
+
+        if let Some(handler) = s.handler {
+            let catch_loc = catch_loc.unwrap();
+            self.jump(finally_loc.unwrap_or(after));
+
+            self.mark(catch_loc);
+            self.update_ctx_prev_loc(catch_loc);
+
+            // _context.t0 = _context["catch"](tryLoc);
+            let catch_call = box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: self
+                    .ctx
+                    .clone()
+                    .computed_member(quote_str!("catch"))
+                    .as_callee(),
+                args: vec![to_arg(self.loc_expr(first_loc))],
+                type_args: None,
+            });
+
+            let mut body = handler.body.stmts;
+            match handler.param {
+                Some(param) => {
+                    // The caught error is stored in a temporary variable, as hoisting the
+                    // parameter would clobber a variable of the outer scope.
+                    let temp = self.make_temp_var();
+                    let safe_param = self.emit_assign(temp, catch_call);
+
+                    match param {
+                        Pat::Ident(param) => {
+                            body = body.fold_with(&mut CatchParamReplacer {
+                                param,
+                                safe_param,
+                                in_assign_target: false,
+                            });
+                        }
+                        param => {
+                            self.vars.extend(extract_var_ids(&param));
+                            self.emit_expr(box Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                op: op!("="),
+                                left: PatOrExpr::Pat(box param),
+                                right: safe_param,
+                            }));
+                        }
+                    }
+                }
+                None => self.emit_expr(catch_call),
+            }
+
+            self.explode_stmts(body);
+        }
+
+        if let Some(finalizer) = s.finalizer {
+            let finally_loc = finally_loc.unwrap();
+
+            self.mark(finally_loc);
+            self.update_ctx_prev_loc(finally_loc);
+            self.explode_stmts(finalizer.stmts);
+
+            // return _context.finish(finallyLoc);
+            let finish = box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: self.ctx_prop("finish").as_callee(),
+                args: vec![to_arg(self.loc_expr(finally_loc))],
+                type_args: None,
+            });
+            self.emit(Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(finish),
+            }));
+        }
+
+        self.mark(after);
+    }
+
+    /// Explodes `child` and stores the result into a temporary variable if
+    /// required.
+    ///
+    /// As a sibling expression contains a leap, the result of `child` should
+    /// be stored before the leap to preserve the order of evaluation.
+    fn explode_via_temp_var(
+        &mut self,
+        temp_var: Option<Box<Expr>>,
+        child: Box<Expr>,
+        ignore_child_result: bool,
+    ) -> Box<Expr> {
+        let result = self.explode_expr(child, ignore_child_result);
+
+        if ignore_child_result {
+            return result;
+        }
+
+        match (temp_var, &*result) {
+            (Some(temp_var), _) => self.emit_assign(temp_var, result),
+            (None, Expr::Lit(..)) => result,
+            (None, _) => {
+                let temp_var = self.make_temp_var();
+                self.emit_assign(temp_var, result)
+            }
+        }
+    }
+
+    fn explode_args(&mut self, args: Vec<ExprOrSpread>) -> Vec<ExprOrSpread> {
+        args.into_iter()
+            .map(|arg| ExprOrSpread {
+                spread: arg.spread,
+                expr: self.explode_via_temp_var(None, arg.expr, false),
+            })
+            .collect()
+    }
+
+    fn finish_expr(&mut self, expr: Box<Expr>, ignore_result: bool) -> Box<Expr> {
+        if ignore_result {
+            self.emit_expr(expr);
+            undefined(DUMMY_SP)
+        } else {
+            expr
+        }
+    }
+
+    pub fn explode_expr(&mut self, e: Box<Expr>, ignore_result: bool) -> Box<Expr> {
+        if !contains_leap(&e) {
+            return self.finish_expr(e, ignore_result);
+        }
+
+        // As `e` contains a leap and it's not a yield expression, one of its
+        // children contains a leap. So every children should be stored into
+        // temporary variables.
+        match *e {
+            Expr::Paren(ParenExpr { expr, .. }) => self.explode_expr(expr, ignore_result),
+
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => {
+                let obj = match obj {
+                    ExprOrSuper::Expr(obj) => {
+                        ExprOrSuper::Expr(self.explode_via_temp_var(None, obj, false))
+                    }
+                    obj => obj,
+                };
+                let prop = if computed {
+                    self.explode_via_temp_var(None, prop, false)
+                } else {
+                    prop
+                };
+
+                self.finish_expr(
+                    box Expr::Member(MemberExpr {
+                        span,
+                        obj,
+                        prop,
+                        computed,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::Call(CallExpr {
+                span,
+                callee,
+                args,
+                type_args,
+            }) => {
+                let has_leaping_args = args.iter().any(|arg| contains_leap(&arg.expr));
+                let mut injected_this = None;
+
+                let callee = match callee {
+                    ExprOrSuper::Expr(box Expr::Member(m)) => {
+                        if !has_leaping_args {
+                            let callee = self.explode_expr(box Expr::Member(m), false);
+                            return self.finish_expr(
+                                box Expr::Call(CallExpr {
+                                    span,
+                                    callee: ExprOrSuper::Expr(callee),
+                                    args,
+                                    type_args,
+                                }),
+                                ignore_result,
+                            );
+                        }
+
+                        // The object is stored in a temporary variable and
+                        // passed as `this` using `.call`.
+                        let obj = match m.obj {
+                            ExprOrSuper::Expr(obj) => {
+                                let temp_var = self.make_temp_var();
+                                let obj = self.explode_via_temp_var(Some(temp_var), obj, false);
+                                injected_this = Some(obj.clone());
+                                ExprOrSuper::Expr(obj)
+                            }
+                            ExprOrSuper::Super(s) => {
+                                injected_this = Some(box Expr::This(ThisExpr { span: DUMMY_SP }));
+                                ExprOrSuper::Super(s)
+                            }
+                        };
+                        let prop = if m.computed {
+                            self.explode_via_temp_var(None, m.prop, false)
+                        } else {
+                            m.prop
+                        };
+
+                        Expr::Member(MemberExpr { obj, prop, ..m })
+                            .member(quote_ident!("call"))
+                            .as_callee()
+                    }
+
+                    ExprOrSuper::Expr(callee) => {
+                        let callee = self.explode_via_temp_var(None, callee, false);
+
+                        match *callee {
+                            // `(0, _context.t0)()`, to avoid passing `_context` as `this`.
+                            Expr::Member(..) => ExprOrSuper::Expr(box Expr::Seq(SeqExpr {
+                                span: DUMMY_SP,
+                                exprs: vec![
+                                    box Expr::Lit(Lit::Num(Number {
+                                        span: DUMMY_SP,
+                                        value: 0.0,
+                                    })),
+                                    callee,
+                                ],
+                            })),
+                            _ => ExprOrSuper::Expr(callee),
+                        }
+                    }
+
+                    ExprOrSuper::Super(s) => ExprOrSuper::Super(s),
+                };
+
+                let args = if has_leaping_args {
+                    let args = self.explode_args(args);
+                    injected_this
+                        .map(to_arg)
+                        .into_iter()
+                        .chain(args)
+                        .collect()
+                } else {
+                    args
+                };
+
+                self.finish_expr(
+                    box Expr::Call(CallExpr {
+                        span,
+                        callee,
+                        args,
+                        type_args,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::New(NewExpr {
+                span,
+                callee,
+                args,
+                type_args,
+            }) => {
+                let callee = self.explode_via_temp_var(None, callee, false);
+                let args = args.map(|args| self.explode_args(args));
+
+                self.finish_expr(
+                    box Expr::New(NewExpr {
+                        span,
+                        callee,
+                        args,
+                        type_args,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::Object(ObjectLit { span, props }) => {
+                let props = props
+                    .into_iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) => {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key,
+                                value: self.explode_via_temp_var(None, value, false),
+                            }))
+                        }
+                        PropOrSpread::Spread(SpreadElement { dot3_token, expr }) => {
+                            PropOrSpread::Spread(SpreadElement {
+                                dot3_token,
+                                expr: self.explode_via_temp_var(None, expr, false),
+                            })
+                        }
+                        prop => prop,
+                    })
+                    .collect();
+
+                self.finish_expr(box Expr::Object(ObjectLit { span, props }), ignore_result)
+            }
+
+            Expr::Array(ArrayLit { span, elems }) => {
+                let elems = elems
+                    .into_iter()
+                    .map(|elem| {
+                        elem.map(|elem| ExprOrSpread {
+                            spread: elem.spread,
+                            expr: self.explode_via_temp_var(None, elem.expr, false),
+                        })
+                    })
+                    .collect();
+
+                self.finish_expr(box Expr::Array(ArrayLit { span, elems }), ignore_result)
+            }
+
+            Expr::Seq(SeqExpr { exprs, .. }) => {
+                let last = exprs.len() - 1;
+                let mut result = None;
+
+                for (i, expr) in exprs.into_iter().enumerate() {
+                    if i == last {
+                        result = Some(self.explode_expr(expr, ignore_result));
+                    } else {
+                        self.explode_expr(expr, true);
+                    }
+                }
+
+                result.unwrap()
+            }
+
+            Expr::Bin(BinExpr {
+                op: op @ op!("&&"),
+                left,
+                right,
+                ..
+            })
+            | Expr::Bin(BinExpr {
+                op: op @ op!("||"),
+                left,
+                right,
+                ..
+            })
+            | Expr::Bin(BinExpr {
+                op: op @ op!("??"),
+                left,
+                right,
+                ..
+            }) => {
+                let after = self.loc();
+                let result = if ignore_result {
+                    None
+                } else {
+                    Some(self.make_temp_var())
+                };
+
+                let left = self.explode_via_temp_var(result.clone(), left, false);
+
+                match op {
+                    op!("&&") => self.jump_if_not(left, after),
+                    op!("||") => self.jump_if(left, after),
+                    _ => {
+                        // left !== null && left !== void 0
+                        let test = box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            op: op!("&&"),
+                            left: box (*left).clone().make_bin(
+                                op!("!=="),
+                                Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                            ),
+                            right: box (*left).make_bin(op!("!=="), *undefined(DUMMY_SP)),
+                        });
+                        self.jump_if(test, after)
+                    }
+                }
+
+                self.explode_via_temp_var(result.clone(), right, ignore_result);
+                self.mark(after);
+
+                result.unwrap_or_else(|| undefined(DUMMY_SP))
+            }
+
+            Expr::Cond(CondExpr {
+                test, cons, alt, ..
+            }) => {
+                let else_loc = self.loc();
+                let after = self.loc();
+                let result = if ignore_result {
+                    None
+                } else {
+                    Some(self.make_temp_var())
+                };
+
+                let test = self.explode_expr(test, false);
+                self.jump_if_not(test, else_loc);
+                self.explode_via_temp_var(result.clone(), cons, ignore_result);
+                self.jump(after);
+
+                self.mark(else_loc);
+                self.explode_via_temp_var(result.clone(), alt, ignore_result);
+                self.mark(after);
+
+                result.unwrap_or_else(|| undefined(DUMMY_SP))
+            }
+
+            Expr::Unary(UnaryExpr { span, op, arg }) => {
+                let arg = self.explode_expr(arg, false);
+                self.finish_expr(box Expr::Unary(UnaryExpr { span, op, arg }), ignore_result)
+            }
+
+            Expr::Bin(BinExpr {
+                span,
+                op,
+                left,
+                right,
+            }) => {
+                let left = self.explode_via_temp_var(None, left, false);
+                let right = self.explode_via_temp_var(None, right, false);
+
+                self.finish_expr(
+                    box Expr::Bin(BinExpr {
+                        span,
+                        op,
+                        left,
+                        right,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::Assign(AssignExpr {
+                span,
+                op,
+                left,
+                right,
+            }) => {
+                let left = match left {
+                    PatOrExpr::Expr(e) => PatOrExpr::Expr(self.explode_expr(e, false)),
+                    PatOrExpr::Pat(box Pat::Expr(e)) => {
+                        PatOrExpr::Expr(self.explode_expr(e, false))
+                    }
+                    left => left,
+                };
+
+                if op == op!("=") {
+                    let right = self.explode_expr(right, false);
+                    return self.finish_expr(
+                        box Expr::Assign(AssignExpr {
+                            span,
+                            op,
+                            left,
+                            right,
+                        }),
+                        ignore_result,
+                    );
+                }
+
+                // The value of the left hand side is evaluated before the
+                // right hand side, e.g. `x += yield y`.
+                let lhs = match left {
+                    PatOrExpr::Expr(ref e) => e.clone(),
+                    PatOrExpr::Pat(box Pat::Ident(ref i)) => box Expr::Ident(i.clone()),
+                    _ => unreachable!("invalid left hand side of compound assignment"),
+                };
+                let temp_var = self.make_temp_var();
+                let lhs = self.emit_assign(temp_var, lhs);
+                let right = self.explode_expr(right, false);
+
+                self.finish_expr(
+                    box Expr::Assign(AssignExpr {
+                        span,
+                        op: op!("="),
+                        left,
+                        right: box Expr::Assign(AssignExpr {
+                            span,
+                            op,
+                            left: PatOrExpr::Expr(lhs),
+                            right,
+                        }),
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg,
+            }) => {
+                let arg = self.explode_expr(arg, false);
+
+                self.finish_expr(
+                    box Expr::Update(UpdateExpr {
+                        span,
+                        op,
+                        prefix,
+                        arg,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::Yield(YieldExpr {
+                span,
+                arg,
+                delegate,
+            }) => {
+                let after = self.loc();
+                let arg = arg.map(|arg| self.explode_expr(arg, false));
+
+                if delegate {
+                    // return _context.delegateYield(arg, "t0", after);
+                    let result_name = self.make_temp_name();
+                    let result = box self.ctx_prop(&result_name);
+                    let call = box Expr::Call(CallExpr {
+                        span,
+                        callee: self.ctx_prop("delegateYield").as_callee(),
+                        args: vec![
+                            to_arg(arg.unwrap_or_else(|| undefined(DUMMY_SP))),
+                            quote_str!(result_name).as_arg(),
+                            to_arg(self.loc_expr(after)),
+                        ],
+                        type_args: None,
+                    });
+                    self.emit(Stmt::Return(ReturnStmt {
+                        span,
+                        arg: Some(call),
+                    }));
+                    self.mark(after);
+
+                    return result;
+                }
+
+                // _context.next = after;
+                // return arg;
+                let after_expr = self.loc_expr(after);
+                self.emit_assign(box self.ctx_prop("next"), after_expr);
+                self.emit(Stmt::Return(ReturnStmt { span, arg }));
+                self.mark(after);
+
+                box self.ctx_prop("sent")
+            }
+
+            Expr::Tpl(Tpl {
+                span,
+                exprs,
+                quasis,
+            }) => {
+                let exprs = exprs
+                    .into_iter()
+                    .map(|expr| self.explode_via_temp_var(None, expr, false))
+                    .collect();
+
+                self.finish_expr(
+                    box Expr::Tpl(Tpl {
+                        span,
+                        exprs,
+                        quasis,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            Expr::TaggedTpl(TaggedTpl {
+                span,
+                tag,
+                exprs,
+                quasis,
+                type_params,
+            }) => {
+                let tag = self.explode_via_temp_var(None, tag, false);
+                let exprs = exprs
+                    .into_iter()
+                    .map(|expr| self.explode_via_temp_var(None, expr, false))
+                    .collect();
+
+                self.finish_expr(
+                    box Expr::TaggedTpl(TaggedTpl {
+                        span,
+                        tag,
+                        exprs,
+                        quasis,
+                        type_params,
+                    }),
+                    ignore_result,
+                )
+            }
+
+            e => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(e.span(), "regenerator: unsupported expression")
+                        .emit()
+                });
+                self.finish_expr(box e, ignore_result)
+            }
+        }
+    }
+}
+
+fn to_arg(expr: Box<Expr>) -> ExprOrSpread {
+    ExprOrSpread { spread: None, expr }
+}
+
+fn is_completion(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::Return(..) | Stmt::Break(..) | Stmt::Continue(..) | Stmt::Throw(..) => true,
+        _ => false,
+    }
+}
+
+/// Replaces locations with the resolved values.
+struct LocResolver<'a> {
+    mark: Mark,
+    locs: &'a [Option<usize>],
+}
+
+impl Fold<Number> for LocResolver<'_> {
+    fn fold(&mut self, n: Number) -> Number {
+        if n.span.ctxt().outer() != self.mark {
+            return n;
+        }
+
+        let value = self.locs[n.value as usize].expect("regenerator: unmarked location");
+        Number {
+            span: DUMMY_SP,
+            value: value as _,
+        }
+    }
+}
+
+/// Replaces references to the parameter of a catch clause with the temporary
+/// variable which holds the caught error.
+///
+/// Binding patterns are left as is, and nested scopes which declare a
+/// binding with the same name are not visited.
+struct CatchParamReplacer {
+    param: Ident,
+    safe_param: Box<Expr>,
+    /// True while folding the left side of an assignment.
+    in_assign_target: bool,
+}
+
+impl CatchParamReplacer {
+    fn is_param(&self, i: &Ident) -> bool {
+        i.sym == self.param.sym && i.span.ctxt() == self.param.span.ctxt()
+    }
+
+    /// Returns true if `node` declares a binding which shadows the parameter.
+    fn is_shadowed_by<N>(&self, node: &N, lexical_only: bool) -> bool
+    where
+        N: VisitWith<ShadowFinder>,
+    {
+        let mut v = ShadowFinder {
+            param: self.param.clone(),
+            lexical_only,
+            in_nested_block: false,
+            found: false,
+        };
+        node.visit_with(&mut v);
+        v.found
+    }
+}
+
+impl Fold<Expr> for CatchParamReplacer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(ref i) if self.is_param(i) => *self.safe_param.clone(),
+            _ => {
+                let old = mem::replace(&mut self.in_assign_target, false);
+                let e = e.fold_children(self);
+                self.in_assign_target = old;
+                e
+            }
+        }
+    }
+}
+
+impl Fold<PatOrExpr> for CatchParamReplacer {
+    fn fold(&mut self, p: PatOrExpr) -> PatOrExpr {
+        match p {
+            PatOrExpr::Pat(box Pat::Ident(ref i)) if self.is_param(i) => {
+                PatOrExpr::Expr(self.safe_param.clone())
+            }
+            PatOrExpr::Pat(pat) => {
+                let old = mem::replace(&mut self.in_assign_target, true);
+                let pat = pat.fold_with(self);
+                self.in_assign_target = old;
+                PatOrExpr::Pat(pat)
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<VarDeclOrPat> for CatchParamReplacer {
+    fn fold(&mut self, p: VarDeclOrPat) -> VarDeclOrPat {
+        match p {
+            VarDeclOrPat::Pat(pat) => {
+                let old = mem::replace(&mut self.in_assign_target, true);
+                let pat = pat.fold_with(self);
+                self.in_assign_target = old;
+                VarDeclOrPat::Pat(pat)
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Pat> for CatchParamReplacer {
+    fn fold(&mut self, p: Pat) -> Pat {
+        match p {
+            Pat::Ident(ref i) if self.in_assign_target && self.is_param(i) => {
+                Pat::Expr(self.safe_param.clone())
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for CatchParamReplacer {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => {
+                if self.is_param(&i) {
+                    Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(i),
+                        value: self.safe_param.clone(),
+                    })
+                } else {
+                    Prop::Shorthand(i)
+                }
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for CatchParamReplacer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl Fold<FnExpr> for CatchParamReplacer {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        match f.ident {
+            Some(ref i) if self.is_param(i) => f,
+            _ => f.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for CatchParamReplacer {
+    fn fold(&mut self, f: Function) -> Function {
+        let shadowed = self.is_shadowed_by(&f.params, false)
+            || match f.body {
+                Some(ref body) => self.is_shadowed_by(&body.stmts, false),
+                None => false,
+            };
+        if shadowed {
+            return f;
+        }
+
+        f.fold_children(self)
+    }
+}
+
+impl Fold<ArrowExpr> for CatchParamReplacer {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let shadowed = self.is_shadowed_by(&f.params, false)
+            || match f.body {
+                BlockStmtOrExpr::BlockStmt(ref body) => self.is_shadowed_by(&body.stmts, false),
+                BlockStmtOrExpr::Expr(..) => false,
+            };
+        if shadowed {
+            return f;
+        }
+
+        f.fold_children(self)
+    }
+}
+
+impl Fold<BlockStmt> for CatchParamReplacer {
+    fn fold(&mut self, b: BlockStmt) -> BlockStmt {
+        if self.is_shadowed_by(&b.stmts, true) {
+            return b;
+        }
+
+        b.fold_children(self)
+    }
+}
+
+impl Fold<CatchClause> for CatchParamReplacer {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        if self.is_shadowed_by(&c.param, false) {
+            return c;
+        }
+
+        c.fold_children(self)
+    }
+}
+
+/// Finds a declaration of the parameter of a catch clause in the statements of
+/// a scope, without visiting nested scopes.
+struct ShadowFinder {
+    param: Ident,
+    /// Ignore `var` declarations, which belong to the enclosing function.
+    lexical_only: bool,
+    in_nested_block: bool,
+    found: bool,
+}
+
+impl ShadowFinder {
+    fn check(&mut self, i: &Ident) {
+        if i.sym == self.param.sym && i.span.ctxt() == self.param.span.ctxt() {
+            self.found = true;
+        }
+    }
+
+    fn visit_nested<N>(&mut self, node: &N)
+    where
+        N: VisitWith<Self>,
+    {
+        let old = mem::replace(&mut self.in_nested_block, true);
+        node.visit_children(self);
+        self.in_nested_block = old;
+    }
+}
+
+impl Visit<Pat> for ShadowFinder {
+    fn visit(&mut self, p: &Pat) {
+        match *p {
+            Pat::Ident(ref i) => self.check(i),
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<VarDecl> for ShadowFinder {
+    fn visit(&mut self, v: &VarDecl) {
+        let declares = match v.kind {
+            VarDeclKind::Var => !self.lexical_only,
+            _ => !self.in_nested_block,
+        };
+        if !declares {
+            return;
+        }
+
+        for decl in &v.decls {
+            decl.name.visit_with(self);
+        }
+    }
+}
+
+impl Visit<FnDecl> for ShadowFinder {
+    fn visit(&mut self, f: &FnDecl) {
+        if !self.in_nested_block {
+            self.check(&f.ident)
+        }
+    }
+}
+
+impl Visit<ClassDecl> for ShadowFinder {
+    fn visit(&mut self, c: &ClassDecl) {
+        if !self.in_nested_block {
+            self.check(&c.ident)
+        }
+    }
+}
+
+impl Visit<BlockStmt> for ShadowFinder {
+    fn visit(&mut self, b: &BlockStmt) {
+        self.visit_nested(b)
+    }
+}
+
+impl Visit<ForStmt> for ShadowFinder {
+    fn visit(&mut self, s: &ForStmt) {
+        self.visit_nested(s)
+    }
+}
+
+impl Visit<ForInStmt> for ShadowFinder {
+    fn visit(&mut self, s: &ForInStmt) {
+        self.visit_nested(s)
+    }
+}
+
+impl Visit<ForOfStmt> for ShadowFinder {
+    fn visit(&mut self, s: &ForOfStmt) {
+        self.visit_nested(s)
+    }
+}
+
+/// The left side of `for (e of items)` is not a declaration.
+impl Visit<VarDeclOrPat> for ShadowFinder {
+    fn visit(&mut self, v: &VarDeclOrPat) {
+        match *v {
+            VarDeclOrPat::VarDecl(ref v) => v.visit_with(self),
+            VarDeclOrPat::Pat(..) => {}
+        }
+    }
+}
+
+/// The parameter of a nested catch clause is handled by [CatchParamReplacer].
+impl Visit<CatchClause> for ShadowFinder {
+    fn visit(&mut self, c: &CatchClause) {
+        c.body.visit_with(self)
+    }
+}
+
+impl Visit<Expr> for ShadowFinder {
+    fn visit(&mut self, _: &Expr) {}
+}
+
+pub(super) fn contains_leap<N>(node: &N) -> bool
+where
+    N: VisitWith<LeapFinder>,
+{
+    let mut v = LeapFinder { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+pub(super) struct LeapFinder {
+    found: bool,
+}
+
+macro_rules! leap {
+    ($T:ty) => {
+        impl Visit<$T> for LeapFinder {
+            fn visit(&mut self, _: &$T) {
+                self.found = true;
+            }
+        }
+    };
+}
+
+leap!(YieldExpr);
+leap!(BreakStmt);
+leap!(ContinueStmt);
+leap!(ReturnStmt);
+leap!(ThrowStmt);
+
+macro_rules! noop {
+    ($T:ty) => {
+        impl Visit<$T> for LeapFinder {
+            fn visit(&mut self, _: &$T) {}
+        }
+    };
+}
+
+noop!(Function);
+noop!(ArrowExpr);
+noop!(Constructor);
+noop!(GetterProp);
+noop!(SetterProp);
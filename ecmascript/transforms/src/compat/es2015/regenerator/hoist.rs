@@ -0,0 +1,209 @@
+use crate::util::{extract_var_ids, ExprFactory};
+use ast::*;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith};
+
+/// Hoists variable and function declarations out of a generator body, so that
+/// statements can be moved into the cases of the dispatch loop.
+#[derive(Debug, Default)]
+pub(super) struct Hoister {
+    pub vars: Vec<Ident>,
+    pub fns: Vec<FnDecl>,
+}
+
+impl Hoister {
+    fn var_decl_to_expr(&mut self, var: VarDecl) -> Option<Box<Expr>> {
+        let mut exprs = vec![];
+
+        for decl in var.decls {
+            self.vars.extend(extract_var_ids(&decl.name));
+
+            if let Some(init) = decl.init {
+                exprs.push(box Expr::Assign(AssignExpr {
+                    span: decl.span,
+                    op: op!("="),
+                    left: PatOrExpr::Pat(box decl.name),
+                    right: init,
+                }));
+            }
+        }
+
+        match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => Some(box Expr::Seq(SeqExpr {
+                span: var.span,
+                exprs,
+            })),
+        }
+    }
+
+    fn var_decl_to_pat(&mut self, var: VarDecl) -> Pat {
+        self.vars.extend(extract_var_ids(&var.decls));
+
+        let decl = var
+            .decls
+            .into_iter()
+            .next()
+            .expect("for-in / for-of statement should have a declarator");
+        decl.name
+    }
+}
+
+impl Fold<Stmt> for Hoister {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        let s = s.fold_children(self);
+
+        match s {
+            Stmt::Decl(Decl::Var(var)) => {
+                let span = var.span;
+                match self.var_decl_to_expr(var) {
+                    Some(expr) => Stmt::Expr(ExprStmt { span, expr }),
+                    None => Stmt::Empty(EmptyStmt { span }),
+                }
+            }
+
+            Stmt::Decl(Decl::Fn(f)) => {
+                let span = f.function.span;
+                self.fns.push(f);
+                Stmt::Empty(EmptyStmt { span })
+            }
+
+            Stmt::Decl(Decl::Class(ClassDecl { ident, class, .. })) => {
+                self.vars.push(ident.clone());
+                Stmt::Expr(ExprStmt {
+                    span: class.span,
+                    expr: box Expr::Assign(AssignExpr {
+                        span: class.span,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                        right: box Expr::Class(ClassExpr {
+                            ident: Some(ident),
+                            class,
+                        }),
+                    }),
+                })
+            }
+
+            _ => s,
+        }
+    }
+}
+
+impl Fold<ForStmt> for Hoister {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let s = s.fold_children(self);
+
+        let init = match s.init {
+            Some(VarDeclOrExpr::VarDecl(var)) => {
+                self.var_decl_to_expr(var).map(VarDeclOrExpr::Expr)
+            }
+            init => init,
+        };
+
+        ForStmt { init, ..s }
+    }
+}
+
+impl Fold<VarDeclOrPat> for Hoister {
+    fn fold(&mut self, v: VarDeclOrPat) -> VarDeclOrPat {
+        match v {
+            VarDeclOrPat::VarDecl(var) => VarDeclOrPat::Pat(self.var_decl_to_pat(var)),
+            _ => v,
+        }
+    }
+}
+
+impl Fold<Function> for Hoister {
+    /// Declarations in nested functions are not hoisted.
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for Hoister {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+impl Fold<Constructor> for Hoister {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        c
+    }
+}
+
+impl Fold<GetterProp> for Hoister {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        p
+    }
+}
+
+impl Fold<SetterProp> for Hoister {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        p
+    }
+}
+
+/// Replaces `arguments` with a variable declared in the outer function.
+pub(super) struct ArgumentsReplacer {
+    pub ident: Ident,
+    pub found: bool,
+}
+
+impl Fold<Expr> for ArgumentsReplacer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(Ident {
+                sym: js_word!("arguments"),
+                ..
+            }) => {
+                self.found = true;
+                Expr::Ident(self.ident.clone())
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for ArgumentsReplacer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl Fold<Function> for ArgumentsReplacer {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<Constructor> for ArgumentsReplacer {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        c
+    }
+}
+
+impl Fold<GetterProp> for ArgumentsReplacer {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        p
+    }
+}
+
+impl Fold<SetterProp> for ArgumentsReplacer {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        p
+    }
+}
+
+/// `regeneratorRuntime.name`
+pub(super) fn runtime(name: &'static str) -> Expr {
+    quote_ident!("regeneratorRuntime").member(quote_ident!(name))
+}
@@ -0,0 +1,82 @@
+use super::emitter::Loc;
+use swc_atoms::JsWord;
+
+/// An entry of the stack which is used to resolve the target of `break` and
+/// `continue` statements.
+#[derive(Debug)]
+pub(super) enum Entry {
+    Loop {
+        break_loc: Loc,
+        continue_loc: Loc,
+        label: Option<JsWord>,
+    },
+    Switch {
+        break_loc: Loc,
+    },
+    Labeled {
+        break_loc: Loc,
+        label: JsWord,
+    },
+}
+
+#[derive(Debug, Default)]
+pub(super) struct LeapManager {
+    stack: Vec<Entry>,
+}
+
+impl LeapManager {
+    pub fn push(&mut self, entry: Entry) {
+        self.stack.push(entry)
+    }
+
+    pub fn pop(&mut self) {
+        self.stack.pop().expect("LeapManager::pop() called with empty stack");
+    }
+
+    pub fn break_loc(&self, label: Option<&JsWord>) -> Loc {
+        for entry in self.stack.iter().rev() {
+            match (entry, label) {
+                (Entry::Loop { break_loc, .. }, None) | (Entry::Switch { break_loc }, None) => {
+                    return *break_loc
+                }
+                (
+                    Entry::Loop {
+                        break_loc,
+                        label: Some(l),
+                        ..
+                    },
+                    Some(label),
+                )
+                | (
+                    Entry::Labeled {
+                        break_loc,
+                        label: l,
+                    },
+                    Some(label),
+                ) if l == label => return *break_loc,
+                _ => {}
+            }
+        }
+
+        unreachable!("regenerator: invalid break target: {:?}", label)
+    }
+
+    pub fn continue_loc(&self, label: Option<&JsWord>) -> Loc {
+        for entry in self.stack.iter().rev() {
+            match (entry, label) {
+                (Entry::Loop { continue_loc, .. }, None) => return *continue_loc,
+                (
+                    Entry::Loop {
+                        continue_loc,
+                        label: Some(l),
+                        ..
+                    },
+                    Some(label),
+                ) if l == label => return *continue_loc,
+                _ => {}
+            }
+        }
+
+        unreachable!("regenerator: invalid continue target: {:?}", label)
+    }
+}
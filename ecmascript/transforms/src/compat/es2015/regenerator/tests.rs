@@ -0,0 +1,261 @@
+use super::regenerator;
+use swc_ecma_parser::Syntax;
+
+fn syntax() -> Syntax {
+    Syntax::default()
+}
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    fn_decl,
+    "function* foo() {
+    yield 1;
+    yield 2;
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo() {
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                _context.next = 2;
+                return 1;
+            case 2:
+                _context.next = 4;
+                return 2;
+            case 4:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    vars_this_arguments,
+    "function* foo(a) {
+    var b = yield a;
+    return this.x + b + arguments[0];
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo(a) {
+    var b, _args = arguments;
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                _context.next = 2;
+                return a;
+            case 2:
+                b = _context.sent;
+                return _context.abrupt("return", this.x + b + _args[0]);
+            case 4:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked, this);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    while_loop,
+    "function* foo() {
+    var i = 0;
+    while (i < 3) {
+        yield i++;
+    }
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo() {
+    var i;
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                i = 0;
+            case 1:
+                if (!(i < 3)) {
+                    _context.next = 6;
+                    break;
+                }
+                _context.next = 4;
+                return i++;
+            case 4:
+                _context.next = 1;
+                break;
+            case 6:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    try_catch,
+    "function* foo() {
+    try {
+        yield 1;
+    } catch (e) {
+        console.log(e);
+    }
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo() {
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                _context.prev = 0;
+                _context.next = 3;
+                return 1;
+            case 3:
+                _context.next = 8;
+                break;
+            case 5:
+                _context.prev = 5;
+                _context.t0 = _context["catch"](0);
+                console.log(_context.t0);
+            case 8:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked, null, [[0, 5]]);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    try_catch_shadowed_param,
+    "function* foo() {
+    var e = 1;
+    try {
+        yield 1;
+    } catch (e) {
+        console.log(e);
+    }
+    return e;
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo() {
+    var e;
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                e = 1;
+            case 1:
+                _context.prev = 1;
+                _context.next = 4;
+                return 1;
+            case 4:
+                _context.next = 9;
+                break;
+            case 6:
+                _context.prev = 6;
+                _context.t0 = _context["catch"](1);
+                console.log(_context.t0);
+            case 9:
+                return _context.abrupt("return", e);
+            case 10:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked, null, [[1, 6]]);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    try_catch_param_in_nested_fn,
+    "function* foo() {
+    try {
+        yield 1;
+    } catch (e) {
+        var f = function (e) {
+            return e;
+        };
+        var g = function () {
+            return e;
+        };
+    }
+}",
+    r#"var _marked = regeneratorRuntime.mark(foo);
+function foo() {
+    var f, g;
+    return regeneratorRuntime.wrap(function foo$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                _context.prev = 0;
+                _context.next = 3;
+                return 1;
+            case 3:
+                _context.next = 9;
+                break;
+            case 5:
+                _context.prev = 5;
+                _context.t0 = _context["catch"](0);
+                f = function(e) {
+                    return e;
+                };
+                g = function() {
+                    return _context.t0;
+                };
+            case 9:
+            case "end":
+                return _context.stop();
+        }
+    }, _marked, null, [[0, 5]]);
+}"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    fn_expr_delegate,
+    "var gen = function* () {
+    yield* other();
+};",
+    r#"var gen = regeneratorRuntime.mark(function _callee() {
+    return regeneratorRuntime.wrap(function _callee$(_context) {
+        while(1)switch(_context.prev = _context.next){
+            case 0:
+                return _context.delegateYield(other(), "t0", 1);
+            case 1:
+            case "end":
+                return _context.stop();
+        }
+    }, _callee);
+});"#
+);
+
+test!(
+    syntax(),
+    |_| regenerator(),
+    class_method,
+    "class Foo {
+    *bar() {
+        yield 1;
+    }
+}",
+    r#"class Foo {
+    bar() {
+        return regeneratorRuntime.mark(function _callee() {
+            return regeneratorRuntime.wrap(function _callee$(_context) {
+                while(1)switch(_context.prev = _context.next){
+                    case 0:
+                        _context.next = 2;
+                        return 1;
+                    case 2:
+                    case "end":
+                        return _context.stop();
+                }
+            }, _callee);
+        }).apply(this, arguments);
+    }
+}"#
+);
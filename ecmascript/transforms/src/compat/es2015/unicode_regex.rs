@@ -0,0 +1,326 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::Fold;
+
+/// `@babel/plugin-transform-unicode-regex`
+///
+/// Code points outside of the BMP are converted to surrogate pairs, and the
+/// `u` flag is removed. Regular expressions which can't be expressed without
+/// the flag (e.g. a negated character class with astral code points, or
+/// unicode property escapes) are not modified.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var a = /\u{1F600}+/u;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var a = /(?:\uD83D\uDE00)+/;
+/// ```
+pub fn unicode_regex() -> impl Pass {
+    UnicodeRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodeRegex;
+
+/// `.` in unicode mode.
+const DOT: &str = concat!(
+    "(?:[\\0-\\t\\x0B\\f\\x0E-\\u2027\\u202A-\\uD7FF\\uE000-\\uFFFF]",
+    "|[\\uD800-\\uDBFF][\\uDC00-\\uDFFF]",
+    "|[\\uD800-\\uDBFF](?![\\uDC00-\\uDFFF])",
+    "|(?:[^\\uD800-\\uDBFF]|^)[\\uDC00-\\uDFFF])"
+);
+
+/// `.` in unicode mode with `s` flag.
+const DOT_ALL: &str = concat!(
+    "(?:[\\0-\\uD7FF\\uE000-\\uFFFF]",
+    "|[\\uD800-\\uDBFF][\\uDC00-\\uDFFF]",
+    "|[\\uD800-\\uDBFF](?![\\uDC00-\\uDFFF])",
+    "|(?:[^\\uD800-\\uDBFF]|^)[\\uDC00-\\uDFFF])"
+);
+
+impl Fold<Regex> for UnicodeRegex {
+    fn fold(&mut self, r: Regex) -> Regex {
+        if !r.flags.contains('u') {
+            return r;
+        }
+
+        match lower(&r.exp, r.flags.contains('s')) {
+            Some(exp) => Regex {
+                exp: exp.into(),
+                flags: r
+                    .flags
+                    .chars()
+                    .filter(|&c| c != 'u')
+                    .collect::<String>()
+                    .into(),
+                ..r
+            },
+            None => r,
+        }
+    }
+}
+
+enum Atom {
+    CodePoint(u32),
+    /// Escapes which are same in non-unicode mode, like `\d` or `\n`.
+    Raw(String),
+}
+
+fn lower(exp: &str, dot_all: bool) -> Option<String> {
+    let chars: Vec<char> = exp.chars().collect();
+    let mut buf = String::with_capacity(exp.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                let (atom, next) = parse_escape(&chars, i)?;
+                i = next;
+                match atom {
+                    Atom::CodePoint(cp) if cp > 0xffff => {
+                        let (high, low) = to_surrogates(cp);
+                        buf.push_str(&format!("(?:{}{})", u(high), u(low)));
+                    }
+                    Atom::CodePoint(cp) => buf.push_str(&u(cp)),
+                    Atom::Raw(s) => buf.push_str(&s),
+                }
+            }
+            '[' => {
+                let (class, next) = lower_class(&chars, i)?;
+                i = next;
+                buf.push_str(&class);
+            }
+            '.' => {
+                i += 1;
+                buf.push_str(if dot_all { DOT_ALL } else { DOT });
+            }
+            c if c as u32 > 0xffff => {
+                i += 1;
+                let (high, low) = to_surrogates(c as u32);
+                buf.push_str(&format!("(?:{}{})", u(high), u(low)));
+            }
+            c => {
+                i += 1;
+                buf.push(c);
+            }
+        }
+    }
+
+    Some(buf)
+}
+
+/// `chars[i]` should be `\`.
+///
+/// Returns `None` for escapes which require unicode mode.
+fn parse_escape(chars: &[char], i: usize) -> Option<(Atom, usize)> {
+    let hex = |from: usize, to: usize| -> Option<u32> {
+        if to > chars.len() || from == to {
+            return None;
+        }
+        let s: String = chars[from..to].iter().collect();
+        u32::from_str_radix(&s, 16).ok()
+    };
+
+    match chars.get(i + 1)? {
+        'u' if chars.get(i + 2) == Some(&'{') => {
+            let end = (i + 3..chars.len()).find(|&j| chars[j] == '}')?;
+            let cp = hex(i + 3, end)?;
+            Some((Atom::CodePoint(cp), end + 1))
+        }
+        'u' => {
+            let cp = hex(i + 2, i + 6)?;
+            // Surrogate pairs are a code point in unicode mode.
+            if (0xd800..0xdc00).contains(&cp)
+                && chars.get(i + 6) == Some(&'\\')
+                && chars.get(i + 7) == Some(&'u')
+            {
+                if let Some(low) = hex(i + 8, i + 12) {
+                    if (0xdc00..0xe000).contains(&low) {
+                        let cp = 0x10000 + ((cp - 0xd800) << 10) + (low - 0xdc00);
+                        return Some((Atom::CodePoint(cp), i + 12));
+                    }
+                }
+            }
+            Some((Atom::CodePoint(cp), i + 6))
+        }
+        'p' | 'P' => None,
+        &c => Some((Atom::Raw(format!("\\{}", c)), i + 2)),
+    }
+}
+
+/// `chars[i]` should be `[`.
+fn lower_class(chars: &[char], i: usize) -> Option<(String, usize)> {
+    let mut i = i + 1;
+    let negated = chars.get(i) == Some(&'^');
+    if negated {
+        i += 1;
+    }
+
+    let mut bmp = String::new();
+    let mut astral = vec![];
+
+    let parse_atom = |i: usize| -> Option<(Atom, usize)> {
+        match *chars.get(i)? {
+            '\\' => parse_escape(chars, i),
+            c => Some((Atom::CodePoint(c as u32), i + 1)),
+        }
+    };
+
+    loop {
+        if *chars.get(i)? == ']' {
+            i += 1;
+            break;
+        }
+
+        let (lo, next) = parse_atom(i)?;
+        i = next;
+
+        let is_range = chars.get(i) == Some(&'-') && chars.get(i + 1).map_or(false, |&c| c != ']');
+        if !is_range {
+            match lo {
+                Atom::CodePoint(cp) if cp > 0xffff => astral.push((cp, cp)),
+                Atom::CodePoint(cp) => bmp.push_str(&class_char(cp)),
+                Atom::Raw(s) => bmp.push_str(&s),
+            }
+            continue;
+        }
+
+        let (hi, next) = parse_atom(i + 1)?;
+        i = next;
+
+        match (lo, hi) {
+            (Atom::CodePoint(lo), Atom::CodePoint(hi)) => {
+                if hi <= 0xffff {
+                    bmp.push_str(&format!("{}-{}", class_char(lo), class_char(hi)));
+                } else if lo > 0xffff {
+                    astral.push((lo, hi));
+                } else {
+                    bmp.push_str(&format!("{}-\\uFFFF", class_char(lo)));
+                    astral.push((0x10000, hi));
+                }
+            }
+            (lo, hi) => {
+                let text = |a: Atom| match a {
+                    Atom::CodePoint(cp) => class_char(cp),
+                    Atom::Raw(s) => s,
+                };
+                bmp.push_str(&format!("{}-{}", text(lo), text(hi)));
+            }
+        }
+    }
+
+    if astral.is_empty() {
+        let class = format!("[{}{}]", if negated { "^" } else { "" }, bmp);
+        return Some((class, i));
+    }
+    if negated {
+        return None;
+    }
+
+    let mut alts = vec![];
+    if !bmp.is_empty() {
+        alts.push(format!("[{}]", bmp));
+    }
+    for (lo, hi) in astral {
+        let (lo_high, lo_low) = to_surrogates(lo);
+        let (hi_high, hi_low) = to_surrogates(hi);
+
+        if lo_high == hi_high {
+            alts.push(format!("{}{}", u(lo_high), range(lo_low, hi_low)));
+        } else {
+            alts.push(format!("{}{}", u(lo_high), range(lo_low, 0xdfff)));
+            if lo_high + 1 < hi_high {
+                alts.push(format!(
+                    "{}{}",
+                    range(lo_high + 1, hi_high - 1),
+                    range(0xdc00, 0xdfff)
+                ));
+            }
+            alts.push(format!("{}{}", u(hi_high), range(0xdc00, hi_low)));
+        }
+    }
+
+    Some((format!("(?:{})", alts.join("|")), i))
+}
+
+fn to_surrogates(cp: u32) -> (u32, u32) {
+    let c = cp - 0x10000;
+    (0xd800 + (c >> 10), 0xdc00 + (c & 0x3ff))
+}
+
+fn u(c: u32) -> String {
+    format!("\\u{:04X}", c)
+}
+
+fn range(lo: u32, hi: u32) -> String {
+    if lo == hi {
+        u(lo)
+    } else {
+        format!("[{}-{}]", u(lo), u(hi))
+    }
+}
+
+/// A code point in a character class.
+fn class_char(cp: u32) -> String {
+    match std::char::from_u32(cp) {
+        Some(c) if c.is_ascii_graphic() && !"\\[]^-".contains(c) => c.to_string(),
+        _ => u(cp),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_astral,
+        "var a = /\\u{1F600}+/u;",
+        "var a = /(?:\\uD83D\\uDE00)+/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_bmp_escape,
+        "var a = /\\u{41}\\d/gu;",
+        "var a = /\\u0041\\d/g;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_class,
+        "var a = /[a-z\\u{1F600}-\\u{1F602}]/u;",
+        "var a = /(?:[a-z]|\\uD83D[\\uDE00-\\uDE02])/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_dot,
+        "var a = /a.b/u;",
+        concat!(
+            "var a = /a(?:[\\0-\\t\\x0B\\f\\x0E-\\u2027\\u202A-\\uD7FF\\uE000-\\uFFFF]",
+            "|[\\uD800-\\uDBFF][\\uDC00-\\uDFFF]",
+            "|[\\uD800-\\uDBFF](?![\\uDC00-\\uDFFF])",
+            "|(?:[^\\uD800-\\uDBFF]|^)[\\uDC00-\\uDFFF])b/;"
+        )
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        negated_astral_class,
+        "var a = /[^\\u{1F600}]/u;",
+        "var a = /[^\\u{1F600}]/u;"
+    );
+}
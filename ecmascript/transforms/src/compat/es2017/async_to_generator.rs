@@ -526,10 +526,10 @@ impl Actual {
                     if done {
                         None
                     } else {
-                        match *p {
+                        match p.pat {
                             Pat::Ident(..) => Some(p.clone()),
                             Pat::Array(..) | Pat::Object(..) => {
-                                Some(Pat::Ident(private_ident!("_")))
+                                Some(Pat::Ident(private_ident!("_")).into())
                             }
                             _ => {
                                 done = true;
@@ -1,12 +1,27 @@
 pub use self::{
+    async_generator::async_generator, dotall_regex::dotall_regex,
+    named_capturing_groups_regex::named_capturing_groups_regex,
     object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
+    unicode_property_regex::unicode_property_regex,
 };
 use crate::pass::Pass;
 use ast::Module;
 
+mod async_generator;
+mod dotall_regex;
+mod named_capturing_groups_regex;
 mod object_rest_spread;
 mod optional_catch_binding;
+mod unicode_property_regex;
 
 pub fn es2018() -> impl Pass {
-    chain_at!(Module, object_rest_spread(), optional_catch_binding())
+    chain_at!(
+        Module,
+        async_generator(),
+        object_rest_spread(),
+        optional_catch_binding(),
+        unicode_property_regex(),
+        named_capturing_groups_regex(),
+        dotall_regex(),
+    )
 }
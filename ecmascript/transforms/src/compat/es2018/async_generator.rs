@@ -0,0 +1,543 @@
+use crate::{
+    pass::Pass,
+    util::{prepend, ExprFactory},
+};
+use ast::*;
+use std::{iter, mem};
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// `@babel/plugin-proposal-async-generator-functions`
+///
+/// Async generators are converted to generators, and `for await` statements
+/// are converted to `for` statements which use `_asyncIterator`.
+///
+/// ## In
+///
+/// ```js
+/// async function* foo() {
+///   await bar();
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo() {
+///   return _wrapAsyncGenerator(function* () {
+///     yield _awaitAsyncGenerator(bar());
+///   }).apply(this, arguments);
+/// }
+/// ```
+pub fn async_generator() -> impl Pass {
+    AsyncGenerator
+}
+
+struct AsyncGenerator;
+
+impl Fold<Function> for AsyncGenerator {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+        if !f.is_async {
+            return f;
+        }
+
+        let body = match f.body {
+            Some(body) => body,
+            None => return f,
+        };
+
+        let body = body.fold_with(&mut Actual {
+            in_generator: f.is_generator,
+            vars: vec![],
+        });
+        if !f.is_generator {
+            return Function {
+                body: Some(body),
+                ..f
+            };
+        }
+
+        let span = body.span;
+        // _wrapAsyncGenerator(function* () {}).apply(this, arguments)
+        let call = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(wrap_async_generator, "wrapAsyncGenerator"),
+            args: vec![Expr::Fn(FnExpr {
+                ident: None,
+                function: Function {
+                    params: vec![],
+                    decorators: Default::default(),
+                    span: DUMMY_SP,
+                    body: Some(body),
+                    is_generator: true,
+                    is_async: false,
+                    type_params: Default::default(),
+                    return_type: Default::default(),
+                },
+            })
+            .as_arg()],
+            type_args: Default::default(),
+        })
+        .apply(
+            DUMMY_SP,
+            box Expr::This(ThisExpr { span: DUMMY_SP }),
+            vec![quote_ident!("arguments").as_arg()],
+        );
+
+        Function {
+            body: Some(BlockStmt {
+                span,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box call),
+                })],
+            }),
+            is_async: false,
+            is_generator: false,
+            ..f
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for AsyncGenerator {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let f = f.fold_children(self);
+        if !f.is_async {
+            return f;
+        }
+
+        let body = f.body.fold_with(&mut Actual {
+            in_generator: false,
+            vars: vec![],
+        });
+
+        ArrowExpr { body, ..f }
+    }
+}
+
+/// Handles the body of an async function.
+struct Actual {
+    /// If true, `await` is converted to `yield _awaitAsyncGenerator()`.
+    in_generator: bool,
+    vars: Vec<VarDeclarator>,
+}
+
+impl Fold<Vec<Stmt>> for Actual {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        // This is to support nested block statements
+        let old = mem::replace(&mut self.vars, vec![]);
+
+        let mut stmts = stmts.fold_children(self);
+
+        if !self.vars.is_empty() {
+            prepend(
+                &mut stmts,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    declare: false,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut self.vars, vec![]),
+                })),
+            );
+        }
+
+        self.vars = old;
+        stmts
+    }
+}
+
+impl Fold<Expr> for Actual {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+        if !self.in_generator {
+            return e;
+        }
+
+        match e {
+            Expr::Await(AwaitExpr { span, arg }) => Expr::Yield(YieldExpr {
+                span,
+                arg: Some(box await_async_generator(arg)),
+                delegate: false,
+            }),
+
+            // yield* _asyncGeneratorDelegate(_asyncIterator(arg), _awaitAsyncGenerator)
+            Expr::Yield(YieldExpr {
+                span,
+                arg: Some(arg),
+                delegate: true,
+            }) => Expr::Yield(YieldExpr {
+                span,
+                arg: Some(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(async_generator_delegate, "asyncGeneratorDelegate"),
+                    args: vec![
+                        Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(async_iterator, "asyncIterator"),
+                            args: vec![ExprOrSpread {
+                                spread: None,
+                                expr: arg,
+                            }],
+                            type_args: Default::default(),
+                        })
+                        .as_arg(),
+                        helper_expr!(await_async_generator, "awaitAsyncGenerator").as_arg(),
+                    ],
+                    type_args: Default::default(),
+                })),
+                delegate: true,
+            }),
+
+            _ => e,
+        }
+    }
+}
+
+impl Fold<Stmt> for Actual {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        match s {
+            Stmt::Labeled(LabeledStmt { span, label, body }) => match *body {
+                Stmt::ForOf(s @ ForOfStmt {
+                    await_token: Some(..),
+                    ..
+                }) => {
+                    let s = s.fold_children(self);
+                    self.fold_for_await(Some(label), s)
+                }
+                body => Stmt::Labeled(LabeledStmt {
+                    span,
+                    label,
+                    body: box body.fold_with(self),
+                }),
+            },
+
+            Stmt::ForOf(
+                s @ ForOfStmt {
+                    await_token: Some(..),
+                    ..
+                },
+            ) => {
+                let s = s.fold_children(self);
+                self.fold_for_await(None, s)
+            }
+
+            _ => s.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for Actual {
+    /// Nested functions are handled by `AsyncGenerator`.
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for Actual {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+impl Fold<Constructor> for Actual {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        c
+    }
+}
+
+impl Actual {
+    fn await_expr(&self, arg: Expr) -> Expr {
+        if self.in_generator {
+            Expr::Yield(YieldExpr {
+                span: DUMMY_SP,
+                arg: Some(box await_async_generator(box arg)),
+                delegate: false,
+            })
+        } else {
+            Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg: box arg,
+            })
+        }
+    }
+
+    /// Converts
+    ///
+    /// ```js
+    /// for await (const x of y) {}
+    /// ```
+    ///
+    /// into
+    ///
+    /// ```js
+    /// var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+    /// try {
+    ///   for (var _iterator = _asyncIterator(y), _step, _value;
+    ///        _step = await _iterator.next(),
+    ///        _iteratorNormalCompletion = _step.done,
+    ///        _value = await _step.value,
+    ///        !_iteratorNormalCompletion;
+    ///        _iteratorNormalCompletion = true) {
+    ///     const x = _value;
+    ///   }
+    /// } catch (err) {
+    ///   _didIteratorError = true;
+    ///   _iteratorError = err;
+    /// } finally {
+    ///   try {
+    ///     if (!_iteratorNormalCompletion && _iterator.return != null) {
+    ///       await _iterator.return();
+    ///     }
+    ///   } finally {
+    ///     if (_didIteratorError) {
+    ///       throw _iteratorError;
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    fn fold_for_await(&mut self, label: Option<Ident>, s: ForOfStmt) -> Stmt {
+        let ForOfStmt {
+            span,
+            left,
+            right,
+            body,
+            ..
+        } = s;
+
+        let normal_completion = private_ident!("_iteratorNormalCompletion");
+        let did_error = private_ident!("_didIteratorError");
+        let error = private_ident!("_iteratorError");
+        let iterator = private_ident!("_iterator");
+        let step = private_ident!("_step");
+        let value = private_ident!("_value");
+        let err = private_ident!("err");
+
+        self.vars.push(declarator(normal_completion.clone(), Some(true.into())));
+        self.vars.push(declarator(did_error.clone(), Some(false.into())));
+        self.vars.push(declarator(error.clone(), None));
+
+        // const x = _value;
+        let init_stmt = match left {
+            VarDeclOrPat::VarDecl(VarDecl {
+                span,
+                kind,
+                declare,
+                decls,
+            }) => Stmt::Decl(Decl::Var(VarDecl {
+                span,
+                kind,
+                declare,
+                decls: decls
+                    .into_iter()
+                    .take(1)
+                    .map(|decl| VarDeclarator {
+                        init: Some(box Expr::Ident(value.clone())),
+                        ..decl
+                    })
+                    .collect(),
+            })),
+            VarDeclOrPat::Pat(pat) => Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: PatOrExpr::Pat(box pat),
+                right: box Expr::Ident(value.clone()),
+            })
+            .into_stmt(),
+        };
+        let body = match *body {
+            Stmt::Block(BlockStmt { span, stmts }) => BlockStmt {
+                span,
+                stmts: iter::once(init_stmt).chain(stmts).collect(),
+            },
+            body => BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![init_stmt, body],
+            },
+        };
+
+        let test = box Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: vec![
+                box assign(
+                    &step,
+                    self.await_expr(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: iterator.clone().member(quote_ident!("next")).as_callee(),
+                        args: vec![],
+                        type_args: Default::default(),
+                    })),
+                ),
+                box assign(
+                    &normal_completion,
+                    step.clone().member(quote_ident!("done")),
+                ),
+                box assign(
+                    &value,
+                    self.await_expr(step.clone().member(quote_ident!("value"))),
+                ),
+                box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!("!"),
+                    arg: box Expr::Ident(normal_completion.clone()),
+                }),
+            ],
+        });
+
+        let for_stmt = Stmt::For(ForStmt {
+            span,
+            init: Some(VarDeclOrExpr::VarDecl(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![
+                    declarator(
+                        iterator.clone(),
+                        Some(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(async_iterator, "asyncIterator"),
+                            args: vec![ExprOrSpread {
+                                spread: None,
+                                expr: right,
+                            }],
+                            type_args: Default::default(),
+                        })),
+                    ),
+                    declarator(step.clone(), None),
+                    declarator(value.clone(), None),
+                ],
+            })),
+            test: Some(test),
+            update: Some(box assign(&normal_completion, true.into())),
+            body: box Stmt::Block(body),
+        });
+        let for_stmt = match label {
+            Some(label) => Stmt::Labeled(LabeledStmt {
+                span,
+                label,
+                body: box for_stmt,
+            }),
+            None => for_stmt,
+        };
+
+        // catch (err) {
+        //   _didIteratorError = true;
+        //   _iteratorError = err;
+        // }
+        let handler = CatchClause {
+            span: DUMMY_SP,
+            param: Some(Pat::Ident(err.clone())),
+            body: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![
+                    assign(&did_error, true.into()).into_stmt(),
+                    assign(&error, Expr::Ident(err)).into_stmt(),
+                ],
+            },
+        };
+
+        // if (!_iteratorNormalCompletion && _iterator.return != null) {
+        //   await _iterator.return();
+        // }
+        let close_iterator = Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test: box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!("&&"),
+                left: box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!("!"),
+                    arg: box Expr::Ident(normal_completion),
+                }),
+                right: box iterator
+                    .clone()
+                    .member(quote_ident!("return"))
+                    .make_bin(op!("!="), Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+            }),
+            cons: box Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![self
+                    .await_expr(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: iterator.member(quote_ident!("return")).as_callee(),
+                        args: vec![],
+                        type_args: Default::default(),
+                    }))
+                    .into_stmt()],
+            }),
+            alt: None,
+        });
+
+        // if (_didIteratorError) {
+        //   throw _iteratorError;
+        // }
+        let rethrow = Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test: box Expr::Ident(did_error),
+            cons: box Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Throw(ThrowStmt {
+                    span: DUMMY_SP,
+                    arg: box Expr::Ident(error),
+                })],
+            }),
+            alt: None,
+        });
+
+        let finalizer = BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Try(TryStmt {
+                span: DUMMY_SP,
+                block: BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![close_iterator],
+                },
+                handler: None,
+                finalizer: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![rethrow],
+                }),
+            })],
+        };
+
+        Stmt::Try(TryStmt {
+            span,
+            block: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![for_stmt],
+            },
+            handler: Some(handler),
+            finalizer: Some(finalizer),
+        })
+    }
+}
+
+/// `_awaitAsyncGenerator(arg)`
+fn await_async_generator(arg: Box<Expr>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(await_async_generator, "awaitAsyncGenerator"),
+        args: vec![ExprOrSpread { spread: None, expr: arg }],
+        type_args: Default::default(),
+    })
+}
+
+fn assign(left: &Ident, right: Expr) -> Expr {
+    Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: op!("="),
+        left: PatOrExpr::Pat(box Pat::Ident(left.clone())),
+        right: box right,
+    })
+}
+
+fn declarator(name: Ident, init: Option<Expr>) -> VarDeclarator {
+    VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(name),
+        init: init.map(Box::new),
+        definite: false,
+    }
+}
@@ -0,0 +1,71 @@
+use super::async_generator;
+use swc_ecma_parser::Syntax;
+
+fn syntax() -> Syntax {
+    Syntax::default()
+}
+
+test!(
+    syntax(),
+    |_| async_generator(),
+    await_and_yield,
+    "async function* agf() {
+    await 1;
+    yield 2;
+}",
+    "function agf() {
+    return _wrapAsyncGenerator(function*() {
+        yield _awaitAsyncGenerator(1);
+        yield 2;
+    }).apply(this, arguments);
+}"
+);
+
+test!(
+    syntax(),
+    |_| async_generator(),
+    yield_star,
+    "async function* agf() {
+    yield* other();
+}",
+    "function agf() {
+    return _wrapAsyncGenerator(function*() {
+        yield* _asyncGeneratorDelegate(_asyncIterator(other()), _awaitAsyncGenerator);
+    }).apply(this, arguments);
+}"
+);
+
+test!(
+    syntax(),
+    |_| async_generator(),
+    for_await_in_async_fn,
+    "async function f() {
+    for await (const x of y) {
+        g(x);
+    }
+}",
+    "async function f() {
+    var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+    try {
+        for(var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(), \
+     _iteratorNormalCompletion = _step.done, _value = await _step.value, \
+     !_iteratorNormalCompletion; _iteratorNormalCompletion = true){
+            const x = _value;
+            g(x);
+        }
+    } catch (err) {
+        _didIteratorError = true;
+        _iteratorError = err;
+    } finally{
+        try {
+            if (!_iteratorNormalCompletion && _iterator.return != null) {
+                await _iterator.return();
+            }
+        } finally{
+            if (_didIteratorError) {
+                throw _iteratorError;
+            }
+        }
+    }
+}"
+);
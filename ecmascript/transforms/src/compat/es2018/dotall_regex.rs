@@ -0,0 +1,98 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::Fold;
+
+/// `@babel/plugin-transform-dotall-regex`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// /a.b/s;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// /a[\s\S]b/;
+/// ```
+pub fn dotall_regex() -> impl Pass {
+    DotAllRegex
+}
+
+#[derive(Clone, Copy)]
+struct DotAllRegex;
+
+impl Fold<Regex> for DotAllRegex {
+    fn fold(&mut self, r: Regex) -> Regex {
+        if !r.flags.contains('s') {
+            return r;
+        }
+
+        let mut exp = String::with_capacity(r.exp.len() + 8);
+        let mut in_class = false;
+        let mut chars = r.exp.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    exp.push(c);
+                    if let Some(c) = chars.next() {
+                        exp.push(c);
+                    }
+                }
+                '[' if !in_class => {
+                    in_class = true;
+                    exp.push(c);
+                }
+                ']' if in_class => {
+                    in_class = false;
+                    exp.push(c);
+                }
+                '.' if !in_class => exp.push_str("[\\s\\S]"),
+                _ => exp.push(c),
+            }
+        }
+
+        Regex {
+            exp: exp.into(),
+            flags: r
+                .flags
+                .chars()
+                .filter(|&c| c != 's')
+                .collect::<String>()
+                .into(),
+            ..r
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dotall_regex(),
+        babel_simple,
+        "var a = /./s;",
+        "var a = /[\\s\\S]/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dotall_regex(),
+        babel_with_flags,
+        "var a = /a.[.]\\./gis;",
+        "var a = /a[\\s\\S][.]\\./gi;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dotall_regex(),
+        babel_no_flag,
+        "var a = /a.b/g;",
+        "var a = /a.b/g;"
+    );
+}
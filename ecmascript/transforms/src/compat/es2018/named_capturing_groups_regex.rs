@@ -0,0 +1,200 @@
+use crate::{pass::Pass, util::ExprFactory};
+use ast::*;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-named-capturing-groups-regex`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var re = /(?<year>\d{4})-(?<month>\d{2})/;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var re = _wrapRegExp(/(\d{4})-(\d{2})/, { year: 1, month: 2 });
+/// ```
+pub fn named_capturing_groups_regex() -> impl Pass {
+    NamedCapturingGroupsRegex
+}
+
+#[derive(Clone, Copy)]
+struct NamedCapturingGroupsRegex;
+
+impl Fold<Expr> for NamedCapturingGroupsRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex { span, exp, flags })) => {
+                let groups = named_groups(&exp);
+                if groups.is_empty() {
+                    return Expr::Lit(Lit::Regex(Regex { span, exp, flags }));
+                }
+
+                let exp = remove_names(&exp, &groups);
+                let groups = ObjectLit {
+                    span: DUMMY_SP,
+                    props: groups
+                        .into_iter()
+                        .map(|(name, idx)| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!(name)),
+                                value: box Expr::Lit(Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: idx as f64,
+                                })),
+                            }))
+                        })
+                        .collect(),
+                };
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: helper!(wrap_reg_exp, "wrapRegExp"),
+                    args: vec![
+                        Expr::Lit(Lit::Regex(Regex {
+                            span: DUMMY_SP,
+                            exp: exp.into(),
+                            flags,
+                        }))
+                        .as_arg(),
+                        groups.as_arg(),
+                    ],
+                    type_args: Default::default(),
+                })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Returns names of capturing groups with their indexes.
+fn named_groups(exp: &str) -> Vec<(JsWord, usize)> {
+    let mut groups = vec![];
+    let mut idx = 0;
+    let mut in_class = false;
+    let mut chars = exp.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => {
+                if chars.peek() != Some(&'?') {
+                    idx += 1;
+                    continue;
+                }
+
+                let rest = chars.clone().skip(1).take(2).collect::<String>();
+                if rest.starts_with('<') && rest != "<=" && rest != "<!" {
+                    idx += 1;
+                    let name: String = chars
+                        .clone()
+                        .skip(2)
+                        .take_while(|&c| c != '>')
+                        .collect();
+                    groups.push((name.into(), idx));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    groups
+}
+
+/// `(?<year>\d{4})\k<year>` => `(\d{4})\1`
+fn remove_names(exp: &str, groups: &[(JsWord, usize)]) -> String {
+    let mut buf = String::with_capacity(exp.len());
+    let mut in_class = false;
+    let mut chars = exp.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('k') if !in_class && chars.peek() == Some(&'<') => {
+                    chars.next();
+                    let name: String = chars.by_ref().take_while(|&c| c != '>').collect();
+                    let idx = groups
+                        .iter()
+                        .find(|g| *g.0 == *name)
+                        .map(|g| g.1)
+                        .unwrap_or(0);
+
+                    // `\1` followed by a digit is another backreference.
+                    if chars.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                        buf.push_str(&format!("(?:\\{})", idx));
+                    } else {
+                        buf.push_str(&format!("\\{}", idx));
+                    }
+                }
+                Some(c) => {
+                    buf.push('\\');
+                    buf.push(c);
+                }
+                None => buf.push('\\'),
+            },
+            '[' if !in_class => {
+                in_class = true;
+                buf.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                buf.push(c);
+            }
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                let rest = chars.clone().skip(1).take(2).collect::<String>();
+                buf.push(c);
+                if rest.starts_with('<') && rest != "<=" && rest != "<!" {
+                    // Skip `?<name>`
+                    chars.by_ref().take_while(|&c| c != '>').for_each(drop);
+                }
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_simple,
+        "var re = /(?<year>\\d{4})-(?<month>\\d{2})/u;",
+        "var re = _wrapRegExp(/(\\d{4})-(\\d{2})/u, {
+    year: 1,
+    month: 2
+});"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_backreference,
+        "var re = /(a)(?:b)(?<=c)(?<x>d)\\k<x>[(]/;",
+        "var re = _wrapRegExp(/(a)(?:b)(?<=c)(d)\\2[(]/, {
+    x: 2
+});"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_no_groups,
+        "var re = /(a)\\k/;",
+        "var re = /(a)\\k/;"
+    );
+}
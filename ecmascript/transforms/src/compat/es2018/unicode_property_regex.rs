@@ -0,0 +1,175 @@
+use crate::{pass::Pass, util::HANDLER};
+use ast::*;
+use swc_common::Fold;
+
+/// `@babel/plugin-proposal-unicode-property-regex`
+///
+/// Only binary properties which don't require unicode data tables are
+/// expanded. Regular expressions using other properties are left as is, and a
+/// warning is reported for them.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// /\p{ASCII_Hex_Digit}+/u;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// /[0-9A-Fa-f]+/u;
+/// ```
+pub fn unicode_property_regex() -> impl Pass {
+    UnicodePropertyRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodePropertyRegex;
+
+/// Property name to the content of a character class.
+static PROPERTIES: &[(&[&str], &str)] = &[
+    (&["Any"], "\\0-\\u{10FFFF}"),
+    (&["ASCII"], "\\0-\\x7F"),
+    (&["ASCII_Hex_Digit", "AHex"], "0-9A-Fa-f"),
+    (
+        &["Hex_Digit", "Hex"],
+        "0-9A-Fa-f\\uFF10-\\uFF19\\uFF21-\\uFF26\\uFF41-\\uFF46",
+    ),
+    (
+        &["White_Space", "space"],
+        "\\t-\\r \\x85\\xA0\\u1680\\u2000-\\u200A\\u2028\\u2029\\u202F\\u205F\\u3000",
+    ),
+    (
+        &["Noncharacter_Code_Point", "NChar"],
+        "\\uFDD0-\\uFDEF\\uFFFE\\uFFFF",
+    ),
+    (
+        &["Pattern_White_Space", "Pat_WS"],
+        "\\t-\\r \\x85\\u200E\\u200F\\u2028\\u2029",
+    ),
+];
+
+impl Fold<Regex> for UnicodePropertyRegex {
+    fn fold(&mut self, r: Regex) -> Regex {
+        if !r.flags.contains('u') {
+            return r;
+        }
+
+        match expand(&r.exp) {
+            Ok(Some(exp)) => Regex {
+                exp: exp.into(),
+                ..r
+            },
+            Ok(None) => r,
+            Err(msg) => {
+                HANDLER.with(|handler| handler.struct_span_warn(r.span, &msg).emit());
+                r
+            }
+        }
+    }
+}
+
+/// Returns `None` if `exp` does not use a property at all, and an error
+/// message if it uses an unsupported property.
+fn expand(exp: &str) -> Result<Option<String>, String> {
+    let mut buf = String::with_capacity(exp.len());
+    let mut in_class = false;
+    let mut changed = false;
+    let mut chars = exp.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(p @ 'p') | Some(p @ 'P') => {
+                    if chars.next() != Some('{') {
+                        return Ok(None);
+                    }
+                    let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let class = match PROPERTIES.iter().find(|(names, _)| names.contains(&&*name)) {
+                        Some((_, class)) => *class,
+                        None => {
+                            return Err(format!(
+                                "Unicode property escape `\\{}{{{}}}` is not supported",
+                                p, name
+                            ))
+                        }
+                    };
+
+                    changed = true;
+                    match (in_class, p) {
+                        (true, 'p') => buf.push_str(class),
+                        // Negated set in a class can't be inlined.
+                        (true, _) => {
+                            return Err(format!(
+                                "Unicode property escape `\\P{{{}}}` in a character class is not \
+                                 supported",
+                                name
+                            ))
+                        }
+                        (false, 'p') => {
+                            buf.push('[');
+                            buf.push_str(class);
+                            buf.push(']');
+                        }
+                        (false, _) => {
+                            buf.push_str("[^");
+                            buf.push_str(class);
+                            buf.push(']');
+                        }
+                    }
+                }
+                Some(c) => {
+                    buf.push('\\');
+                    buf.push(c);
+                }
+                None => buf.push('\\'),
+            },
+            '[' if !in_class => {
+                in_class = true;
+                buf.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    if changed {
+        Ok(Some(buf))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_simple,
+        "var a = /\\p{ASCII_Hex_Digit}+/u;",
+        "var a = /[0-9A-Fa-f]+/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_negated_and_class,
+        "var a = /\\P{ASCII}[_\\p{AHex}]/u;",
+        "var a = /[^\\0-\\x7F][_0-9A-Fa-f]/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        unsupported,
+        "var a = /\\p{Script=Greek}/u;",
+        "var a = /\\p{Script=Greek}/u;"
+    );
+}
@@ -0,0 +1,8 @@
+pub use self::json_strings::json_strings;
+use crate::pass::Pass;
+
+mod json_strings;
+
+pub fn es2019() -> impl Pass {
+    json_strings()
+}
@@ -0,0 +1,33 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::{Fold, Mark};
+
+/// `@babel/plugin-proposal-json-strings`
+///
+/// U+2028 and U+2029 are not allowed in string literals before es2019. The code
+/// generator always escapes them, so this pass only marks string literals
+/// containing them as modified, to prevent their original text from being
+/// printed if `keep_raw_literals` is enabled.
+pub fn json_strings() -> impl Pass {
+    JsonStrings {
+        mark: Mark::fresh(Mark::root()),
+    }
+}
+
+#[derive(Clone, Copy)]
+struct JsonStrings {
+    mark: Mark,
+}
+
+impl Fold<Str> for JsonStrings {
+    fn fold(&mut self, s: Str) -> Str {
+        if !s.value.contains(|c| c == '\u{2028}' || c == '\u{2029}') {
+            return s;
+        }
+
+        Str {
+            span: s.span.apply_mark(self.mark),
+            ..s
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub use self::{nullish_coalescing::nullish_coalescing, opt_chaining::optional_chaining};
+use crate::pass::Pass;
+use swc_common::chain;
+
+mod nullish_coalescing;
+mod opt_chaining;
+
+pub fn es2020() -> impl Pass {
+    chain!(nullish_coalescing(), optional_chaining())
+}
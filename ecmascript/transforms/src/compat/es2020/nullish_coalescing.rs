@@ -0,0 +1,111 @@
+use crate::{
+    pass::Pass,
+    util::{prepend, undefined, StmtLike},
+};
+use ast::*;
+use std::{fmt::Debug, mem};
+use swc_common::{Fold, FoldWith, Spanned, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// `a ?? b` => `a !== null && a !== void 0 ? a : b`
+pub fn nullish_coalescing() -> impl Pass {
+    NullishCoalescing::default()
+}
+
+#[derive(Debug, Default)]
+struct NullishCoalescing {
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T> Fold<Vec<T>> for NullishCoalescing
+where
+    T: Debug + StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        // This is to support nested block statements
+        let old = mem::replace(&mut self.vars, vec![]);
+
+        let mut stmts = stmts.fold_children(self);
+
+        if !self.vars.is_empty() {
+            prepend(
+                &mut stmts,
+                T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    declare: false,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut self.vars, vec![]),
+                }))),
+            );
+        }
+
+        self.vars = old;
+        stmts
+    }
+}
+
+impl Fold<Expr> for NullishCoalescing {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Bin(BinExpr {
+                span,
+                left,
+                op: op!("??"),
+                right,
+            }) => {
+                let (l, aliased) = match *left {
+                    Expr::Ident(ref i) => (i.clone(), false),
+                    _ => {
+                        let i = private_ident!(left.span(), "ref");
+                        self.vars.push(VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(i.clone()),
+                            init: None,
+                            definite: false,
+                        });
+                        (i, true)
+                    }
+                };
+
+                let test_left = if aliased {
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(box Pat::Ident(l.clone())),
+                        right: left,
+                    })
+                } else {
+                    left
+                };
+
+                validate!(Expr::Cond(CondExpr {
+                    span,
+                    test: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: test_left,
+                            op: op!("!=="),
+                            right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                        }),
+                        op: op!("&&"),
+                        right: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::Ident(l.clone()),
+                            op: op!("!=="),
+                            right: undefined(DUMMY_SP),
+                        }),
+                    }),
+                    cons: box Expr::Ident(l),
+                    alt: right,
+                }))
+            }
+
+            _ => e,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+use swc_ecma_parser::{EsConfig, Syntax};
+
+fn tr(_: ()) -> impl Pass {
+    nullish_coalescing()
+}
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        nullish_coalescing: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    syntax(),
+    |_| tr(()),
+    runtime_semantics_ident,
+    "const foo = bar ?? 'default';",
+    "const foo = bar !== null && bar !== void 0 ? bar : 'default';"
+);
+
+test!(
+    syntax(),
+    |_| tr(()),
+    runtime_semantics_member,
+    "const foo = obj.foo ?? 'default';",
+    "var ref;
+const foo = (ref = obj.foo) !== null && ref !== void 0 ? ref : 'default';"
+);
+
+test!(
+    syntax(),
+    |_| tr(()),
+    nested,
+    "const foo = a.b ?? c.d ?? 'default';",
+    "var ref, ref1;
+const foo = (ref1 = (ref = a.b) !== null && ref !== void 0 ? ref : c.d) !== null && ref1 !== \
+     void 0 ? ref1 : 'default';"
+);
+
+test!(
+    syntax(),
+    |_| tr(()),
+    in_function,
+    "function foo(opts) {
+    var foo = opts.foo ?? 'default';
+}",
+    "function foo(opts) {
+    var ref;
+    var foo = (ref = opts.foo) !== null && ref !== void 0 ? ref : 'default';
+}"
+);
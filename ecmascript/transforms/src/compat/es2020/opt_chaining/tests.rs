@@ -1,12 +1,13 @@
 use super::*;
-use swc_ecma_parser::{Syntax, TsConfig};
+use swc_ecma_parser::{EsConfig, Syntax};
 
 fn tr(_: ()) -> impl Pass {
     optional_chaining()
 }
 
 fn syntax() -> Syntax {
-    Syntax::Typescript(TsConfig {
+    Syntax::Es(EsConfig {
+        optional_chaining: true,
         ..Default::default()
     })
 }
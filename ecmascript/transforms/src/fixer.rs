@@ -383,7 +383,9 @@ impl Fold<Expr> for Fixer {
                     | e @ Expr::Cond(..)
                     | e @ Expr::Arrow(..) => box validate!(e).wrap_with_paren(),
                     Expr::Bin(BinExpr { op: op_of_rhs, .. }) => {
-                        if op_of_rhs.precedence() <= expr.op.precedence() {
+                        if op_of_rhs.precedence() <= expr.op.precedence()
+                            || mixes_nullish_with_logical(expr.op, op_of_rhs)
+                        {
                             box expr.right.wrap_with_paren()
                         } else {
                             validate!(expr.right)
@@ -396,7 +398,9 @@ impl Fold<Expr> for Fixer {
                     // While simplifying, (1 + x) * Nan becomes `1 + x * Nan`.
                     // But it should be `(1 + x) * Nan`
                     Expr::Bin(BinExpr { op: op_of_lhs, .. }) => {
-                        if op_of_lhs.precedence() < expr.op.precedence() {
+                        if op_of_lhs.precedence() < expr.op.precedence()
+                            || mixes_nullish_with_logical(expr.op, op_of_lhs)
+                        {
                             Expr::Bin(validate!(BinExpr {
                                 left: box expr.left.wrap_with_paren(),
                                 ..expr
@@ -631,6 +635,13 @@ fn ignore_return_value(expr: Box<Expr>) -> Option<Box<Expr>> {
     }
 }
 
+/// `??` cannot be mixed with `||` or `&&` without parens.
+fn mixes_nullish_with_logical(op: BinaryOp, child: BinaryOp) -> bool {
+    let is_logical = |op| op == op!("||") || op == op!("&&");
+
+    (op == op!("??") && is_logical(child)) || (is_logical(op) && child == op!("??"))
+}
+
 fn handle_expr_stmt(expr: Expr) -> Expr {
     match expr {
         // It's important for arrow pass to work properly.
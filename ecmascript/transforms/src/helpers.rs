@@ -165,6 +165,7 @@ define_helpers!(Helpers {
     class_private_method_set: (),
     class_static_private_field_spec_get: (),
     class_static_private_field_spec_set: (),
+    class_static_private_method_get: (),
     construct: (set_prototype_of),
     create_class: (),
     decorate: (to_array, to_property_key),
@@ -213,6 +214,9 @@ define_helpers!(Helpers {
     to_consumable_array: (array_without_holes, iterable_to_array, non_iterable_spread),
     to_primitive: (type_of),
     to_property_key: (type_of, to_primitive),
+    ts_decorate: (),
+    ts_metadata: (),
+    ts_param: (),
     type_of: (),
     wrap_async_generator: (async_generator),
     wrap_native_super: (
@@ -221,6 +225,7 @@ define_helpers!(Helpers {
         set_prototype_of,
         is_native_function
     ),
+    wrap_reg_exp: (wrap_native_super, inherits),
 
     class_private_field_destructure: (),
 });
@@ -3,5 +3,11 @@ function _classPrivateFieldGet(receiver, privateMap) {
     throw new TypeError("attempted to get private field on non-instance");
   }
 
-  return privateMap.get(receiver).value;
+  var descriptor = privateMap.get(receiver);
+
+  if (descriptor.get) {
+    return descriptor.get.call(receiver);
+  }
+
+  return descriptor.value;
 }
@@ -0,0 +1,5 @@
+function _tsMetadata(key, value) {
+  if (typeof Reflect === "object" && typeof Reflect.metadata === "function") {
+    return Reflect.metadata(key, value);
+  }
+}
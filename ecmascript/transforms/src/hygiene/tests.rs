@@ -497,7 +497,8 @@ fn fn_args() {
                             .parse_stmt("actual1.js", "_defineProperty(this, 'force', force);")?],
                     }),
                     params: vec![Pat::Ident(quote_ident!("force"))
-                        .fold_with(&mut marker(&[("force", mark1)]))],
+                        .fold_with(&mut marker(&[("force", mark1)]))
+                        .into()],
                     type_params: Default::default(),
                     return_type: Default::default(),
                 },
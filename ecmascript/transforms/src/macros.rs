@@ -12,10 +12,11 @@ macro_rules! impl_fold_fn {
                 let f = validate!(f);
                 let f = f.fold_children(self);
 
-                let (params, body) = self.fold_fn_like(f.params, f.body.unwrap());
+                let params = f.params.into_iter().map(|param| param.pat).collect();
+                let (params, body) = self.fold_fn_like(params, f.body.unwrap());
 
                 validate!(Function {
-                    params,
+                    params: params.into_iter().map(Param::from).collect(),
                     body: Some(body),
                     ..f
                 })
@@ -138,8 +139,8 @@ macro_rules! impl_fold_fn {
                 let params = f
                     .params
                     .into_iter()
-                    .map(|pat| match pat {
-                        PatOrTsParamProp::Pat(p) => p,
+                    .map(|param| match param {
+                        ParamOrTsParamProp::Param(p) => p.pat,
                         _ => unreachable!(
                             "TsParameterProperty should be removed by typescript::strip pass"
                         ),
@@ -149,7 +150,10 @@ macro_rules! impl_fold_fn {
                 let (params, body) = self.fold_fn_like(params, f.body.unwrap());
 
                 validate!(Constructor {
-                    params: params.into_iter().map(PatOrTsParamProp::Pat).collect(),
+                    params: params
+                        .into_iter()
+                        .map(|pat| ParamOrTsParamProp::Param(pat.into()))
+                        .collect(),
                     body: Some(body),
                     ..f
                 })
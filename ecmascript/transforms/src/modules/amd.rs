@@ -1,6 +1,7 @@
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_amd_dynamic_import, make_descriptor, use_strict, Exports, ModulePass,
+    Scope,
 };
 use crate::{
     pass::Pass,
@@ -11,7 +12,7 @@ use fxhash::FxHashSet;
 use serde::{Deserialize, Serialize};
 use std::iter;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, Mark, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, Span, VisitWith, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -540,7 +541,7 @@ impl Fold<Module> for Amd {
                                 is_async: false,
                                 is_generator: false,
                                 decorators: Default::default(),
-                                params: factory_params,
+                                params: factory_params.into_iter().map(Param::from).collect(),
                                 body: Some(BlockStmt {
                                     span: DUMMY_SP,
                                     stmts,
@@ -611,5 +612,9 @@ impl ModulePass for Amd {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        make_amd_dynamic_import(span, args, self.config.config.no_interop)
+    }
 }
 mark_as_nested!(Amd);
@@ -1163,3 +1163,73 @@ export const foo = function () {
 });
 "
 );
+
+fn dynamic_import_syntax() -> Syntax {
+    Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Default::default()),
+    dynamic_import,
+    "import('./foo').then(foo => foo.default);",
+    "define([], function() {
+    'use strict';
+    new Promise(function (_resolve, _reject) {
+        require(['./foo'], function (m) {
+            _resolve(_interopRequireWildcard(m));
+        }, _reject);
+    }).then(foo => foo.default);
+});"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        config: util::Config {
+            no_interop: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }),
+    dynamic_import_no_interop,
+    "export function load(name) {
+    return import(name);
+}",
+    "define(['exports'], function(_exports) {
+    'use strict';
+    Object.defineProperty(_exports, '__esModule', {
+        value: true
+    });
+    _exports.load = load;
+    function load(name) {
+        return new Promise(function (_resolve, _reject) {
+            require([name], _resolve, _reject);
+        });
+    }
+});"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        config: util::Config {
+            lazy: util::Lazy::Bool(true),
+            ..Default::default()
+        },
+        ..Default::default()
+    }),
+    dynamic_import_lazy,
+    "import('foo').then(foo => foo.default);",
+    "define([], function() {
+    'use strict';
+    new Promise(function (_resolve, _reject) {
+        require(['foo'], function (m) {
+            _resolve(_interopRequireWildcard(m));
+        }, _reject);
+    }).then(foo => foo.default);
+});"
+);
@@ -1,7 +1,7 @@
 pub use super::util::Config;
 use super::util::{
-    define_es_module, define_property, has_use_strict, initialize_to_undefined, make_descriptor,
-    make_require_call, use_strict, ModulePass, Scope,
+    define_es_module, define_property, has_use_strict, initialize_to_undefined,
+    make_cjs_dynamic_import, make_descriptor, make_require_call, use_strict, ModulePass, Scope,
 };
 use crate::{
     pass::Pass,
@@ -10,7 +10,7 @@ use crate::{
 use ast::*;
 use fxhash::FxHashSet;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Span, VisitWith, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -664,6 +664,10 @@ impl ModulePass for CommonJs {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        make_cjs_dynamic_import(span, args, self.config.no_interop)
+    }
 }
 
 mark_as_nested!(CommonJs);
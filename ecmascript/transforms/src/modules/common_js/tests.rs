@@ -4038,3 +4038,65 @@ const foo = function() {
 }();
 exports.foo = foo;"
 );
+
+fn dynamic_import_syntax() -> ::swc_ecma_parser::Syntax {
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Default::default()),
+    dynamic_import,
+    "import('./foo').then(foo => foo.default);",
+    "'use strict';
+Promise.resolve().then(function () {
+    return _interopRequireWildcard(require('./foo'));
+}).then(foo => foo.default);"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        no_interop: true,
+        ..Default::default()
+    }),
+    dynamic_import_no_interop,
+    "function load(name) {
+    return import(`./locales/${name}`);
+}",
+    "'use strict';
+function load(name) {
+    return Promise.resolve(String(`./locales/${name}`)).then(function (_s) {
+        return require(_s);
+    });
+}"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        lazy: Lazy::Bool(true),
+        ..Default::default()
+    }),
+    dynamic_import_lazy,
+    "import foo from 'foo';
+function load() {
+    return import('foo').then(() => foo);
+}",
+    "'use strict';
+function _foo() {
+    const data = _interopRequireDefault(require('foo'));
+    _foo = function () {
+        return data;
+    };
+    return data;
+}
+function load() {
+    return Promise.resolve().then(function () {
+        return _interopRequireWildcard(require('foo'));
+    }).then(() => _foo().default);
+}"
+);
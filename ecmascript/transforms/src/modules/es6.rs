@@ -0,0 +1,415 @@
+use crate::{pass::Pass, util::HANDLER};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, Span, Spanned, SyntaxContext};
+
+#[cfg(test)]
+mod tests;
+
+/// Keeps es modules, but rewrites module specifiers.
+///
+/// Specifiers are rewritten by `alias`, then by `paths` and the extension
+/// is appended last.
+pub fn es6(config: Config) -> impl Pass {
+    Es6 { config }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Replaces module names.
+    ///
+    /// `{ "lodash": "lodash-es" }` rewrites `lodash/fp` to `lodash-es/fp`
+    /// as well.
+    #[serde(default)]
+    pub alias: BTreeMap<String, String>,
+
+    /// Directory `paths` are resolved from.
+    #[serde(default)]
+    pub base_url: Option<PathBuf>,
+
+    /// tsconfig-style path mapping, like `{ "@app/*": ["src/app/*"] }`.
+    ///
+    /// Matched specifiers are replaced with a path relative to the file
+    /// being compiled. Only the first target of a pattern is used.
+    #[serde(default)]
+    pub paths: BTreeMap<String, Vec<String>>,
+
+    /// Appended to relative specifiers without an extension, like `.js`
+    /// for node.
+    #[serde(default)]
+    pub extension: Option<String>,
+
+    /// Modules known to be es modules.
+    ///
+    /// Top-level `require()` statements of them are converted to imports.
+    #[serde(default)]
+    pub esm_modules: Vec<JsWord>,
+
+    /// Path of the file being compiled. Required for `paths`.
+    #[serde(skip)]
+    pub filename: Option<PathBuf>,
+}
+
+/// Extensions which are not replaced by `extension`.
+const EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "json", "node"];
+
+struct Es6 {
+    config: Config,
+}
+
+impl Es6 {
+    /// Returns the new specifier, if `src` should be rewritten.
+    fn rewrite(&self, src: &str, span: Span) -> Option<String> {
+        let mut changed = false;
+        let mut src = src.to_string();
+
+        if let Some(aliased) = self.alias(&src) {
+            changed = true;
+            src = aliased;
+        }
+
+        if let Some(mapped) = self.map_path(&src, span) {
+            changed = true;
+            src = mapped;
+        }
+
+        if let Some(ref ext) = self.config.extension {
+            if is_relative(&src) && src != "." && src != ".." && !src.ends_with('/') {
+                // `./foo.bar` may refer to `./foo.bar.js`.
+                let has_ext = Path::new(&src)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map_or(false, |ext| EXTENSIONS.contains(&ext));
+                if !has_ext {
+                    changed = true;
+                    src.push_str(ext);
+                }
+            }
+        }
+
+        if changed {
+            Some(src)
+        } else {
+            None
+        }
+    }
+
+    fn alias(&self, src: &str) -> Option<String> {
+        let (name, to) = self
+            .config
+            .alias
+            .iter()
+            .filter(|(name, _)| {
+                src == name.as_str()
+                    || (src.starts_with(name.as_str()) && src[name.len()..].starts_with('/'))
+            })
+            .max_by_key(|(name, _)| name.len())?;
+
+        Some(format!("{}{}", to, &src[name.len()..]))
+    }
+
+    /// Maps `src` using `paths`, like `tsc`.
+    ///
+    /// An exact match wins, and then the pattern with the longest prefix.
+    fn map_path(&self, src: &str, span: Span) -> Option<String> {
+        if is_relative(src) || self.config.paths.is_empty() {
+            return None;
+        }
+
+        let (target, matched) = match self.config.paths.get(src) {
+            Some(targets) => (targets.first()?, ""),
+            None => {
+                let (pattern, targets) = self
+                    .config
+                    .paths
+                    .iter()
+                    .filter(|(pattern, _)| {
+                        let mut parts = pattern.splitn(2, '*');
+                        match (parts.next(), parts.next()) {
+                            (Some(prefix), Some(suffix)) => {
+                                src.len() >= prefix.len() + suffix.len()
+                                    && src.starts_with(prefix)
+                                    && src.ends_with(suffix)
+                            }
+                            _ => false,
+                        }
+                    })
+                    .max_by_key(|(pattern, _)| pattern.find('*'))?;
+
+                let prefix = pattern.find('*').unwrap();
+                let suffix = pattern.len() - prefix - 1;
+
+                (targets.first()?, &src[prefix..src.len() - suffix])
+            }
+        };
+
+        let dir = match self.config.filename {
+            Some(ref filename) => filename.parent()?,
+            None => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            span,
+                            &format!(
+                                "cannot map `{}` using `paths` without the name of the file",
+                                src
+                            ),
+                        )
+                        .emit()
+                });
+                return None;
+            }
+        };
+
+        let target = target.replacen('*', matched, 1);
+        let base_url = match self.config.base_url {
+            Some(ref base_url) => base_url.as_path(),
+            None => Path::new(""),
+        };
+        let path = relative_path(&normalize(dir), &normalize(&base_url.join(target)));
+
+        let mut s = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !s.starts_with("../") && s != ".." {
+            s.insert_str(0, "./");
+        }
+        Some(s)
+    }
+
+    fn rewrite_str(&self, s: Str) -> Str {
+        match self.rewrite(&s.value, s.span) {
+            Some(value) => Str {
+                value: value.into(),
+                has_escape: false,
+                ..s
+            },
+            None => s,
+        }
+    }
+
+    /// Returns the module if `e` is `require('foo')` of an es module.
+    fn esm_require<'a>(&self, e: &'a Expr) -> Option<&'a Str> {
+        match *e {
+            Expr::Call(CallExpr {
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("require"),
+                        span,
+                        ..
+                    })),
+                ref args,
+                ..
+            }) if span.ctxt() == SyntaxContext::empty() && args.len() == 1 => match args[0] {
+                ExprOrSpread {
+                    spread: None,
+                    expr: box Expr::Lit(Lit::Str(ref src)),
+                } if self.config.esm_modules.contains(&src.value) => Some(src),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts
+    ///
+    /// - `require('foo');` to `import 'foo';`
+    /// - `const foo = require('foo');` to `import * as foo from 'foo';`
+    /// - `const { a, b: c } = require('foo');` to `import { a, b as c } from
+    ///   'foo';`
+    fn convert_require(&self, stmt: Stmt) -> ModuleItem {
+        match stmt {
+            Stmt::Expr(ExprStmt { span, ref expr }) => {
+                if let Some(src) = self.esm_require(expr) {
+                    return ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span,
+                        specifiers: vec![],
+                        src: src.clone(),
+                    }));
+                }
+            }
+
+            Stmt::Decl(Decl::Var(VarDecl {
+                span,
+                kind: VarDeclKind::Const,
+                ref decls,
+                ..
+            })) if decls.len() == 1 => {
+                let src = match decls[0].init {
+                    Some(ref init) => self.esm_require(init),
+                    None => None,
+                };
+
+                if let Some(src) = src {
+                    if let Some(specifiers) = import_specifiers(&decls[0].name) {
+                        return ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                            span,
+                            specifiers,
+                            src: src.clone(),
+                        }));
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        ModuleItem::Stmt(stmt)
+    }
+}
+
+/// Specifiers importing the bindings `pat` declares, if `pat` can be
+/// expressed as an import.
+fn import_specifiers(pat: &Pat) -> Option<Vec<ImportSpecifier>> {
+    match *pat {
+        Pat::Ident(ref local) => Some(vec![ImportSpecifier::Namespace(ImportStarAs {
+            span: local.span,
+            local: local.clone(),
+        })]),
+        Pat::Object(ObjectPat { ref props, .. }) => props
+            .iter()
+            .map(|prop| match *prop {
+                ObjectPatProp::Assign(AssignPatProp {
+                    span,
+                    ref key,
+                    value: None,
+                }) => Some(ImportSpecifier::Specific(ImportSpecific {
+                    span,
+                    local: key.clone(),
+                    imported: None,
+                })),
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(ref key),
+                    value: box Pat::Ident(ref local),
+                }) => Some(ImportSpecifier::Specific(ImportSpecific {
+                    span: prop.span(),
+                    local: local.clone(),
+                    imported: Some(key.clone()),
+                })),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+fn is_relative(src: &str) -> bool {
+    src == "." || src == ".." || src.starts_with("./") || src.starts_with("../")
+}
+
+/// Resolves `.` and `..` lexically.
+fn normalize(path: &Path) -> PathBuf {
+    let mut buf = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !buf.pop() {
+                    buf.push(c);
+                }
+            }
+            _ => buf.push(c),
+        }
+    }
+    buf
+}
+
+/// Returns `path` relative to the directory `base`.
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let base: Vec<_> = base.components().collect();
+    let path: Vec<_> = path.components().collect();
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+
+    base[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path[common..].iter().cloned())
+        .collect()
+}
+
+impl Fold<Module> for Es6 {
+    fn fold(&mut self, module: Module) -> Module {
+        // Other `require()` calls are left as is, because hoisting them would change
+        // the order of evaluation.
+        let body = if self.config.esm_modules.is_empty() {
+            module.body
+        } else {
+            module
+                .body
+                .into_iter()
+                .map(|item| match item {
+                    ModuleItem::Stmt(stmt) => self.convert_require(stmt),
+                    _ => item,
+                })
+                .collect()
+        };
+
+        Module {
+            body: body.fold_with(self),
+            ..module
+        }
+    }
+}
+
+impl Fold<ImportDecl> for Es6 {
+    fn fold(&mut self, import: ImportDecl) -> ImportDecl {
+        ImportDecl {
+            src: self.rewrite_str(import.src),
+            ..import
+        }
+    }
+}
+
+impl Fold<NamedExport> for Es6 {
+    fn fold(&mut self, export: NamedExport) -> NamedExport {
+        NamedExport {
+            src: export.src.map(|src| self.rewrite_str(src)),
+            ..export
+        }
+    }
+}
+
+impl Fold<ExportAll> for Es6 {
+    fn fold(&mut self, export: ExportAll) -> ExportAll {
+        ExportAll {
+            src: self.rewrite_str(export.src),
+            ..export
+        }
+    }
+}
+
+impl Fold<Expr> for Es6 {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let mut e = e.fold_children(self);
+
+        // import('foo')
+        if let Expr::Call(CallExpr {
+            callee:
+                ExprOrSuper::Expr(box Expr::Ident(Ident {
+                    sym: js_word!("import"),
+                    ..
+                })),
+            ref mut args,
+            ..
+        }) = e
+        {
+            if let Some(&mut ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(ref mut src)),
+            }) = args.first_mut()
+            {
+                *src = self.rewrite_str(src.clone());
+            }
+        }
+
+        e
+    }
+}
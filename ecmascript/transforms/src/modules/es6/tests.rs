@@ -0,0 +1,196 @@
+use super::{es6, Config};
+use crate::{resolver, tests::Tester};
+use ast::Module;
+use swc_common::{chain, Fold};
+use swc_ecma_parser::{EsConfig, Syntax};
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold<Module> {
+    chain!(resolver(), es6(config))
+}
+
+fn paths() -> Config {
+    Config {
+        base_url: Some("/project/src".into()),
+        paths: vec![
+            ("@app/*".into(), vec!["app/*".into()]),
+            ("@app/utils/*".into(), vec!["shared/utils/*".into()]),
+            ("config".into(), vec!["../config/index".into()]),
+        ]
+        .into_iter()
+        .collect(),
+        filename: Some("/project/src/app/pages/home.js".into()),
+        ..Default::default()
+    }
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    noop,
+    "import foo from 'foo';
+export * from './bar';",
+    "import foo from 'foo';
+export * from './bar';"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        alias: vec![
+            ("lodash".into(), "lodash-es".into()),
+            ("lodash/fp".into(), "lodash-fp".into()),
+            ("react".into(), "preact/compat".into()),
+        ]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    }),
+    alias,
+    "import map from 'lodash/map';
+import { flow } from 'lodash/fp';
+import lodash from 'lodash';
+import { useState } from 'react';
+import reactDom from 'react-dom';
+export { foo } from 'lodash';",
+    "import map from 'lodash-es/map';
+import { flow } from 'lodash-fp';
+import lodash from 'lodash-es';
+import { useState } from 'preact/compat';
+import reactDom from 'react-dom';
+export { foo } from 'lodash-es';"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        extension: Some(".js".into()),
+        ..Default::default()
+    }),
+    extension,
+    "import a from './a';
+import b from '../b.js';
+import c from './c.json';
+import d from 'd';
+import e from './e/';
+export * from './f';
+import('./g').then(g => g);
+import h from './h.min';",
+    "import a from './a.js';
+import b from '../b.js';
+import c from './c.json';
+import d from 'd';
+import e from './e/';
+export * from './f.js';
+import('./g.js').then(g => g);
+import h from './h.min.js';"
+);
+
+test!(
+    syntax(),
+    |_| tr(paths()),
+    paths,
+    "import button from '@app/components/button';
+import format from '@app/utils/format';
+import config from 'config';
+import other from 'other';",
+    "import button from '../components/button';
+import format from '../../shared/utils/format';
+import config from '../../../config/index';
+import other from 'other';"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        extension: Some(".js".into()),
+        ..paths()
+    }),
+    paths_with_extension,
+    "import home from '@app/pages/home2';",
+    "import home from './home2.js';"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        esm_modules: vec!["lodash-es".into(), "side-effect".into()],
+        ..Default::default()
+    }),
+    esm_require,
+    "'use strict';
+require('side-effect');
+const _ = require('lodash-es');
+const { map, filter: where } = require('lodash-es');
+const cjs = require('cjs');
+function f() {
+    return require('lodash-es').debounce;
+}",
+    "'use strict';
+import 'side-effect';
+import * as _ from 'lodash-es';
+import { map, filter as where } from 'lodash-es';
+const cjs = require('cjs');
+function f() {
+    return require('lodash-es').debounce;
+}"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        esm_modules: vec!["foo".into()],
+        ..Default::default()
+    }),
+    esm_require_shadowed,
+    "function f(require) {
+    return require('foo');
+}
+const foo = require('foo');",
+    "function f(require) {
+    return require('foo');
+}
+import * as foo from 'foo';"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        esm_modules: vec!["foo".into()],
+        ..Default::default()
+    }),
+    esm_require_nested,
+    "if (cond) {
+    const foo = require('foo');
+}
+let bar = cond && require('foo');",
+    "if (cond) {
+    const foo = require('foo');
+}
+let bar = cond && require('foo');"
+);
+
+#[test]
+fn paths_without_filename() {
+    Tester::run(|tester| {
+        tester.apply_transform(
+            tr(Config {
+                filename: None,
+                ..paths()
+            }),
+            "input.js",
+            syntax(),
+            "import config from 'config';
+import other from 'other';",
+        )?;
+        assert_eq!(tester.handler.err_count(), 1);
+
+        Ok(())
+    });
+}
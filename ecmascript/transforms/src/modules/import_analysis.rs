@@ -31,6 +31,21 @@ impl Fold<Module> for ImportAnalyzer {
     }
 }
 
+impl Visit<CallExpr> for ImportAnalyzer {
+    fn visit(&mut self, call: &CallExpr) {
+        call.visit_children(self);
+
+        // import('foo')
+        match call.callee {
+            ExprOrSuper::Expr(box Expr::Ident(Ident {
+                sym: js_word!("import"),
+                ..
+            })) => enable_helper!(interop_require_wildcard),
+            _ => {}
+        }
+    }
+}
+
 impl Visit<ExportAll> for ImportAnalyzer {
     fn visit(&mut self, export: &ExportAll) {
         *self
@@ -0,0 +1,909 @@
+use super::util::{default_strict_mode, has_use_strict, local_name_for_src, use_strict};
+use crate::{
+    pass::Pass,
+    util::{id, DestructuringFinder, ExprFactory, Id},
+};
+use ast::*;
+use fxhash::FxHashMap;
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+use std::{iter, mem};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, Mark, VisitWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// Converts es modules to the `System.register` format.
+///
+/// Imports become variables updated by the `setters` of the module, and
+/// every write to an exported binding is reported through `_export` so
+/// importers see live bindings.
+pub fn system_js(config: Config) -> impl Pass {
+    SystemJs {
+        config,
+        in_top_level: Default::default(),
+        export: private_ident!("_export"),
+        context: private_ident!("_context"),
+        exported: Default::default(),
+        hoisted: Default::default(),
+        tmp: None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub module_id: Option<String>,
+
+    #[serde(default = "default_strict_mode")]
+    pub strict_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            module_id: None,
+            strict_mode: default_strict_mode(),
+        }
+    }
+}
+
+struct SystemJs {
+    config: Config,
+    in_top_level: bool,
+    /// `_export` parameter of the declaration function.
+    export: Ident,
+    /// `_context` parameter of the declaration function.
+    context: Ident,
+    /// Exported local bindings and the names they are exported as.
+    exported: FxHashMap<Id, Vec<JsWord>>,
+    /// Variables declared in the declaration function.
+    hoisted: IndexSet<Id>,
+    /// Temporary variable used to preserve the value of an expression.
+    tmp: Option<Ident>,
+}
+
+/// A dependency of the module.
+struct Dep {
+    /// Parameter of the setter.
+    ident: Ident,
+    setter: Vec<Stmt>,
+}
+
+/// Binding created by an import.
+enum Imported {
+    Namespace,
+    Named(JsWord),
+}
+
+impl Fold<Module> for SystemJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let items = module.body;
+        self.in_top_level = true;
+
+        // ====================
+        //  Collect bindings
+        // ====================
+
+        let mut imports = FxHashMap::<Id, (JsWord, Imported)>::default();
+        // `export { foo }`
+        let mut local_exports = vec![];
+        // Names excluded from `export *`.
+        let mut own_names = vec![];
+
+        for item in &items {
+            match item {
+                ModuleItem::Stmt(..) => {}
+                ModuleItem::ModuleDecl(decl) => match decl {
+                    ModuleDecl::Import(import) => {
+                        for s in &import.specifiers {
+                            let (local, imported) = match s {
+                                ImportSpecifier::Specific(s) => (
+                                    &s.local,
+                                    Imported::Named(
+                                        s.imported.as_ref().unwrap_or(&s.local).sym.clone(),
+                                    ),
+                                ),
+                                ImportSpecifier::Default(s) => {
+                                    (&s.local, Imported::Named(js_word!("default")))
+                                }
+                                ImportSpecifier::Namespace(s) => (&s.local, Imported::Namespace),
+                            };
+                            imports.insert(id(local), (import.src.value.clone(), imported));
+                        }
+                    }
+
+                    ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                        let mut found: Vec<Ident> = vec![];
+                        match decl {
+                            Decl::Fn(f) => found.push(f.ident.clone()),
+                            Decl::Class(c) => found.push(c.ident.clone()),
+                            Decl::Var(var) => var
+                                .decls
+                                .visit_with(&mut DestructuringFinder { found: &mut found }),
+                            _ => {}
+                        }
+
+                        for i in found {
+                            own_names.push(i.sym.clone());
+                            self.exported.entry(id(&i)).or_default().push(i.sym);
+                        }
+                    }
+
+                    ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => {
+                        let ident = match decl {
+                            DefaultDecl::Class(ClassExpr {
+                                ident: Some(ident), ..
+                            }) => ident,
+                            DefaultDecl::Fn(FnExpr {
+                                ident: Some(ident), ..
+                            }) => ident,
+                            _ => continue,
+                        };
+                        self.exported
+                            .entry(id(ident))
+                            .or_default()
+                            .push(js_word!("default"));
+                    }
+
+                    ModuleDecl::ExportNamed(export) => {
+                        for s in &export.specifiers {
+                            match s {
+                                ExportSpecifier::Named(NamedExportSpecifier {
+                                    orig,
+                                    exported,
+                                    ..
+                                }) => {
+                                    let name = exported.as_ref().unwrap_or(orig).sym.clone();
+                                    own_names.push(name.clone());
+
+                                    if export.src.is_none() {
+                                        local_exports.push((id(orig), name));
+                                    }
+                                }
+                                ExportSpecifier::Default(DefaultExportSpecifier { exported })
+                                | ExportSpecifier::Namespace(NamespaceExportSpecifier {
+                                    name: exported,
+                                    ..
+                                }) => own_names.push(exported.sym.clone()),
+                            }
+                        }
+                    }
+
+                    _ => {}
+                },
+            }
+        }
+
+        for (orig, name) in local_exports {
+            if !imports.contains_key(&orig) {
+                self.exported.entry(orig).or_default().push(name);
+            }
+        }
+
+        // ====================
+        //  Process items
+        // ====================
+
+        let mut deps = IndexMap::<JsWord, Dep>::default();
+        macro_rules! dep {
+            ($src:expr) => {{
+                let src: JsWord = $src;
+                deps.entry(src.clone()).or_insert_with(|| Dep {
+                    ident: Ident::new(
+                        local_name_for_src(&src),
+                        DUMMY_SP.apply_mark(Mark::fresh(Mark::root())),
+                    ),
+                    setter: vec![],
+                })
+            }};
+        }
+
+        // Statements of the declaration function, executed before `execute`.
+        let mut before = vec![];
+        let mut execute = Vec::with_capacity(items.len());
+        let emit_use_strict = self.config.strict_mode || has_use_strict(&items);
+        let skip = if has_use_strict(&items) { 1 } else { 0 };
+
+        for item in items.into_iter().skip(skip) {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    self.hoist_decl(decl, &mut before, &mut execute);
+                    continue;
+                }
+                ModuleItem::Stmt(stmt) => {
+                    execute.push(stmt.fold_with(self));
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let dep = dep!(import.src.value);
+
+                    for s in import.specifiers {
+                        let (local, value) = match s {
+                            ImportSpecifier::Specific(s) => {
+                                let imported = s.imported.unwrap_or_else(|| s.local.clone());
+                                (s.local, dep.ident.clone().member(imported))
+                            }
+                            ImportSpecifier::Default(s) => {
+                                (s.local, dep.ident.clone().member(quote_ident!("default")))
+                            }
+                            ImportSpecifier::Namespace(s) => {
+                                (s.local, Expr::Ident(dep.ident.clone()))
+                            }
+                        };
+
+                        self.hoisted.insert(id(&local));
+                        dep.setter.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Pat(box Pat::Ident(local)),
+                                op: op!("="),
+                                right: box value,
+                            }
+                            .into_stmt(),
+                        );
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    self.hoist_decl(decl, &mut before, &mut execute)
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => match decl {
+                    DefaultDecl::Fn(FnExpr { ident, function }) => {
+                        let ident = ident.unwrap_or_else(|| private_ident!("_default"));
+
+                        before.push(Stmt::Decl(Decl::Fn(
+                            FnDecl {
+                                ident: ident.clone(),
+                                function,
+                                declare: false,
+                            }
+                            .fold_with(self),
+                        )));
+                        before.push(self.export_call(js_word!("default"), box ident.into()));
+                    }
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }) => self.hoist_decl(
+                        Decl::Class(ClassDecl {
+                            ident,
+                            class,
+                            declare: false,
+                        }),
+                        &mut before,
+                        &mut execute,
+                    ),
+                    DefaultDecl::Class(class) => {
+                        let class = class.fold_with(self);
+                        execute.push(self.export_call(js_word!("default"), box Expr::Class(class)));
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => {}
+                },
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                    let expr = expr.fold_with(self);
+                    execute.push(self.export_call(js_word!("default"), expr));
+                }
+
+                // export { foo } from 'foo';
+                ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    specifiers,
+                    ..
+                }) => {
+                    let dep = dep!(src.value);
+
+                    for s in specifiers {
+                        let (name, value) = match s {
+                            ExportSpecifier::Named(NamedExportSpecifier {
+                                orig, exported, ..
+                            }) => (
+                                exported.as_ref().unwrap_or(&orig).sym.clone(),
+                                box dep.ident.clone().member(orig),
+                            ),
+                            ExportSpecifier::Default(DefaultExportSpecifier { exported }) => (
+                                exported.sym,
+                                box dep.ident.clone().member(quote_ident!("default")),
+                            ),
+                            ExportSpecifier::Namespace(NamespaceExportSpecifier {
+                                name, ..
+                            }) => (name.sym, box Expr::Ident(dep.ident.clone())),
+                        };
+                        dep.setter.push(self.export_call(name, value));
+                    }
+                }
+
+                // export { foo };
+                ModuleDecl::ExportNamed(NamedExport { specifiers, .. }) => {
+                    for s in specifiers {
+                        let (orig, exported) = match s {
+                            ExportSpecifier::Named(NamedExportSpecifier {
+                                orig, exported, ..
+                            }) => (orig, exported),
+                            _ => continue,
+                        };
+                        let name = exported.unwrap_or_else(|| orig.clone()).sym;
+
+                        match imports.get(&id(&orig)) {
+                            // Re-exported imports are updated by the setter.
+                            Some((src, imported)) => {
+                                let dep = dep!(src.clone());
+                                let value = match imported {
+                                    Imported::Namespace => box Expr::Ident(dep.ident.clone()),
+                                    Imported::Named(imported) => box dep
+                                        .ident
+                                        .clone()
+                                        .member(Ident::new(imported.clone(), DUMMY_SP)),
+                                };
+                                dep.setter.push(self.export_call(name, value));
+                            }
+                            // Local bindings are exported when they are declared or assigned.
+                            None => {}
+                        }
+                    }
+                }
+
+                ModuleDecl::ExportAll(export) => {
+                    let dep = dep!(export.src.value);
+                    let stmts = self.export_all(dep.ident.clone(), &own_names);
+                    dep.setter.extend(stmts);
+                }
+
+                ModuleDecl::TsImportEquals(..)
+                | ModuleDecl::TsExportAssignment(..)
+                | ModuleDecl::TsNamespaceExport(..) => {}
+            }
+        }
+
+        // ====================
+        //  Emit
+        // ====================
+
+        let mut stmts = Vec::with_capacity(before.len() + 3);
+        if emit_use_strict {
+            stmts.push(use_strict());
+        }
+
+        let hoisted = mem::replace(&mut self.hoisted, Default::default())
+            .into_iter()
+            .map(|(sym, ctxt)| Ident::new(sym, DUMMY_SP.with_ctxt(ctxt)))
+            .chain(self.tmp.take());
+        let decls: Vec<_> = hoisted
+            .map(|i| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(i),
+                init: None,
+                definite: false,
+            })
+            .collect();
+        if !decls.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls,
+                declare: false,
+            })));
+        }
+
+        stmts.extend(before);
+
+        let mut dep_names = ArrayLit {
+            span: DUMMY_SP,
+            elems: Vec::with_capacity(deps.len()),
+        };
+        let mut setters = ArrayLit {
+            span: DUMMY_SP,
+            elems: Vec::with_capacity(deps.len()),
+        };
+        for (src, dep) in deps {
+            dep_names
+                .elems
+                .push(Some(Lit::Str(quote_str!(src)).as_arg()));
+
+            let params = if dep.setter.is_empty() {
+                vec![]
+            } else {
+                vec![Pat::Ident(dep.ident).into()]
+            };
+            setters
+                .elems
+                .push(Some(fn_expr(params, dep.setter).as_arg()));
+        }
+
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("setters")),
+                        value: box Expr::Array(setters),
+                    })),
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("execute")),
+                        value: box Expr::Fn(fn_expr(vec![], execute)),
+                    })),
+                ],
+            })),
+        }));
+
+        let declare = fn_expr(
+            vec![
+                Pat::Ident(self.export.clone()).into(),
+                Pat::Ident(self.context.clone()).into(),
+            ],
+            stmts,
+        );
+
+        Module {
+            body: vec![CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!("System")
+                    .member(quote_ident!("register"))
+                    .as_callee(),
+                args: self
+                    .config
+                    .module_id
+                    .clone()
+                    .map(|s| quote_str!(s).as_arg())
+                    .into_iter()
+                    .chain(iter::once(dep_names.as_arg()))
+                    .chain(iter::once(declare.as_arg()))
+                    .collect(),
+                type_args: Default::default(),
+            }
+            .into_stmt()
+            .into()],
+            ..module
+        }
+    }
+}
+
+impl SystemJs {
+    /// Creates `_export('name', value)`.
+    fn export_call(&self, name: JsWord, value: Box<Expr>) -> Stmt {
+        self.export_expr(name, value).into_stmt()
+    }
+
+    fn export_expr(&self, name: JsWord, value: Box<Expr>) -> Expr {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: self.export.clone().as_callee(),
+            args: vec![
+                Lit::Str(quote_str!(name)).as_arg(),
+                ExprOrSpread {
+                    spread: None,
+                    expr: value,
+                },
+            ],
+            type_args: Default::default(),
+        })
+    }
+
+    fn tmp(&mut self) -> Ident {
+        self.tmp
+            .get_or_insert_with(|| private_ident!("_tmp"))
+            .clone()
+    }
+
+    /// Names `ident` is exported as.
+    fn exported_names(&self, ident: &Ident) -> &[JsWord] {
+        self.exported.get(&id(ident)).map(|v| &**v).unwrap_or(&[])
+    }
+
+    /// Creates
+    ///
+    /// ```js
+    /// (_tmp = expr, _export('a', a), _export('b', b), _tmp)
+    /// ```
+    ///
+    /// for each exported binding in `idents`.
+    fn export_after(&mut self, expr: Box<Expr>, idents: Vec<Ident>) -> Expr {
+        let exports: Vec<_> = idents
+            .into_iter()
+            .flat_map(|i| {
+                self.exported_names(&i)
+                    .iter()
+                    .map(|name| box self.export_expr(name.clone(), box i.clone().into()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if exports.is_empty() {
+            return *expr;
+        }
+
+        let tmp = self.tmp();
+        Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: iter::once(box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box Pat::Ident(tmp.clone())),
+                op: op!("="),
+                right: expr,
+            }))
+            .chain(exports)
+            .chain(iter::once(box Expr::Ident(tmp)))
+            .collect(),
+        })
+    }
+
+    /// Moves a top-level declaration out of `execute`.
+    ///
+    /// Functions are declared in the declaration function, and classes and
+    /// variables are converted to assignments to hoisted variables.
+    fn hoist_decl(&mut self, decl: Decl, before: &mut Vec<Stmt>, execute: &mut Vec<Stmt>) {
+        match decl {
+            Decl::Fn(f) => {
+                let names = self.exported_names(&f.ident).to_vec();
+                let ident = f.ident.clone();
+
+                before.push(Stmt::Decl(Decl::Fn(f.fold_with(self))));
+                for name in names {
+                    before.push(self.export_call(name, box ident.clone().into()));
+                }
+            }
+            Decl::Class(ClassDecl { ident, class, .. }) => {
+                self.hoisted.insert(id(&ident));
+
+                let expr = Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                    op: op!("="),
+                    right: box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                });
+                execute.push(expr.fold_with(self).into_stmt());
+            }
+            Decl::Var(var) => {
+                if let Some(expr) = self.hoist_var(var) {
+                    execute.push(expr.fold_with(self).into_stmt());
+                }
+            }
+            Decl::TsInterface(..)
+            | Decl::TsTypeAlias(..)
+            | Decl::TsEnum(..)
+            | Decl::TsModule(..) => {}
+        }
+    }
+
+    /// Hoists variables declared by `var` and returns the assignments of
+    /// their initializers.
+    fn hoist_var(&mut self, var: VarDecl) -> Option<Expr> {
+        let mut exprs = vec![];
+
+        for decl in var.decls {
+            let mut found: Vec<Ident> = vec![];
+            decl.name
+                .visit_with(&mut DestructuringFinder { found: &mut found });
+            self.hoisted.extend(found.iter().map(id));
+
+            if let Some(init) = decl.init {
+                exprs.push(box Expr::Assign(AssignExpr {
+                    span: decl.span,
+                    left: PatOrExpr::Pat(box decl.name),
+                    op: op!("="),
+                    right: init,
+                }));
+            }
+        }
+
+        match exprs.len() {
+            0 => None,
+            1 => exprs.pop().map(|e| *e),
+            _ => Some(Expr::Seq(SeqExpr {
+                span: var.span,
+                exprs,
+            })),
+        }
+    }
+
+    /// Hoists `var` of `for (var a in b)` and `for (var a of b)`.
+    fn hoist_for_head(&mut self, left: VarDeclOrPat) -> VarDeclOrPat {
+        match left {
+            VarDeclOrPat::VarDecl(mut var)
+                if self.in_top_level && var.kind == VarDeclKind::Var && var.decls.len() == 1 =>
+            {
+                let decl = var.decls.remove(0);
+                let mut found: Vec<Ident> = vec![];
+                decl.name
+                    .visit_with(&mut DestructuringFinder { found: &mut found });
+                self.hoisted.extend(found.iter().map(id));
+
+                VarDeclOrPat::Pat(decl.name)
+            }
+            _ => left,
+        }
+    }
+
+    /// Exports bindings assigned by the head of a `for in` / `for of` loop at
+    /// the start of each iteration.
+    fn export_for_head(&self, left: &VarDeclOrPat, body: Box<Stmt>) -> Box<Stmt> {
+        let pat = match left {
+            VarDeclOrPat::Pat(pat) => pat,
+            VarDeclOrPat::VarDecl(..) => return body,
+        };
+
+        let mut found: Vec<Ident> = vec![];
+        pat.visit_with(&mut DestructuringFinder { found: &mut found });
+
+        let mut stmts = vec![];
+        for i in found {
+            for name in self.exported_names(&i) {
+                stmts.push(self.export_call(name.clone(), box i.clone().into()));
+            }
+        }
+        if stmts.is_empty() {
+            return body;
+        }
+        stmts.push(*body);
+
+        box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        })
+    }
+
+    /// Creates a setter for `export * from 'foo'`.
+    ///
+    /// ```js
+    /// var _exportObj = {};
+    /// for (var _key in _foo) {
+    ///     if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _foo[_key];
+    /// }
+    /// _export(_exportObj);
+    /// ```
+    fn export_all(&self, dep: Ident, own_names: &[JsWord]) -> Vec<Stmt> {
+        let obj = private_ident!("_exportObj");
+        let key = private_ident!("_key");
+
+        let test = iter::once(js_word!("default"))
+            .chain(iter::once("__esModule".into()))
+            .chain(own_names.iter().cloned())
+            .map(|name| box key.clone().make_bin(op!("!=="), Lit::Str(quote_str!(name))))
+            .fold(None, |test: Option<Box<Expr>>, right| match test {
+                Some(left) => Some(box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!("&&"),
+                    left,
+                    right,
+                })),
+                None => Some(right),
+            })
+            .unwrap();
+
+        vec![
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(obj.clone()),
+                    init: Some(box Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props: vec![],
+                    })),
+                    definite: false,
+                }],
+                declare: false,
+            })),
+            Stmt::ForIn(ForInStmt {
+                span: DUMMY_SP,
+                left: VarDeclOrPat::VarDecl(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(key.clone()),
+                        init: None,
+                        definite: false,
+                    }],
+                    declare: false,
+                }),
+                right: box dep.clone().into(),
+                body: box Stmt::Block(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test,
+                        cons: box AssignExpr {
+                            span: DUMMY_SP,
+                            left: PatOrExpr::Expr(box obj.clone().computed_member(key.clone())),
+                            op: op!("="),
+                            right: box dep.computed_member(key),
+                        }
+                        .into_stmt(),
+                        alt: None,
+                    })],
+                }),
+            }),
+            CallExpr {
+                span: DUMMY_SP,
+                callee: self.export.clone().as_callee(),
+                args: vec![obj.as_arg()],
+                type_args: Default::default(),
+            }
+            .into_stmt(),
+        ]
+    }
+}
+
+fn fn_expr(params: Vec<Param>, stmts: Vec<Stmt>) -> FnExpr {
+    FnExpr {
+        ident: None,
+        function: Function {
+            span: DUMMY_SP,
+            is_async: false,
+            is_generator: false,
+            decorators: Default::default(),
+            params,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            type_params: Default::default(),
+            return_type: Default::default(),
+        },
+    }
+}
+
+impl Fold<Stmt> for SystemJs {
+    /// Hoists `var` declarations of the module scope.
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            Stmt::Decl(Decl::Var(var)) if self.in_top_level && var.kind == VarDeclKind::Var => {
+                let span = var.span;
+                match self.hoist_var(var) {
+                    Some(expr) => expr.fold_with(self).into_stmt(),
+                    None => Stmt::Empty(EmptyStmt { span }),
+                }
+            }
+            _ => stmt.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ForStmt> for SystemJs {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let init = match s.init {
+            Some(VarDeclOrExpr::VarDecl(var))
+                if self.in_top_level && var.kind == VarDeclKind::Var =>
+            {
+                self.hoist_var(var).map(|e| VarDeclOrExpr::Expr(box e))
+            }
+            init => init,
+        };
+
+        ForStmt { init, ..s }.fold_children(self)
+    }
+}
+
+impl Fold<ForInStmt> for SystemJs {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        let left = self.hoist_for_head(s.left);
+        let s = ForInStmt { left, ..s }.fold_children(self);
+
+        ForInStmt {
+            body: self.export_for_head(&s.left, s.body),
+            ..s
+        }
+    }
+}
+
+impl Fold<ForOfStmt> for SystemJs {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        let left = self.hoist_for_head(s.left);
+        let s = ForOfStmt { left, ..s }.fold_children(self);
+
+        ForOfStmt {
+            body: self.export_for_head(&s.left, s.body),
+            ..s
+        }
+    }
+}
+
+impl Fold<Expr> for SystemJs {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            Expr::Assign(assign) => {
+                let ident = match assign.left {
+                    PatOrExpr::Pat(box Pat::Ident(ref i))
+                    | PatOrExpr::Expr(box Expr::Ident(ref i)) => Some(i.clone()),
+                    _ => None,
+                };
+
+                match ident {
+                    // _export('a', a = 1)
+                    Some(i) => self
+                        .exported_names(&i)
+                        .iter()
+                        .fold(Expr::Assign(assign), |expr, name| {
+                            self.export_expr(name.clone(), box expr)
+                        }),
+                    None => {
+                        let mut found: Vec<Ident> = vec![];
+                        if let PatOrExpr::Pat(ref pat) = assign.left {
+                            pat.visit_with(&mut DestructuringFinder { found: &mut found });
+                        }
+                        self.export_after(box Expr::Assign(assign), found)
+                    }
+                }
+            }
+
+            Expr::Update(UpdateExpr {
+                prefix,
+                arg: box Expr::Ident(i),
+                span,
+                op,
+            }) => {
+                let update = box Expr::Update(UpdateExpr {
+                    span,
+                    op,
+                    prefix,
+                    arg: box Expr::Ident(i.clone()),
+                });
+
+                if prefix {
+                    // _export('a', ++a)
+                    self.exported_names(&i).iter().fold(*update, |expr, name| {
+                        self.export_expr(name.clone(), box expr)
+                    })
+                } else {
+                    // (_tmp = a++, _export('a', a), _tmp)
+                    self.export_after(update, vec![i])
+                }
+            }
+
+            // import.meta
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("import"),
+                        ..
+                    },
+                prop:
+                    Ident {
+                        sym: js_word!("meta"),
+                        ..
+                    },
+            }) => self.context.clone().member(quote_ident!("meta")),
+
+            // import('foo')
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                type_args,
+            }) => Expr::Call(CallExpr {
+                span,
+                callee: self
+                    .context
+                    .clone()
+                    .member(quote_ident!("import"))
+                    .as_callee(),
+                args,
+                type_args,
+            }),
+
+            _ => expr,
+        }
+    }
+}
+
+mark_as_nested!(SystemJs);
+mark_as_nested!(ArrowExpr, SystemJs);
@@ -0,0 +1,330 @@
+use super::{system_js, Config};
+use crate::resolver;
+use ast::Module;
+use swc_common::{chain, Fold};
+use swc_ecma_parser::{EsConfig, Syntax};
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold<Module> {
+    chain!(resolver(), system_js(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    imports,
+    "import foo from 'foo';
+import { bar, baz as qux } from 'bar';
+import * as ns from 'ns';
+import 'side-effect';
+
+foo(bar, qux, ns);",
+    "System.register(['foo', 'bar', 'ns', 'side-effect'], function (_export, _context) {
+    'use strict';
+    var foo, bar, qux, ns;
+    return {
+        setters: [function (_foo) {
+            foo = _foo.default;
+        }, function (_bar) {
+            bar = _bar.bar;
+            qux = _bar.baz;
+        }, function (_ns) {
+            ns = _ns;
+        }, function () {}],
+        execute: function () {
+            foo(bar, qux, ns);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        module_id: Some("app".into()),
+        strict_mode: false,
+    }),
+    module_id,
+    "foo();",
+    "System.register('app', [], function (_export, _context) {
+    return {
+        setters: [],
+        execute: function () {
+            foo();
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_decls,
+    "export var a = 1, b;
+export let c = 2;
+export function d() {}
+export class E {}",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var a, b, c, E;
+    function d() {}
+    _export('d', d);
+    return {
+        setters: [],
+        execute: function () {
+            _export('a', a = 1);
+            _export('c', c = 2);
+            _export('E', E = class E {});
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_bindings,
+    "export let count = 0;
+export function increment() {
+    count += 1;
+    count++;
+    return ++count;
+}
+function reset() {
+    let count = 0;
+    count = 1;
+}",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var count, _tmp;
+    function increment() {
+        _export('count', count += 1);
+        _tmp = count++, _export('count', count), _tmp;
+        return _export('count', ++count);
+    }
+    _export('increment', increment);
+    function reset() {
+        let count = 0;
+        count = 1;
+    }
+    return {
+        setters: [],
+        execute: function () {
+            _export('count', count = 0);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_named,
+    "var a = 1;
+function f() {}
+export { a, a as b, f };
+a = 2;",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var a;
+    function f() {}
+    _export('f', f);
+    return {
+        setters: [],
+        execute: function () {
+            _export('b', _export('a', a = 1));
+            _export('b', _export('a', a = 2));
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_destructuring,
+    "export const { a, b: [c] } = obj;",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var a, c, _tmp;
+    return {
+        setters: [],
+        execute: function () {
+            _tmp = { a, b: [c] } = obj, _export('a', a), _export('c', c), _tmp;
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default_expr,
+    "export default 1 + 2;",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    return {
+        setters: [],
+        execute: function () {
+            _export('default', 1 + 2);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default_fn,
+    "export default function () {}",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    function _default() {}
+    _export('default', _default);
+    return {
+        setters: [],
+        execute: function () {}
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default_class,
+    "export default class Foo {}
+Foo = null;",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var Foo;
+    return {
+        setters: [],
+        execute: function () {
+            _export('default', Foo = class Foo {});
+            _export('default', Foo = null);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    re_exports,
+    "import { foo } from 'foo';
+export { foo };
+export { bar, baz as qux } from 'bar';",
+    "System.register(['foo', 'bar'], function (_export, _context) {
+    'use strict';
+    var foo;
+    return {
+        setters: [function (_foo) {
+            foo = _foo.foo;
+            _export('foo', _foo.foo);
+        }, function (_bar) {
+            _export('bar', _bar.bar);
+            _export('qux', _bar.baz);
+        }],
+        execute: function () {}
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_all,
+    "export * from 'foo';
+export const a = 1;",
+    "System.register(['foo'], function (_export, _context) {
+    'use strict';
+    var a;
+    return {
+        setters: [function (_foo) {
+            var _exportObj = {};
+            for (var _key in _foo) {
+                if (_key !== 'default' && _key !== '__esModule' && _key !== 'a')
+                    _exportObj[_key] = _foo[_key];
+            }
+            _export(_exportObj);
+        }],
+        execute: function () {
+            _export('a', a = 1);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    hoist_top_level_vars,
+    "for (var i = 0; i < 2; i++) {
+    if (i) {
+        var x = i;
+    }
+}
+for (var key in obj) {}
+function f() {
+    var y = 1;
+}
+const g = () => {
+    var z = 1;
+};
+export { i, x, key };",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var i, x, key, g, _tmp;
+    function f() {
+        var y = 1;
+    }
+    return {
+        setters: [],
+        execute: function () {
+            for (_export('i', i = 0); i < 2; _tmp = i++, _export('i', i), _tmp) {
+                if (i) {
+                    _export('x', x = i);
+                }
+            }
+            for (key in obj) {
+                _export('key', key);
+                {}
+            }
+            g = () => {
+                var z = 1;
+            };
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    context,
+    "const url = import.meta.url;
+export async function load() {
+    return import('./foo');
+}",
+    "System.register([], function (_export, _context) {
+    'use strict';
+    var url;
+    async function load() {
+        return _context.import('./foo');
+    }
+    _export('load', load);
+    return {
+        setters: [],
+        execute: function () {
+            url = _context.meta.url;
+        }
+    };
+});"
+);
@@ -2,7 +2,8 @@ use self::config::BuiltConfig;
 pub use self::config::Config;
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, make_require_call, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_amd_dynamic_import, make_descriptor, make_require_call, use_strict,
+    Exports, ModulePass, Scope,
 };
 use crate::{
     pass::Pass,
@@ -12,7 +13,7 @@ use ast::*;
 use fxhash::FxHashSet;
 use std::sync::Arc;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, Mark, SourceMap, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, SourceMap, Span, VisitWith, DUMMY_SP};
 
 mod config;
 #[cfg(test)]
@@ -531,8 +532,8 @@ impl Fold<Module> for Umd {
             is_generator: false,
             decorators: Default::default(),
             params: vec![
-                Pat::Ident(quote_ident!("global")),
-                Pat::Ident(quote_ident!("factory")),
+                Pat::Ident(quote_ident!("global")).into(),
+                Pat::Ident(quote_ident!("factory")).into(),
             ],
             body: Some(BlockStmt {
                 span: DUMMY_SP,
@@ -654,7 +655,7 @@ impl Fold<Module> for Umd {
                 is_async: false,
                 is_generator: false,
                 decorators: Default::default(),
-                params: factory_params,
+                params: factory_params.into_iter().map(Param::from).collect(),
                 body: Some(BlockStmt {
                     span: DUMMY_SP,
                     stmts,
@@ -736,5 +737,9 @@ impl ModulePass for Umd {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        make_amd_dynamic_import(span, args, self.config.config.no_interop)
+    }
 }
 mark_as_nested!(Umd);
@@ -1859,3 +1859,93 @@ export const foo = function () {
     _exports.foo = foo;
 });"
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    }),
+    |tester| tr(
+        tester,
+        Config {
+            ..Default::default()
+        }
+    ),
+    dynamic_import,
+    r#"
+import * as foo from "foo";
+
+import("./bar").then(bar => bar(foo));
+"#,
+    r#"
+(function (global, factory) {
+  if (typeof define === "function" && define.amd) {
+    define(["foo"], factory);
+  } else if (typeof exports !== "undefined") {
+    factory(require("foo"));
+  } else {
+    var mod = {
+      exports: {}
+    };
+    factory(global.foo);
+    global.input = mod.exports;
+  }
+})(this, function (foo) {
+  "use strict";
+
+  foo = _interopRequireWildcard(foo);
+  new Promise(function (_resolve, _reject) {
+    require(["./bar"], function (m) {
+      _resolve(_interopRequireWildcard(m));
+    }, _reject);
+  }).then(bar => bar(foo));
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    }),
+    |tester| tr(
+        tester,
+        Config {
+            config: util::Config {
+                lazy: util::Lazy::Bool(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    ),
+    dynamic_import_lazy,
+    r#"
+import * as foo from "foo";
+
+import("bar").then(bar => bar(foo));
+"#,
+    r#"
+(function (global, factory) {
+  if (typeof define === "function" && define.amd) {
+    define(["foo"], factory);
+  } else if (typeof exports !== "undefined") {
+    factory(require("foo"));
+  } else {
+    var mod = {
+      exports: {}
+    };
+    factory(global.foo);
+    global.input = mod.exports;
+  }
+})(this, function (foo) {
+  "use strict";
+
+  foo = _interopRequireWildcard(foo);
+  new Promise(function (_resolve, _reject) {
+    require(["bar"], function (m) {
+      _resolve(_interopRequireWildcard(m));
+    }, _reject);
+  }).then(bar => bar(foo));
+});
+"#
+);
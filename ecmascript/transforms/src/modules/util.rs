@@ -13,6 +13,12 @@ pub(super) trait ModulePass {
     fn config(&self) -> &Config;
     fn scope(&self) -> &Scope;
     fn scope_mut(&mut self) -> &mut Scope;
+
+    /// Lowers `import(src)`.
+    ///
+    /// [Config::lazy] does not affect this, as the module is required only
+    /// when the returned promise is resolved.
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,7 +45,7 @@ impl Default for Config {
     }
 }
 
-const fn default_strict_mode() -> bool {
+pub(super) const fn default_strict_mode() -> bool {
     true
 }
 
@@ -146,7 +152,7 @@ impl Scope {
             is_async: false,
             is_generator: false,
             decorators: Default::default(),
-            params: vec![Pat::Ident(key_ident.clone())],
+            params: vec![Pat::Ident(key_ident.clone()).into()],
             body: Some(BlockStmt {
                 span: DUMMY_SP,
                 stmts: iter::once(Stmt::If(IfStmt {
@@ -637,6 +643,22 @@ impl Scope {
                     }
                 }
             }
+
+            // import('foo')
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                ..
+            }) => {
+                let args = args.fold_with(folder);
+                folder.make_dynamic_import(span, args)
+            }
+
             _ => expr.fold_children(folder),
         }
     }
@@ -657,6 +679,212 @@ pub(super) fn make_require_call(src: JsWord) -> Expr {
     })
 }
 
+/// Creates
+///
+/// ```js
+/// Promise.resolve().then(function () {
+///     return _interopRequireWildcard(require('foo'));
+/// });
+/// ```
+///
+/// If the source is not a string literal, it's evaluated before the
+/// promise is created.
+pub(super) fn make_cjs_dynamic_import(
+    span: Span,
+    args: Vec<ExprOrSpread>,
+    no_interop: bool,
+) -> Expr {
+    let (src, resolved) = match args.into_iter().next() {
+        Some(ExprOrSpread {
+            spread: None,
+            expr: box Expr::Lit(Lit::Str(src)),
+        }) => (None, box Expr::Lit(Lit::Str(src))),
+        arg => {
+            let s = private_ident!("_s");
+            let src = arg
+                .map(|arg| arg.expr)
+                .unwrap_or_else(|| undefined(DUMMY_SP));
+            let src = CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!("String").as_callee(),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: src,
+                }],
+                type_args: Default::default(),
+            };
+
+            (Some((src, s.clone())), box Expr::Ident(s))
+        }
+    };
+
+    let mut module = box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("require").as_callee(),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: resolved,
+        }],
+        type_args: Default::default(),
+    });
+    if !no_interop {
+        module = box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(interop_require_wildcard, "interopRequireWildcard"),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: module,
+            }],
+            type_args: Default::default(),
+        });
+    }
+
+    let (resolve_args, params) = match src {
+        Some((src, s)) => (vec![src.as_arg()], vec![Pat::Ident(s).into()]),
+        None => (vec![], vec![]),
+    };
+
+    Expr::Call(CallExpr {
+        span,
+        callee: CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("Promise")
+                .member(quote_ident!("resolve"))
+                .as_callee(),
+            args: resolve_args,
+            type_args: Default::default(),
+        }
+        .member(quote_ident!("then"))
+        .as_callee(),
+        args: vec![FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                is_async: false,
+                is_generator: false,
+                decorators: Default::default(),
+                params,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(module),
+                    })],
+                }),
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        }
+        .as_arg()],
+        type_args: Default::default(),
+    })
+}
+
+/// Creates
+///
+/// ```js
+/// new Promise(function (_resolve, _reject) {
+///     require(['foo'], function (m) {
+///         _resolve(_interopRequireWildcard(m));
+///     }, _reject);
+/// });
+/// ```
+pub(super) fn make_amd_dynamic_import(
+    span: Span,
+    args: Vec<ExprOrSpread>,
+    no_interop: bool,
+) -> Expr {
+    let resolve = private_ident!("_resolve");
+    let reject = private_ident!("_reject");
+
+    let src = args
+        .into_iter()
+        .next()
+        .map(|arg| arg.expr)
+        .unwrap_or_else(|| undefined(DUMMY_SP));
+
+    let callback = if no_interop {
+        box Expr::Ident(resolve.clone())
+    } else {
+        let m = private_ident!("m");
+
+        box Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                is_async: false,
+                is_generator: false,
+                decorators: Default::default(),
+                params: vec![Pat::Ident(m.clone()).into()],
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![CallExpr {
+                        span: DUMMY_SP,
+                        callee: resolve.clone().as_callee(),
+                        args: vec![CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(interop_require_wildcard, "interopRequireWildcard"),
+                            args: vec![m.as_arg()],
+                            type_args: Default::default(),
+                        }
+                        .as_arg()],
+                        type_args: Default::default(),
+                    }
+                    .into_stmt()],
+                }),
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        })
+    };
+
+    Expr::New(NewExpr {
+        span,
+        callee: box Expr::Ident(quote_ident!("Promise")),
+        args: Some(vec![FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                is_async: false,
+                is_generator: false,
+                decorators: Default::default(),
+                params: vec![
+                    Pat::Ident(resolve).into(),
+                    Pat::Ident(reject.clone()).into(),
+                ],
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![CallExpr {
+                        span: DUMMY_SP,
+                        callee: quote_ident!("require").as_callee(),
+                        args: vec![
+                            ArrayLit {
+                                span: DUMMY_SP,
+                                elems: vec![Some(ExprOrSpread {
+                                    spread: None,
+                                    expr: src,
+                                })],
+                            }
+                            .as_arg(),
+                            ExprOrSpread {
+                                spread: None,
+                                expr: callback,
+                            },
+                            reject.as_arg(),
+                        ],
+                        type_args: Default::default(),
+                    }
+                    .into_stmt()],
+                }),
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        }
+        .as_arg()]),
+        type_args: Default::default(),
+    })
+}
+
 pub(super) fn local_name_for_src(src: &JsWord) -> JsWord {
     if !src.contains('/') {
         return format!("_{}", src.to_camel_case()).into();
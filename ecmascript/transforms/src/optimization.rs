@@ -1,11 +1,17 @@
 pub use self::{
+    compressor::compressor,
     inline_globals::InlineGlobals,
     inline_vars::inline_vars,
     json_parse::JsonParse,
+    mangler::mangler,
     simplify::{expr_simplifier, simplifier},
+    tree_shaker::tree_shaker,
 };
 
+pub mod compressor;
 mod inline_globals;
 pub mod inline_vars;
 mod json_parse;
+pub mod mangler;
 mod simplify;
+pub mod tree_shaker;
@@ -0,0 +1,172 @@
+use super::{expr_simplifier, inline_vars, simplify::dce};
+use crate::{
+    pass::Pass,
+    util::{preserve_effects, undefined},
+};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use swc_common::{Fold, FoldWith, SyntaxContext};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes configured calls, and then applies the passes of [simplifier]
+/// `passes` times.
+///
+/// [simplifier]: ../fn.simplifier.html
+pub fn compressor(config: Config) -> impl 'static + Pass {
+    Compressor { config }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Drop calls to methods of `console`.
+    ///
+    /// A `console` declared in the program is not affected, so `resolver`
+    /// should be applied first.
+    #[serde(default)]
+    pub drop_console: bool,
+
+    /// Calls to these functions are removed if their result is not used.
+    ///
+    /// Names are compared with the callee, like `Math.floor`.
+    #[serde(default)]
+    pub pure_funcs: Vec<String>,
+
+    /// Number of times the optimizers are applied.
+    #[serde(default = "default_passes")]
+    pub passes: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            drop_console: false,
+            pure_funcs: vec![],
+            passes: default_passes(),
+        }
+    }
+}
+
+const fn default_passes() -> usize {
+    1
+}
+
+struct Compressor {
+    config: Config,
+}
+
+macro_rules! compress {
+    ($T:tt) => {
+        impl Fold<$T> for Compressor {
+            fn fold(&mut self, node: $T) -> $T {
+                let mut node = node.fold_with(&mut DropCalls {
+                    config: &self.config,
+                });
+
+                for _ in 0..self.config.passes {
+                    node = node
+                        .fold_with(&mut expr_simplifier())
+                        .fold_with(&mut inline_vars(Default::default()))
+                        .fold_with(&mut dce());
+                }
+
+                node
+            }
+        }
+    };
+}
+
+compress!(Module);
+compress!(Script);
+
+struct DropCalls<'a> {
+    config: &'a Config,
+}
+
+impl DropCalls<'_> {
+    fn is_console(&self, callee: &ExprOrSuper) -> bool {
+        if !self.config.drop_console {
+            return false;
+        }
+
+        match *callee {
+            ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                ..
+            })) => obj.sym == *"console" && obj.span.ctxt() == SyntaxContext::empty(),
+            _ => false,
+        }
+    }
+
+    fn is_pure(&self, callee: &ExprOrSuper) -> bool {
+        if self.config.pure_funcs.is_empty() {
+            return false;
+        }
+
+        match *callee {
+            ExprOrSuper::Expr(ref callee) => match name_of(callee) {
+                Some(name) => self.config.pure_funcs.contains(&name),
+                None => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Returns `a.b.c` for `a.b.c`.
+fn name_of(e: &Expr) -> Option<String> {
+    match *e {
+        Expr::Ident(ref i) => Some(i.sym.to_string()),
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            prop: box Expr::Ident(ref prop),
+            computed: false,
+            ..
+        }) => name_of(obj).map(|obj| format!("{}.{}", obj, prop.sym)),
+        _ => None,
+    }
+}
+
+impl Fold<Expr> for DropCalls<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Call(CallExpr {
+                span, ref callee, ..
+            }) if self.is_console(callee) => *undefined(span),
+            _ => e,
+        }
+    }
+}
+
+impl Fold<ExprStmt> for DropCalls<'_> {
+    fn fold(&mut self, s: ExprStmt) -> ExprStmt {
+        let s = s.fold_children(self);
+
+        match *s.expr {
+            Expr::Call(CallExpr {
+                ref callee,
+                ref args,
+                ..
+            }) if self.is_pure(callee) && args.iter().all(|arg| arg.spread.is_none()) => {}
+            _ => return s,
+        }
+
+        let args = match *s.expr {
+            Expr::Call(CallExpr { args, .. }) => args,
+            _ => unreachable!(),
+        };
+
+        ExprStmt {
+            span: s.span,
+            expr: box preserve_effects(
+                s.span,
+                *undefined(s.span),
+                args.into_iter().map(|arg| arg.expr),
+            ),
+        }
+    }
+}
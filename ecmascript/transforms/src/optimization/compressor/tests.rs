@@ -0,0 +1,77 @@
+use super::*;
+use crate::resolver;
+use swc_common::chain;
+use swc_ecma_parser::Syntax;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+test!(
+    syntax(),
+    |_| compressor(Config {
+        drop_console: true,
+        ..Default::default()
+    }),
+    drop_console,
+    r#"
+console.log(foo());
+bar();
+"#,
+    r#"
+bar();
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(
+        resolver(),
+        compressor(Config {
+            drop_console: true,
+            ..Default::default()
+        })
+    ),
+    drop_console_shadowed,
+    r#"
+export function f(console) {
+    console.log(foo());
+}
+console.log(bar());
+"#,
+    r#"
+export function f(console) {
+    console.log(foo());
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| compressor(Default::default()),
+    keep_console,
+    r#"
+console.log(foo());
+"#,
+    r#"
+console.log(foo());
+"#
+);
+
+test!(
+    syntax(),
+    |_| compressor(Config {
+        pure_funcs: vec!["Math.floor".into(), "debug".into()],
+        ..Default::default()
+    }),
+    pure_funcs,
+    r#"
+Math.floor(a());
+debug(1, 2);
+x = Math.floor(b);
+"#,
+    r#"
+a();
+x = Math.floor(b);
+"#
+);
@@ -0,0 +1,187 @@
+use crate::util::{id, DestructuringFinder, Id};
+use ast::*;
+use fxhash::FxHashSet;
+use swc_common::{Visit, VisitWith};
+
+/// Facts about variables which are collected before the analysis.
+///
+/// These make the pass safe for code where a variable is declared and
+/// assigned in unusual order, like the output of the compat passes.
+#[derive(Debug, Default)]
+pub(super) struct Facts {
+    /// Variables which are assigned, updated or redeclared anywhere. Values
+    /// depending on these are not inlined.
+    pub mutated: FxHashSet<Id>,
+    /// Variables referenced (textually) before their declaration.
+    pub used_before_decl: FxHashSet<Id>,
+}
+
+pub(super) fn analyze<T>(node: &T) -> Facts
+where
+    T: VisitWith<FactFinder>,
+{
+    let mut v = FactFinder {
+        facts: Default::default(),
+        declared: Default::default(),
+        used: Default::default(),
+    };
+    node.visit_with(&mut v);
+
+    v.facts
+}
+
+/// Returns true if `e` references any of `ids`.
+pub(super) fn refers_to_any(e: &Expr, ids: &FxHashSet<Id>) -> bool {
+    let mut v = RefFinder { ids, found: false };
+    e.visit_with(&mut v);
+
+    v.found
+}
+
+pub(super) struct FactFinder {
+    facts: Facts,
+    declared: FxHashSet<Id>,
+    used: FxHashSet<Id>,
+}
+
+impl FactFinder {
+    fn mutate_pat<T>(&mut self, node: &T)
+    where
+        T: for<'any> VisitWith<DestructuringFinder<'any, Id>>,
+    {
+        let mut found = vec![];
+        node.visit_with(&mut DestructuringFinder { found: &mut found });
+
+        self.facts.mutated.extend(found);
+    }
+
+    fn mutate_expr(&mut self, e: &Expr) {
+        if let Some(i) = root_ident(e) {
+            self.facts.mutated.insert(id(i));
+        }
+    }
+}
+
+/// Returns `a` for `a.b.c`.
+fn root_ident(e: &Expr) -> Option<&Ident> {
+    match *e {
+        Expr::Ident(ref i) => Some(i),
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ..
+        }) => root_ident(obj),
+        Expr::Paren(ParenExpr { ref expr, .. }) => root_ident(expr),
+        _ => None,
+    }
+}
+
+impl Visit<Expr> for FactFinder {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Ident(ref i) => {
+                let i = id(i);
+                if !self.declared.contains(&i) {
+                    self.used.insert(i);
+                }
+            }
+            Expr::Member(MemberExpr {
+                ref obj,
+                ref prop,
+                computed,
+                ..
+            }) => {
+                obj.visit_with(self);
+                if computed {
+                    prop.visit_with(self);
+                }
+            }
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<VarDeclarator> for FactFinder {
+    fn visit(&mut self, d: &VarDeclarator) {
+        d.init.visit_with(self);
+
+        let mut found: Vec<Id> = vec![];
+        d.name
+            .visit_with(&mut DestructuringFinder { found: &mut found });
+
+        for i in found {
+            if self.used.contains(&i) {
+                self.facts.used_before_decl.insert(i.clone());
+            }
+            if !self.declared.insert(i.clone()) && d.init.is_some() {
+                self.facts.mutated.insert(i);
+            }
+        }
+    }
+}
+
+impl Visit<AssignExpr> for FactFinder {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.left {
+            PatOrExpr::Pat(ref p) => self.mutate_pat(p),
+            PatOrExpr::Expr(ref e) => self.mutate_expr(e),
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for FactFinder {
+    fn visit(&mut self, e: &UpdateExpr) {
+        self.mutate_expr(&e.arg);
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UnaryExpr> for FactFinder {
+    fn visit(&mut self, e: &UnaryExpr) {
+        if e.op == op!("delete") {
+            self.mutate_expr(&e.arg);
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<ForInStmt> for FactFinder {
+    fn visit(&mut self, s: &ForInStmt) {
+        self.mutate_pat(&s.left);
+
+        s.visit_children(self);
+    }
+}
+
+impl Visit<ForOfStmt> for FactFinder {
+    fn visit(&mut self, s: &ForOfStmt) {
+        self.mutate_pat(&s.left);
+
+        s.visit_children(self);
+    }
+}
+
+struct RefFinder<'a> {
+    ids: &'a FxHashSet<Id>,
+    found: bool,
+}
+
+impl Visit<Ident> for RefFinder<'_> {
+    fn visit(&mut self, i: &Ident) {
+        if self.ids.contains(&id(i)) {
+            self.found = true;
+        }
+    }
+}
+
+impl Visit<MemberExpr> for RefFinder<'_> {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
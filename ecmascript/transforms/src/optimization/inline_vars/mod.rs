@@ -1,4 +1,8 @@
-use self::{preventer::prevent, var::VarInfo};
+use self::{
+    facts::{refers_to_any, FactFinder, Facts},
+    preventer::prevent,
+    var::VarInfo,
+};
 use crate::{
     pass::Pass,
     scope::ScopeKind,
@@ -7,16 +11,18 @@ use crate::{
 use ast::*;
 use either::Either;
 use fxhash::FxHashMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
     cell::{RefCell, RefMut},
     collections::{hash_map::Entry, VecDeque},
+    sync::Arc,
 };
 use swc_atoms::JsWord;
 use swc_common::{
     fold::VisitWith, util::move_map::MoveMap, Fold, FoldWith, SyntaxContext, DUMMY_SP,
 };
 
+mod facts;
 mod hoister;
 mod preventer;
 #[cfg(test)]
@@ -48,8 +54,16 @@ impl Gen {
 
 /// Ported from [`InlineVariables`](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/InlineVariables.java)
 /// of the google closure compiler.
-pub fn inline_vars(_: Config) -> impl 'static + Pass {
-    Inline::root()
+///
+/// Values of variables are inlined only if the variable is assigned once, and
+/// variables referenced before their declaration are left as-is. A value is
+/// inlined into a nested function only if it's a literal assigned to a
+/// `const`.
+pub fn inline_vars(config: Config) -> impl 'static + Pass {
+    Inline {
+        config,
+        ..Inline::root()
+    }
 }
 
 impl Inline<'static> {
@@ -67,12 +81,16 @@ impl Inline<'static> {
             changed: false,
             top_level: true,
             id_gen: Default::default(),
+            config: Default::default(),
+            facts: Default::default(),
         }
     }
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Config {
+    /// Don't inline variables declared in the top-level scope.
     #[serde(default)]
     pub locals_only: bool,
 }
@@ -109,6 +127,8 @@ struct Inline<'a> {
     scope: Scope<'a>,
     top_level: bool,
     id_gen: Gen,
+    config: Config,
+    facts: Arc<Facts>,
 }
 
 impl Inline<'_> {
@@ -135,6 +155,8 @@ impl Inline<'_> {
                         changed: false,
                         top_level: false,
                         id_gen: self.id_gen.clone(),
+                        config: self.config,
+                        facts: self.facts.clone(),
                     };
 
                     let res = op(&mut c);
@@ -170,6 +192,8 @@ impl Inline<'_> {
                     top_level: false,
                     changed: self.changed,
                     id_gen: self.id_gen.clone(),
+                    config: self.config,
+                    facts: self.facts.clone(),
                 };
 
                 let res = op(&mut c);
@@ -197,7 +221,6 @@ impl Scope<'_> {
         match e {
             Expr::Ident(i) => {
                 if let Some(mut v) = self.find(i) {
-                    println!("cnt--; {}: usage", i.sym);
                     v.usage -= 1;
                 }
             }
@@ -286,8 +309,6 @@ impl Scope<'_> {
             Entry::Vacant(..) => self.parent.and_then(|p| p.take_var(i)),
         }?;
 
-        println!("take_var({}): {:?}", i.sym, var);
-
         Some(var)
     }
 }
@@ -446,6 +467,10 @@ impl Fold<VarDecl> for Inline<'_> {
                         // If variable is used, we can't remove it.
                         let var = match decl.name {
                             Pat::Ident(ref i) => {
+                                if self.facts.used_before_decl.contains(&id(i)) {
+                                    return Some(decl);
+                                }
+
                                 let scope = match self.scope.scope_for(i) {
                                     // We can't remove variables in top level
                                     Some(v) if v.is_root() => return Some(decl),
@@ -479,26 +504,12 @@ impl Fold<VarDecl> for Inline<'_> {
                             _ => return Some(decl),
                         };
 
-                        match var {
-                            Either::Left(var) => {
-                                if var.can_be_removed() {
-                                    println!(
-                                        "Scope({}): removing var {:?} as it's not used (including \
-                                         {:?})",
-                                        id, decl.name, var
-                                    );
-                                    return None;
-                                }
-                            }
-                            Either::Right(var) => {
-                                if var.can_be_removed() {
-                                    println!(
-                                        "Scope({}): removing var {:?} as it's not used ({:?})",
-                                        id, decl.name, *var
-                                    );
-                                    return None;
-                                }
-                            }
+                        let can_be_removed = match var {
+                            Either::Left(var) => var.can_be_removed(),
+                            Either::Right(var) => var.can_be_removed(),
+                        };
+                        if can_be_removed {
+                            return None;
                         }
                     }
 
@@ -582,14 +593,13 @@ impl Fold<Expr> for Inline<'_> {
                 match self.phase {
                     Phase::Analysis => {
                         if let Some(mut var) = self.scope.find(&i) {
-                            println!("cnt++; {}: usage", i.sym);
                             var.usage += 1;
 
                             let declared_in_outer_fn = var.scope_id() != self.scope.id
                                 && Some(var.scope_id())
                                     != self.scope.find_fn_scope().map(|scope| scope.id);
                             if declared_in_outer_fn {
-                                var.prevent_inline();
+                                var.mark_as_used_in_nested_fn();
                             }
                         }
                     }
@@ -597,19 +607,35 @@ impl Fold<Expr> for Inline<'_> {
                     Phase::Storage => {}
 
                     Phase::Inlining => {
+                        if self.facts.used_before_decl.contains(&id(&i)) {
+                            return Expr::Ident(i);
+                        }
+                        if self.config.locals_only
+                            && self.scope.scope_for(&i).map_or(false, |s| s.is_root())
+                        {
+                            return Expr::Ident(i);
+                        }
+
                         let e: Option<Expr> = if let Some(mut var) = self.scope.find(&i) {
-                            if var.no_inline() {
+                            if var.no_inline()
+                                || (var.used_in_nested_fn() && !var.is_const_lit())
+                                || var
+                                    .value()
+                                    .map_or(false, |v| refers_to_any(v, &self.facts.mutated))
+                            {
                                 return Expr::Ident(i);
                             }
 
                             if var.assign == 0 && var.usage == 1 {
-                                println!("Taking value of {}", i.sym);
                                 var.take_value()
                             } else if var.assign == 0 {
-                                if let Some(e) = var.value() {
-                                    Some(e.clone())
-                                } else {
-                                    None
+                                // Duplicate only values without identity or side effects.
+                                match var.value() {
+                                    Some(e @ Expr::Lit(..)) if !is_regex(e) => Some(e.clone()),
+                                    Some(e @ Expr::Ident(..)) | Some(e @ Expr::This(..)) => {
+                                        Some(e.clone())
+                                    }
+                                    _ => None,
                                 }
                             } else {
                                 None
@@ -618,8 +644,6 @@ impl Fold<Expr> for Inline<'_> {
                             None
                         };
                         if let Some(e) = e {
-                            println!("Scope({}): inlined '{}'", self.scope.id, i.sym);
-
                             self.changed = true;
                             self.scope.drop_usage(&Expr::Ident(i));
 
@@ -638,6 +662,13 @@ impl Fold<Expr> for Inline<'_> {
     }
 }
 
+fn is_regex(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::Regex(..)) => true,
+        _ => false,
+    }
+}
+
 impl Inline<'_> {
     fn required<T: for<'any> VisitWith<DestructuringFinder<'any, Id>>>(&mut self, node: &T) {
         if self.phase != Phase::Analysis {
@@ -734,9 +765,15 @@ impl Inline<'_> {
 
                 match e {
                     Expr::Ident(ref i) => {
-                        if let Some(expr) = self.scope.find(i).and_then(|var| var.value().cloned())
-                        {
-                            println!("Storage: changed {:?} -> {:?}", e, expr);
+                        // Values of reassigned variables are not final yet.
+                        let expr = self.scope.find(i).and_then(|var| {
+                            if var.assign == 0 && !var.no_inline() {
+                                var.value().cloned()
+                            } else {
+                                None
+                            }
+                        });
+                        if let Some(expr) = expr {
                             // self.scope.drop_usage(&e);
                             changed = true;
                             e = expr;
@@ -788,6 +825,9 @@ impl Inline<'_> {
         if let Some(value) = value {
             v.set_value(value);
         }
+        if kind == Some(VarDeclKind::Const) {
+            v.mark_as_const();
+        }
 
         if kind.is_none() && self.phase == Phase::Analysis {
             v.assign += 1;
@@ -827,20 +867,17 @@ impl Fold<Stmt> for Inline<'_> {
 impl<T: StmtLike> Fold<Vec<T>> for Inline<'_>
 where
     T: FoldWith<Self>,
-    Vec<T>: FoldWith<Self>,
+    Vec<T>: FoldWith<Self> + VisitWith<FactFinder>,
 {
     fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
         let top_level = self.top_level;
         self.top_level = false;
 
-        let stmts = stmts.fold_children(self);
+        if top_level && self.phase == Phase::Analysis {
+            self.facts = Arc::new(facts::analyze(&stmts));
+        }
 
-        println!(
-            "---- ---- ({}) {:?} ---- ----\n{:?}",
-            self.scope.id,
-            self.phase,
-            *self.scope.vars.borrow()
-        );
+        let stmts = stmts.fold_children(self);
 
         match self.phase {
             Phase::Analysis => {
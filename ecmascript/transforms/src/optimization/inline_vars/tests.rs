@@ -1685,3 +1685,67 @@ to_fn!(
     use(foo);",
     "use(function foo() {})"
 );
+
+#[test]
+fn locals_only_keeps_top_level() {
+    test_cfg(
+        Config { locals_only: true },
+        "var a = 1; use(a); function f() { var b = 2; use(b); }",
+        "var a = 1; use(a); function f() { use(2); }",
+    );
+}
+
+to!(
+    const_in_nested_fn,
+    "const a = 1;
+    function f() {
+        return a;
+    }",
+    "const a = 1;
+    function f() {
+        return 1;
+    }"
+);
+
+identical!(
+    var_in_nested_fn,
+    "var a = 1;
+    function f() {
+        return a;
+    }"
+);
+
+identical!(
+    used_before_decl,
+    "use(a);
+    var a = 1;
+    use(a);"
+);
+
+identical!(
+    init_depends_on_mutated,
+    "function f(i) {
+        var x = i;
+        i++;
+        use(x);
+    }"
+);
+
+identical!(
+    multi_use_call_not_duplicated,
+    "function f() {
+        var a = foo();
+        use(a);
+        use(a);
+    }"
+);
+
+identical!(
+    this_not_inlined_into_nested_fn,
+    "function f() {
+        var _this = this;
+        return function () {
+            return _this;
+        };
+    }"
+);
@@ -12,6 +12,9 @@ pub(super) struct VarInfo {
     pub assign: u16,
     no_inline: bool,
     needed: bool,
+    is_const: bool,
+    /// Used from a function nested in the declaring function.
+    used_in_nested_fn: bool,
     ///
     ///   - Analysis phase: None
     ///   - Storage phase: None -> Some()
@@ -44,6 +47,8 @@ impl VarInfo {
             assign: 0,
             no_inline: false,
             needed: false,
+            is_const: false,
+            used_in_nested_fn: false,
             value: None,
         }
     }
@@ -53,8 +58,6 @@ impl VarInfo {
     }
 
     pub fn take_value(&mut self) -> Option<Expr> {
-        println!("VarInfo.take_value()");
-
         self.value.take()
     }
 
@@ -75,6 +78,28 @@ impl VarInfo {
         self.no_inline
     }
 
+    pub fn mark_as_const(&mut self) {
+        self.is_const = true;
+    }
+
+    /// Returns true if this is a `const` initialized with a literal, which can
+    /// be inlined into nested functions.
+    pub fn is_const_lit(&self) -> bool {
+        match self.value {
+            Some(Expr::Lit(Lit::Regex(..))) => false,
+            Some(Expr::Lit(..)) => self.is_const,
+            _ => false,
+        }
+    }
+
+    pub fn mark_as_used_in_nested_fn(&mut self) {
+        self.used_in_nested_fn = true;
+    }
+
+    pub const fn used_in_nested_fn(&self) -> bool {
+        self.used_in_nested_fn
+    }
+
     pub fn value(&self) -> Option<&Expr> {
         self.value.as_ref()
     }
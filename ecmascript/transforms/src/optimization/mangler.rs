@@ -0,0 +1,744 @@
+use crate::{
+    pass::Pass,
+    scope::{IdentType, ScopeKind},
+    util::DestructuringFinder,
+};
+use ast::*;
+use fxhash::{FxBuildHasher, FxHashMap, FxHashSet};
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
+use swc_atoms::JsWord;
+use swc_common::{fold::VisitWith, Fold, FoldWith};
+
+#[cfg(test)]
+mod tests;
+
+/// Renames bindings to short names.
+///
+/// This pass does not look at syntax contexts, so it should be applied to the
+/// output of `hygiene()`, where every identifier already resolves to the
+/// binding it refers to.
+///
+/// Scopes which can be observed by a direct `eval` or by a `with` statement
+/// are left as-is.
+pub fn mangler(config: Config) -> impl 'static + Pass {
+    Mangler {
+        config,
+        phase: Phase::Analysis,
+        scopes: vec![],
+        refs: vec![],
+        exported: Default::default(),
+        cur: 0,
+        next: 0,
+        hoist: false,
+        ident_type: IdentType::Ref,
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Mangle bindings declared in the top-level scope.
+    ///
+    /// Names exported from a module are never mangled.
+    #[serde(default)]
+    pub top_level: bool,
+
+    /// Bindings with these names are not renamed, and these names are never
+    /// used as a mangled name.
+    #[serde(default)]
+    pub reserved: Vec<JsWord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Analysis,
+    Renaming,
+}
+
+#[derive(Debug)]
+struct Scope {
+    parent: Option<usize>,
+    kind: ScopeKind,
+    /// Bindings declared in this scope, in declaration order.
+    decls: IndexSet<JsWord, FxBuildHasher>,
+    /// Bindings of ancestor scopes (`None` for globals) referenced from this
+    /// scope or its descendants.
+    outer: FxHashSet<(Option<usize>, JsWord)>,
+    /// True if `eval` or `with` can observe names of this scope.
+    dynamic: bool,
+    renamed: FxHashMap<JsWord, JsWord>,
+}
+
+impl Scope {
+    fn new(kind: ScopeKind, parent: Option<usize>) -> Self {
+        Scope {
+            parent,
+            kind,
+            decls: Default::default(),
+            outer: Default::default(),
+            dynamic: false,
+            renamed: Default::default(),
+        }
+    }
+}
+
+struct Mangler {
+    config: Config,
+    phase: Phase,
+    /// Scopes in the order they are entered, so parents come before children.
+    scopes: Vec<Scope>,
+    /// Identifiers seen in the analysis phase, with the scope they appear in.
+    refs: Vec<(usize, JsWord)>,
+    /// Names exported from the module.
+    exported: FxHashSet<JsWord>,
+    cur: usize,
+    /// Id of the next scope in the renaming phase.
+    next: usize,
+    /// True if a binding is hoisted to the nearest function scope.
+    hoist: bool,
+    ident_type: IdentType,
+}
+
+impl Mangler {
+    fn run<N>(&mut self, node: N) -> N
+    where
+        N: FoldWith<Self>,
+    {
+        self.phase = Phase::Analysis;
+        self.scopes = vec![Scope::new(ScopeKind::Fn, None)];
+        self.refs.clear();
+        self.exported.clear();
+        self.cur = 0;
+        let node = node.fold_children(self);
+
+        self.assign_names();
+
+        self.phase = Phase::Renaming;
+        self.cur = 0;
+        self.next = 1;
+        node.fold_children(self)
+    }
+
+    fn with_scope<F, T>(&mut self, kind: ScopeKind, op: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let id = match self.phase {
+            Phase::Analysis => {
+                self.scopes.push(Scope::new(kind, Some(self.cur)));
+                self.scopes.len() - 1
+            }
+            Phase::Renaming => {
+                let id = self.next;
+                self.next += 1;
+                debug_assert_eq!(self.scopes[id].kind, kind);
+                id
+            }
+        };
+
+        let old_cur = replace(&mut self.cur, id);
+        let old_hoist = replace(&mut self.hoist, false);
+        let old_type = replace(&mut self.ident_type, IdentType::Ref);
+        let res = op(self);
+        self.cur = old_cur;
+        self.hoist = old_hoist;
+        self.ident_type = old_type;
+
+        res
+    }
+
+    fn fold_binding<N>(&mut self, node: N) -> N
+    where
+        N: FoldWith<Self>,
+    {
+        let old = replace(&mut self.ident_type, IdentType::Binding);
+        let node = node.fold_with(self);
+        self.ident_type = old;
+        node
+    }
+
+    fn declare(&mut self, sym: JsWord) {
+        let mut id = self.cur;
+        if self.hoist {
+            while self.scopes[id].kind != ScopeKind::Fn {
+                id = self.scopes[id].parent.unwrap();
+            }
+        }
+        self.scopes[id].decls.insert(sym.clone());
+
+        // A hoisted binding must not be captured by the scopes in between.
+        self.refs.push((self.cur, sym));
+    }
+
+    fn reference(&mut self, sym: JsWord) {
+        if sym == js_word!("eval") {
+            self.mark_dynamic();
+        }
+        self.refs.push((self.cur, sym));
+    }
+
+    fn mark_dynamic(&mut self) {
+        let mut id = Some(self.cur);
+        while let Some(i) = id {
+            self.scopes[i].dynamic = true;
+            id = self.scopes[i].parent;
+        }
+    }
+
+    fn scope_of(&self, mut id: usize, sym: &JsWord) -> Option<usize> {
+        loop {
+            if self.scopes[id].decls.contains(sym) {
+                return Some(id);
+            }
+            id = self.scopes[id].parent?;
+        }
+    }
+
+    fn name_of(&self, scope: Option<usize>, sym: &JsWord) -> JsWord {
+        scope
+            .and_then(|id| self.scopes[id].renamed.get(sym))
+            .unwrap_or(sym)
+            .clone()
+    }
+
+    fn can_rename(&self, id: usize, sym: &JsWord) -> bool {
+        if self.scopes[id].dynamic || self.config.reserved.contains(sym) {
+            return false;
+        }
+        id != 0 || (self.config.top_level && !self.exported.contains(sym))
+    }
+
+    fn assign_names(&mut self) {
+        for (from, sym) in replace(&mut self.refs, vec![]) {
+            let target = self.scope_of(from, &sym);
+
+            let mut id = Some(from);
+            while let Some(i) = id {
+                if Some(i) == target {
+                    break;
+                }
+                self.scopes[i].outer.insert((target, sym.clone()));
+                id = self.scopes[i].parent;
+            }
+        }
+
+        // Names which are kept somewhere can be referenced from anywhere.
+        let mut kept: FxHashSet<JsWord> = self.config.reserved.iter().cloned().collect();
+        for id in 0..self.scopes.len() {
+            for sym in &self.scopes[id].decls {
+                if !self.can_rename(id, sym) {
+                    kept.insert(sym.clone());
+                }
+            }
+        }
+
+        // Parents are assigned before their children, so names of outer
+        // bindings are final here.
+        for id in 0..self.scopes.len() {
+            let mut used = kept.clone();
+            for &(target, ref sym) in &self.scopes[id].outer {
+                used.insert(self.name_of(target, sym));
+            }
+
+            let mut renamed = FxHashMap::default();
+            let mut n = 0;
+            for sym in &self.scopes[id].decls {
+                if !self.can_rename(id, sym) {
+                    continue;
+                }
+
+                let name = loop {
+                    let name = base54(n);
+                    n += 1;
+                    if !used.contains(&name) && !is_reserved_word(&name) {
+                        break name;
+                    }
+                };
+                used.insert(name.clone());
+                renamed.insert(sym.clone(), name);
+            }
+            self.scopes[id].renamed = renamed;
+        }
+    }
+
+    fn fold_fn(&mut self, f: Function) -> Function {
+        let decorators = f.decorators.fold_with(self);
+        let params = self.fold_binding(f.params);
+        let body = f.body.map(|body| body.fold_children(self));
+
+        Function {
+            decorators,
+            params,
+            body,
+            ..f
+        }
+    }
+}
+
+/// Returns the `n`th short name.
+fn base54(mut n: usize) -> JsWord {
+    const HEAD: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+    const TAIL: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+    let mut s = String::new();
+    s.push(HEAD[n % HEAD.len()] as char);
+    n /= HEAD.len();
+    while n > 0 {
+        n -= 1;
+        s.push(TAIL[n % TAIL.len()] as char);
+        n /= TAIL.len();
+    }
+
+    s.into()
+}
+
+fn is_reserved_word(s: &str) -> bool {
+    match s {
+        "do" | "if" | "in" | "for" | "let" | "new" | "try" | "var" | "NaN" | "case" | "else"
+        | "enum" | "eval" | "null" | "this" | "true" | "void" | "with" | "await" | "break"
+        | "catch" | "class" | "const" | "false" | "super" | "throw" | "while" | "yield"
+        | "delete" | "export" | "import" | "public" | "return" | "static" | "switch" | "typeof"
+        | "default" | "extends" | "finally" | "package" | "private" | "continue" | "debugger"
+        | "function" | "Infinity" | "arguments" | "interface" | "protected" | "undefined"
+        | "implements" | "instanceof" => true,
+        _ => false,
+    }
+}
+
+impl Fold<Module> for Mangler {
+    fn fold(&mut self, m: Module) -> Module {
+        self.run(m)
+    }
+}
+
+impl Fold<Script> for Mangler {
+    fn fold(&mut self, s: Script) -> Script {
+        self.run(s)
+    }
+}
+
+impl Fold<Ident> for Mangler {
+    fn fold(&mut self, i: Ident) -> Ident {
+        match self.phase {
+            Phase::Analysis => {
+                match self.ident_type {
+                    IdentType::Binding => self.declare(i.sym.clone()),
+                    IdentType::Ref => self.reference(i.sym.clone()),
+                    IdentType::Label => {}
+                }
+                i
+            }
+            Phase::Renaming => {
+                let sym = self.name_of(self.scope_of(self.cur, &i.sym), &i.sym);
+                Ident { sym, ..i }
+            }
+        }
+    }
+}
+
+impl Fold<Expr> for Mangler {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let old = replace(&mut self.ident_type, IdentType::Ref);
+        let e = e.fold_children(self);
+        self.ident_type = old;
+        e
+    }
+}
+
+impl Fold<MemberExpr> for Mangler {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl Fold<MetaPropExpr> for Mangler {
+    fn fold(&mut self, e: MetaPropExpr) -> MetaPropExpr {
+        e
+    }
+}
+
+impl Fold<PropName> for Mangler {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(e) => PropName::Computed(e.fold_with(self)),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<PrivateName> for Mangler {
+    fn fold(&mut self, n: PrivateName) -> PrivateName {
+        n
+    }
+}
+
+impl Fold<ClassProp> for Mangler {
+    fn fold(&mut self, p: ClassProp) -> ClassProp {
+        let key = if p.computed {
+            p.key.fold_with(self)
+        } else {
+            p.key
+        };
+
+        ClassProp {
+            key,
+            value: p.value.fold_with(self),
+            decorators: p.decorators.fold_with(self),
+            ..p
+        }
+    }
+}
+
+impl Fold<Prop> for Mangler {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => {
+                let renamed = i.clone().fold_with(self);
+                if renamed.sym == i.sym {
+                    return Prop::Shorthand(renamed);
+                }
+
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(renamed),
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ObjectPatProp> for Mangler {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            ObjectPatProp::Assign(p) => {
+                let key = p.key.clone().fold_with(self);
+                let value = p.value.fold_with(self);
+                if key.sym == p.key.sym {
+                    return ObjectPatProp::Assign(AssignPatProp {
+                        span: p.span,
+                        key,
+                        value,
+                    });
+                }
+
+                let pat = match value {
+                    Some(right) => Pat::Assign(AssignPat {
+                        span: p.span,
+                        left: box Pat::Ident(key),
+                        right,
+                        type_ann: None,
+                    }),
+                    None => Pat::Ident(key),
+                };
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(p.key),
+                    value: box pat,
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<VarDecl> for Mangler {
+    fn fold(&mut self, decl: VarDecl) -> VarDecl {
+        let old = replace(&mut self.hoist, decl.kind == VarDeclKind::Var);
+        let decls = decl.decls.fold_with(self);
+        self.hoist = old;
+
+        VarDecl { decls, ..decl }
+    }
+}
+
+impl Fold<VarDeclarator> for Mangler {
+    fn fold(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        let name = self.fold_binding(decl.name);
+        let init = decl.init.fold_with(self);
+
+        VarDeclarator { name, init, ..decl }
+    }
+}
+
+impl Fold<FnDecl> for Mangler {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        // Function declarations in blocks are hoisted in sloppy mode.
+        let old = replace(&mut self.hoist, true);
+        let ident = self.fold_binding(f.ident);
+        self.hoist = old;
+
+        FnDecl {
+            ident,
+            function: f.function.fold_with(self),
+            ..f
+        }
+    }
+}
+
+impl Fold<FnExpr> for Mangler {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        self.with_scope(ScopeKind::Fn, |m| FnExpr {
+            ident: m.fold_binding(f.ident),
+            function: m.fold_fn(f.function),
+        })
+    }
+}
+
+impl Fold<Function> for Mangler {
+    fn fold(&mut self, f: Function) -> Function {
+        self.with_scope(ScopeKind::Fn, |m| m.fold_fn(f))
+    }
+}
+
+impl Fold<ArrowExpr> for Mangler {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        self.with_scope(ScopeKind::Fn, |m| {
+            let params = m.fold_binding(f.params);
+            let body = match f.body {
+                BlockStmtOrExpr::BlockStmt(body) => {
+                    BlockStmtOrExpr::BlockStmt(body.fold_children(m))
+                }
+                BlockStmtOrExpr::Expr(body) => BlockStmtOrExpr::Expr(body.fold_with(m)),
+            };
+
+            ArrowExpr { params, body, ..f }
+        })
+    }
+}
+
+impl Fold<Constructor> for Mangler {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        let c = Constructor {
+            key: c.key.fold_with(self),
+            ..c
+        };
+
+        self.with_scope(ScopeKind::Fn, |m| Constructor {
+            params: m.fold_binding(c.params),
+            body: c.body.map(|body| body.fold_children(m)),
+            ..c
+        })
+    }
+}
+
+impl Fold<GetterProp> for Mangler {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let p = GetterProp {
+            key: p.key.fold_with(self),
+            ..p
+        };
+
+        self.with_scope(ScopeKind::Fn, |m| GetterProp {
+            body: p.body.map(|body| body.fold_children(m)),
+            ..p
+        })
+    }
+}
+
+impl Fold<SetterProp> for Mangler {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let p = SetterProp {
+            key: p.key.fold_with(self),
+            ..p
+        };
+
+        self.with_scope(ScopeKind::Fn, |m| SetterProp {
+            param: m.fold_binding(p.param),
+            body: p.body.map(|body| body.fold_children(m)),
+            ..p
+        })
+    }
+}
+
+impl Fold<ClassDecl> for Mangler {
+    fn fold(&mut self, c: ClassDecl) -> ClassDecl {
+        let ident = self.fold_binding(c.ident);
+
+        ClassDecl {
+            ident,
+            class: c.class.fold_with(self),
+            ..c
+        }
+    }
+}
+
+impl Fold<ClassExpr> for Mangler {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        self.with_scope(ScopeKind::Block, |m| ClassExpr {
+            ident: m.fold_binding(c.ident),
+            class: c.class.fold_with(m),
+        })
+    }
+}
+
+impl Fold<CatchClause> for Mangler {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        self.with_scope(ScopeKind::Block, |m| CatchClause {
+            param: m.fold_binding(c.param),
+            body: c.body.fold_children(m),
+            ..c
+        })
+    }
+}
+
+macro_rules! block_scope {
+    ($T:tt) => {
+        impl Fold<$T> for Mangler {
+            fn fold(&mut self, node: $T) -> $T {
+                self.with_scope(ScopeKind::Block, |m| node.fold_children(m))
+            }
+        }
+    };
+}
+
+block_scope!(BlockStmt);
+block_scope!(ForStmt);
+block_scope!(ForInStmt);
+block_scope!(ForOfStmt);
+block_scope!(SwitchStmt);
+
+impl Fold<WithStmt> for Mangler {
+    fn fold(&mut self, s: WithStmt) -> WithStmt {
+        if self.phase == Phase::Analysis {
+            self.mark_dynamic();
+        }
+
+        s.fold_children(self)
+    }
+}
+
+impl Fold<LabeledStmt> for Mangler {
+    fn fold(&mut self, s: LabeledStmt) -> LabeledStmt {
+        LabeledStmt {
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+}
+
+impl Fold<BreakStmt> for Mangler {
+    fn fold(&mut self, s: BreakStmt) -> BreakStmt {
+        s
+    }
+}
+
+impl Fold<ContinueStmt> for Mangler {
+    fn fold(&mut self, s: ContinueStmt) -> ContinueStmt {
+        s
+    }
+}
+
+impl Fold<ImportSpecifier> for Mangler {
+    fn fold(&mut self, s: ImportSpecifier) -> ImportSpecifier {
+        match s {
+            ImportSpecifier::Specific(s) => {
+                let local = self.fold_binding(s.local.clone());
+                let imported = match s.imported {
+                    Some(imported) => Some(imported),
+                    None if local.sym != s.local.sym => Some(s.local),
+                    None => None,
+                };
+
+                ImportSpecifier::Specific(ImportSpecific {
+                    span: s.span,
+                    local,
+                    imported,
+                })
+            }
+            _ => {
+                let old = replace(&mut self.ident_type, IdentType::Binding);
+                let s = s.fold_children(self);
+                self.ident_type = old;
+                s
+            }
+        }
+    }
+}
+
+impl Fold<ExportDecl> for Mangler {
+    fn fold(&mut self, e: ExportDecl) -> ExportDecl {
+        if self.phase == Phase::Analysis {
+            match e.decl {
+                Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                    self.exported.insert(ident.sym.clone());
+                }
+                Decl::Var(ref var) => {
+                    let mut found: Vec<Ident> = vec![];
+                    var.decls
+                        .visit_with(&mut DestructuringFinder { found: &mut found });
+                    self.exported.extend(found.into_iter().map(|i| i.sym));
+                }
+                _ => {}
+            }
+        }
+
+        e.fold_children(self)
+    }
+}
+
+impl Fold<ExportDefaultDecl> for Mangler {
+    fn fold(&mut self, e: ExportDefaultDecl) -> ExportDefaultDecl {
+        // The name of a default-exported declaration is bound in the module
+        // scope.
+        let decl = match e.decl {
+            DefaultDecl::Class(ClassExpr {
+                ident: Some(ident),
+                class,
+            }) => DefaultDecl::Class(ClassExpr {
+                ident: Some(self.fold_binding(ident)),
+                class: class.fold_with(self),
+            }),
+            DefaultDecl::Fn(FnExpr {
+                ident: Some(ident),
+                function,
+            }) => DefaultDecl::Fn(FnExpr {
+                ident: Some(self.fold_binding(ident)),
+                function: function.fold_with(self),
+            }),
+            decl => decl.fold_with(self),
+        };
+
+        ExportDefaultDecl { decl, ..e }
+    }
+}
+
+impl Fold<NamedExport> for Mangler {
+    fn fold(&mut self, e: NamedExport) -> NamedExport {
+        // Specifiers of a re-export refer to the other module.
+        if e.src.is_some() {
+            return e;
+        }
+
+        e.fold_children(self)
+    }
+}
+
+impl Fold<NamedExportSpecifier> for Mangler {
+    fn fold(&mut self, s: NamedExportSpecifier) -> NamedExportSpecifier {
+        let old = replace(&mut self.ident_type, IdentType::Ref);
+        let orig = s.orig.clone().fold_with(self);
+        self.ident_type = old;
+
+        let exported = match s.exported {
+            Some(exported) => Some(exported),
+            None if orig.sym != s.orig.sym => Some(s.orig),
+            None => None,
+        };
+
+        NamedExportSpecifier {
+            span: s.span,
+            orig,
+            exported,
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use super::*;
+use swc_ecma_parser::Syntax;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+fn top_level() -> Config {
+    Config {
+        top_level: true,
+        ..Default::default()
+    }
+}
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    params_and_locals,
+    r#"
+function foo(first, second) {
+    var third = first + second;
+    return third;
+}
+"#,
+    r#"
+function foo(a, b) {
+    var c = a + b;
+    return c;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    globals_are_not_shadowed,
+    r#"
+function foo(value) {
+    return a(value);
+}
+"#,
+    r#"
+function foo(b) {
+    return a(b);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    sibling_scopes_reuse_names,
+    r#"
+function f(first) { return first; }
+function g(second) { return second; }
+"#,
+    r#"
+function f(a) { return a; }
+function g(a) { return a; }
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    captured_bindings,
+    r#"
+function f(outer) {
+    return function (inner) {
+        return outer + inner;
+    };
+}
+"#,
+    r#"
+function f(a) {
+    return function (b) {
+        return a + b;
+    };
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    block_scope_and_hoisting,
+    r#"
+function f() {
+    {
+        let x = 1;
+        var y = 2;
+    }
+    return y;
+}
+"#,
+    r#"
+function f() {
+    {
+        let b = 1;
+        var a = 2;
+    }
+    return a;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(top_level()),
+    top_level,
+    r#"
+var longName = 1;
+function fn() {
+    return longName;
+}
+"#,
+    r#"
+var a = 1;
+function b() {
+    return a;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Config {
+        reserved: vec!["keepMe".into()],
+        ..Default::default()
+    }),
+    reserved,
+    r#"
+function foo(keepMe, other) {
+    return keepMe + other;
+}
+"#,
+    r#"
+function foo(keepMe, a) {
+    return keepMe + a;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    shorthand_props,
+    r#"
+function foo(value, { key, other = 1 }) {
+    return { value, key, other };
+}
+"#,
+    r#"
+function foo(a, { key: b, other: c = 1 }) {
+    return { value: a, key: b, other: c };
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(Default::default()),
+    eval,
+    r#"
+function foo(bar) {
+    return eval("bar");
+}
+"#,
+    r#"
+function foo(bar) {
+    return eval("bar");
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| mangler(top_level()),
+    module,
+    r#"
+import { foo } from 'foo';
+export const bar = foo();
+export default function baz(qux) {
+    return quux;
+}
+export { quux };
+var quux = baz;
+"#,
+    r#"
+import { foo as a } from 'foo';
+export const bar = a();
+export default function b(a) {
+    return c;
+}
+export { c as quux };
+var c = b;
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| mangler(top_level()),
+    exec,
+    r#"
+var total = 0;
+function add(value) {
+    var result = total + value;
+    for (let index = 0; index < 2; index++) {
+        result += index;
+    }
+    try {
+        throw result;
+    } catch (error) {
+        total = error;
+    }
+    return { total, value };
+}
+expect(add(1)).toEqual({ total: 2, value: 1 });
+"#
+);
@@ -1029,7 +1029,7 @@ fn ignore_result(e: Expr) -> Option<Expr> {
             left,
             op,
             right,
-        }) if op != op!("&&") && op != op!("||") => {
+        }) if op != op!("&&") && op != op!("||") && op != op!("??") => {
             let left = ignore_result(*left);
             let right = ignore_result(*right);
 
@@ -1068,6 +1068,16 @@ fn ignore_result(e: Expr) -> Option<Expr> {
                         right,
                     }))
                 }
+            } else if op == op!("??") {
+                match ignore_result(*right) {
+                    Some(right) => Some(Expr::Bin(BinExpr {
+                        span,
+                        left,
+                        op,
+                        right: box right,
+                    })),
+                    None => ignore_result(*left),
+                }
             } else {
                 debug_assert_eq!(op, op!("||"));
 
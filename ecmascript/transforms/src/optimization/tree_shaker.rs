@@ -0,0 +1,604 @@
+use crate::{
+    pass::Pass,
+    util::{id, DestructuringFinder, ExprExt, Id, COMMENTS},
+};
+use ast::*;
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{comments::CommentKind, util::move_map::MoveMap, Fold, Span, Visit, VisitWith};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes unused top-level declarations and imports of a module.
+///
+/// A call or `new` expression annotated with `/*#__PURE__*/` is assumed to
+/// have no side effects.
+///
+/// The pass requires the `resolver` pass to be applied beforehand.
+pub fn tree_shaker(config: Config) -> impl 'static + Pass {
+    TreeShaker { config }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Names of exports used by other modules. `default` means the default
+    /// export.
+    ///
+    /// If `None`, all exports are treated as used.
+    #[serde(default)]
+    pub used_exports: Option<Vec<JsWord>>,
+
+    /// Imported modules which don't have side effects, like packages with
+    /// `"sideEffects": false` in `package.json`.
+    ///
+    /// Imports from these modules are removed if no imported binding is used.
+    #[serde(default)]
+    pub no_side_effects: Vec<JsWord>,
+}
+
+struct TreeShaker {
+    config: Config,
+}
+
+/// A top-level declaration or statement.
+#[derive(Debug, Default)]
+struct Node {
+    /// Bindings declared by this node.
+    decls: Vec<Id>,
+    /// Bindings referenced by this node.
+    refs: FxHashSet<Id>,
+    /// Should be kept even if no binding in `decls` is used.
+    root: bool,
+}
+
+impl TreeShaker {
+    fn is_export_used(&self, name: &JsWord) -> bool {
+        match self.config.used_exports {
+            Some(ref used) => used.contains(name),
+            None => true,
+        }
+    }
+
+    fn has_side_effects(&self, src: &Str) -> bool {
+        !self.config.no_side_effects.contains(&src.value)
+    }
+
+    fn analyze(&self, item: &ModuleItem, nodes: &mut Vec<Node>) {
+        match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ref import)) => nodes.push(Node {
+                decls: import
+                    .specifiers
+                    .iter()
+                    .map(|s| match *s {
+                        ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                        | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                        | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => id(local),
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                self.analyze_decl(decl, true, nodes)
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                ref decl,
+                ..
+            })) => {
+                let (ident, root) = match *decl {
+                    DefaultDecl::Class(ClassExpr {
+                        ref ident,
+                        ref class,
+                    }) => (ident.as_ref(), !is_pure_class(class)),
+                    DefaultDecl::Fn(FnExpr { ref ident, .. }) => (ident.as_ref(), false),
+                    DefaultDecl::TsInterfaceDecl(..) => (None, true),
+                };
+
+                nodes.push(Node {
+                    decls: ident.into_iter().map(id).collect(),
+                    refs: refs(decl),
+                    root: root || self.is_export_used(&js_word!("default")),
+                })
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                ref expr,
+                ..
+            })) => nodes.push(Node {
+                refs: refs(expr),
+                root: !is_pure(expr) || self.is_export_used(&js_word!("default")),
+                ..Default::default()
+            }),
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(ref export)) => {
+                let mut node = Node::default();
+                for s in &export.specifiers {
+                    if self.is_export_used(exported_name(s)) {
+                        node.root = true;
+                        if export.src.is_none() {
+                            if let ExportSpecifier::Named(NamedExportSpecifier {
+                                ref orig, ..
+                            }) = *s
+                            {
+                                node.refs.insert(id(orig));
+                            }
+                        }
+                    }
+                }
+                nodes.push(node)
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(ref decl)) => self.analyze_decl(decl, false, nodes),
+
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => nodes.push(Node {
+                refs: refs(expr),
+                root: !is_pure(expr),
+                ..Default::default()
+            }),
+
+            ModuleItem::Stmt(Stmt::Empty(..)) => nodes.push(Node::default()),
+
+            _ => nodes.push(Node {
+                refs: refs(item),
+                root: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Pushes a node for each declarator of a variable declaration, and a
+    /// node for other declarations.
+    fn analyze_decl(&self, decl: &Decl, exported: bool, nodes: &mut Vec<Node>) {
+        match *decl {
+            Decl::Fn(FnDecl {
+                ref ident,
+                ref function,
+                ..
+            }) => nodes.push(Node {
+                decls: vec![id(ident)],
+                refs: refs(function),
+                root: exported && self.is_export_used(&ident.sym),
+            }),
+
+            Decl::Class(ClassDecl {
+                ref ident,
+                ref class,
+                ..
+            }) => nodes.push(Node {
+                decls: vec![id(ident)],
+                refs: refs(class),
+                root: !is_pure_class(class) || (exported && self.is_export_used(&ident.sym)),
+            }),
+
+            Decl::Var(VarDecl { ref decls, .. }) => {
+                for decl in decls {
+                    let mut ids = vec![];
+                    decl.name
+                        .visit_with(&mut DestructuringFinder { found: &mut ids });
+
+                    let root = match decl.name {
+                        Pat::Ident(..) => {
+                            decl.init.as_ref().map_or(false, |init| !is_pure(init))
+                                || (exported && ids.iter().any(|i| self.is_export_used(&i.0)))
+                        }
+                        // Destructuring may throw.
+                        _ => true,
+                    };
+
+                    nodes.push(Node {
+                        decls: ids,
+                        refs: refs(decl),
+                        root,
+                    })
+                }
+            }
+
+            _ => nodes.push(Node {
+                refs: refs(decl),
+                root: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns `None` if `decl` should be removed.
+    fn shake_decl(
+        &self,
+        decl: Decl,
+        live: &mut impl Iterator<Item = bool>,
+        exported: bool,
+    ) -> Option<(Decl, bool)> {
+        match decl {
+            Decl::Fn(..) | Decl::Class(..) => {
+                if !live.next().unwrap() {
+                    return None;
+                }
+                let name = match decl {
+                    Decl::Fn(FnDecl { ref ident, .. })
+                    | Decl::Class(ClassDecl { ref ident, .. }) => &ident.sym,
+                    _ => unreachable!(),
+                };
+                let exported = exported && self.is_export_used(name);
+
+                Some((decl, exported))
+            }
+
+            Decl::Var(var) => {
+                let mut keep_export = false;
+                let decls = var.decls.move_flat_map(|decl| {
+                    if !live.next().unwrap() {
+                        return None;
+                    }
+                    if exported {
+                        let mut ids: Vec<Id> = vec![];
+                        decl.name
+                            .visit_with(&mut DestructuringFinder { found: &mut ids });
+                        keep_export |= ids.iter().any(|i| self.is_export_used(&i.0));
+                    }
+
+                    Some(decl)
+                });
+                if decls.is_empty() {
+                    return None;
+                }
+
+                Some((Decl::Var(VarDecl { decls, ..var }), keep_export))
+            }
+
+            _ => {
+                live.next();
+                Some((decl, exported))
+            }
+        }
+    }
+
+    /// Imports `src` only for side effects.
+    fn import_for_effects(&self, span: Span, src: Str) -> Option<ModuleItem> {
+        if !self.has_side_effects(&src) {
+            return None;
+        }
+
+        Some(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            span,
+            specifiers: vec![],
+            src,
+        })))
+    }
+}
+
+impl Fold<Module> for TreeShaker {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut nodes = vec![];
+        for item in &module.body {
+            self.analyze(item, &mut nodes);
+        }
+
+        let mut used = FxHashSet::default();
+        let mut live = vec![false; nodes.len()];
+        loop {
+            let mut changed = false;
+            for (node, live) in nodes.iter().zip(live.iter_mut()) {
+                if *live || !(node.root || node.decls.iter().any(|i| used.contains(i))) {
+                    continue;
+                }
+                *live = true;
+                changed = true;
+                used.extend(node.refs.iter().cloned());
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut live = live.into_iter();
+
+        let body = module.body.move_flat_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                live.next();
+
+                let specifiers = import.specifiers.move_flat_map(|s| {
+                    let local = match s {
+                        ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                        | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                        | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+                    };
+                    if used.contains(&id(local)) {
+                        Some(s)
+                    } else {
+                        None
+                    }
+                });
+
+                if specifiers.is_empty() {
+                    return self.import_for_effects(import.span, import.src);
+                }
+
+                Some(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    specifiers,
+                    ..import
+                })))
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl })) => {
+                let (decl, exported) = self.shake_decl(decl, &mut live, true)?;
+
+                Some(if exported {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl }))
+                } else {
+                    ModuleItem::Stmt(Stmt::Decl(decl))
+                })
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if !live.next().unwrap() {
+                    return None;
+                }
+                if self.is_export_used(&js_word!("default")) {
+                    return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(
+                        export,
+                    )));
+                }
+
+                // The default export is not used, but the declaration is.
+                let decl = match export.decl {
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }) => Decl::Class(ClassDecl {
+                        ident,
+                        class,
+                        declare: false,
+                    }),
+                    DefaultDecl::Fn(FnExpr {
+                        ident: Some(ident),
+                        function,
+                    }) => Decl::Fn(FnDecl {
+                        ident,
+                        function,
+                        declare: false,
+                    }),
+                    DefaultDecl::Class(ClassExpr { ident: None, class }) => {
+                        return Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                            span: export.span,
+                            expr: box Expr::Class(ClassExpr { ident: None, class }),
+                        })));
+                    }
+                    decl => {
+                        return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(
+                            ExportDefaultDecl { decl, ..export },
+                        )))
+                    }
+                };
+
+                Some(ModuleItem::Stmt(Stmt::Decl(decl)))
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                if !live.next().unwrap() {
+                    return None;
+                }
+                if self.is_export_used(&js_word!("default")) {
+                    return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                        export,
+                    )));
+                }
+
+                Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                    span: export.span,
+                    expr: export.expr,
+                })))
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                live.next();
+
+                let specifiers = export.specifiers.move_flat_map(|s| {
+                    if self.is_export_used(exported_name(&s)) {
+                        Some(s)
+                    } else {
+                        None
+                    }
+                });
+
+                if specifiers.is_empty() {
+                    return match export.src {
+                        Some(src) => self.import_for_effects(export.span, src),
+                        None => None,
+                    };
+                }
+
+                Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                    NamedExport {
+                        specifiers,
+                        ..export
+                    },
+                )))
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let (decl, _) = self.shake_decl(decl, &mut live, false)?;
+
+                Some(ModuleItem::Stmt(Stmt::Decl(decl)))
+            }
+
+            _ => {
+                if live.next().unwrap() {
+                    Some(item)
+                } else {
+                    None
+                }
+            }
+        });
+
+        Module { body, ..module }
+    }
+}
+
+fn exported_name(s: &ExportSpecifier) -> &JsWord {
+    match *s {
+        ExportSpecifier::Namespace(NamespaceExportSpecifier { ref name, .. }) => &name.sym,
+        ExportSpecifier::Default(DefaultExportSpecifier { ref exported }) => &exported.sym,
+        ExportSpecifier::Named(NamedExportSpecifier {
+            exported: Some(ref exported),
+            ..
+        }) => &exported.sym,
+        ExportSpecifier::Named(NamedExportSpecifier { ref orig, .. }) => &orig.sym,
+    }
+}
+
+/// Returns true if `span` is annotated with `/*#__PURE__*/`.
+fn has_pure_annotation(span: Span) -> bool {
+    if span.is_dummy() || !COMMENTS.is_set() {
+        return false;
+    }
+
+    COMMENTS.with(|comments| {
+        comments.leading_comments(span.lo()).map_or(false, |cmts| {
+            cmts.iter().any(|c| {
+                c.kind == CommentKind::Block && {
+                    let text = c.text.trim();
+                    text == "#__PURE__" || text == "@__PURE__"
+                }
+            })
+        })
+    })
+}
+
+/// Returns true if evaluating `e` has no side effect.
+fn is_pure(e: &Expr) -> bool {
+    match *e {
+        Expr::Call(CallExpr {
+            span,
+            callee: ExprOrSuper::Expr(ref callee),
+            ref args,
+            ..
+        }) if has_pure_annotation(span) => {
+            is_pure(callee)
+                && args
+                    .iter()
+                    .all(|arg| arg.spread.is_none() && is_pure(&arg.expr))
+        }
+        Expr::New(NewExpr {
+            span,
+            ref callee,
+            ref args,
+            ..
+        }) if has_pure_annotation(span) => {
+            is_pure(callee)
+                && args
+                    .iter()
+                    .flatten()
+                    .all(|arg| arg.spread.is_none() && is_pure(&arg.expr))
+        }
+        Expr::Class(ClassExpr { ref class, .. }) => is_pure_class(class),
+        Expr::Tpl(Tpl { ref exprs, .. }) => exprs.iter().all(|e| is_pure(e)),
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_pure(expr),
+        Expr::Array(ArrayLit { ref elems, .. }) => elems
+            .iter()
+            .flatten()
+            .all(|e| e.spread.is_none() && is_pure(&e.expr)),
+        Expr::Unary(UnaryExpr { op, ref arg, .. }) => op != op!("delete") && is_pure(arg),
+        Expr::Bin(BinExpr {
+            ref left,
+            ref right,
+            ..
+        }) => is_pure(left) && is_pure(right),
+        Expr::Seq(SeqExpr { ref exprs, .. }) => exprs.iter().all(|e| is_pure(e)),
+        Expr::Cond(CondExpr {
+            ref test,
+            ref cons,
+            ref alt,
+            ..
+        }) => is_pure(test) && is_pure(cons) && is_pure(alt),
+        _ => !e.may_have_side_effects(),
+    }
+}
+
+fn is_pure_class(class: &Class) -> bool {
+    if !class.decorators.is_empty() {
+        return false;
+    }
+    if let Some(ref super_class) = class.super_class {
+        if !is_pure(super_class) {
+            return false;
+        }
+    }
+
+    class.body.iter().all(|member| match *member {
+        ClassMember::Method(ClassMethod {
+            key: PropName::Computed(ref key),
+            ..
+        }) => is_pure(key),
+        ClassMember::ClassProp(ClassProp {
+            ref key,
+            computed,
+            ref value,
+            is_static,
+            ..
+        }) => {
+            (!computed || is_pure(key))
+                && (!is_static || value.as_ref().map_or(true, |value| is_pure(value)))
+        }
+        ClassMember::PrivateProp(PrivateProp {
+            ref value,
+            is_static,
+            ..
+        }) => !is_static || value.as_ref().map_or(true, |value| is_pure(value)),
+        _ => true,
+    })
+}
+
+fn refs<T>(node: &T) -> FxHashSet<Id>
+where
+    T: VisitWith<RefCollector>,
+{
+    let mut v = RefCollector {
+        refs: Default::default(),
+    };
+    node.visit_with(&mut v);
+
+    v.refs
+}
+
+/// Collects identifiers which may reference a top-level binding.
+struct RefCollector {
+    refs: FxHashSet<Id>,
+}
+
+impl Visit<Ident> for RefCollector {
+    fn visit(&mut self, i: &Ident) {
+        self.refs.insert(id(i));
+    }
+}
+
+impl Visit<MemberExpr> for RefCollector {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for RefCollector {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(ref e) = *n {
+            e.visit_with(self);
+        }
+    }
+}
+
+impl Visit<ClassProp> for RefCollector {
+    fn visit(&mut self, p: &ClassProp) {
+        if p.computed {
+            p.key.visit_with(self);
+        }
+        p.value.visit_with(self);
+        p.decorators.visit_with(self);
+    }
+}
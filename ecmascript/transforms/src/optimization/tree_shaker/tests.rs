@@ -0,0 +1,239 @@
+use super::*;
+use crate::resolver;
+use swc_common::chain;
+use swc_ecma_parser::Syntax;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+fn tr(config: Config) -> impl Pass {
+    chain!(resolver(), tree_shaker(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    unused_decls,
+    r#"
+function foo() {}
+function bar() {
+    return baz;
+}
+class Baz {}
+var a = 1, b = bar();
+use(a);
+"#,
+    r#"
+function bar() {
+    return baz;
+}
+var a = 1, b = bar();
+use(a);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    transitive,
+    r#"
+function a() {}
+function b() {
+    return a();
+}
+function c() {
+    return b();
+}
+export function d() {
+    return c();
+}
+"#,
+    r#"
+function a() {}
+function b() {
+    return a();
+}
+function c() {
+    return b();
+}
+export function d() {
+    return c();
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    keep_impure,
+    r#"
+var a = foo();
+class A extends B() {}
+foo();
+"#,
+    r#"
+var a = foo();
+class A extends B() {}
+foo();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    pure_annotation,
+    r#"
+var a = /*#__PURE__*/ foo();
+var b = /*#__PURE__*/ new Foo(1);
+/*#__PURE__*/ bar();
+var c = /*#__PURE__*/ foo(bar());
+"#,
+    r#"
+var c = /*#__PURE__*/ foo(bar());
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        used_exports: Some(vec!["a".into()]),
+        ..Default::default()
+    }),
+    used_exports,
+    r#"
+export function a() {
+    return c;
+}
+export function b() {}
+export const c = 1, d = 2;
+function e() {}
+export { e, e as f };
+export default function g() {}
+"#,
+    r#"
+export function a() {
+    return c;
+}
+const c = 1;
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        used_exports: Some(vec![]),
+        ..Default::default()
+    }),
+    unused_default_export,
+    r#"
+export default foo();
+"#,
+    r#"
+foo();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        used_exports: Some(vec![]),
+        ..Default::default()
+    }),
+    unused_default_decl,
+    r#"
+export default function foo() {}
+use();
+"#,
+    r#"
+use();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        used_exports: Some(vec![]),
+        ..Default::default()
+    }),
+    default_decl_used_locally,
+    r#"
+export default class Foo {}
+use(Foo);
+"#,
+    r#"
+class Foo {}
+use(Foo);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        used_exports: Some(vec![]),
+        ..Default::default()
+    }),
+    unused_reexport,
+    r#"
+export { a } from 'a';
+export * from 'b';
+"#,
+    r#"
+import 'a';
+export * from 'b';
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    unused_imports,
+    r#"
+import a, { b, c as d } from 'a';
+import * as e from 'e';
+import 'f';
+use(b);
+"#,
+    r#"
+import { b } from 'a';
+import 'e';
+import 'f';
+use(b);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        no_side_effects: vec!["a".into(), "f".into()],
+        ..Default::default()
+    }),
+    no_side_effects,
+    r#"
+import a from 'a';
+import b from 'b';
+import 'f';
+export { c } from 'a';
+"#,
+    r#"
+import 'b';
+export { c } from 'a';
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    shadowed,
+    r#"
+var a = 1;
+export function foo(a) {
+    return a;
+}
+"#,
+    r#"
+export function foo(a) {
+    return a;
+}
+"#
+);
@@ -1,6 +1,6 @@
 use self::{
     class_name_tdz::ClassNameTdzFolder,
-    private_field::FieldAccessFolder,
+    private_field::{method_fn_ident, FieldAccessFolder},
     this_in_static::ThisInStaticFolder,
     used_name::{UsedNameCollector, UsedNameRenamer},
 };
@@ -9,7 +9,7 @@ use crate::{
     pass::Pass,
     util::{
         alias_ident_for, alias_if_required, constructor::inject_after_super, default_constructor,
-        undefined, ExprFactory, ModuleItemLike, StmtLike,
+        prepend, undefined, ExprFactory, ModuleItemLike, StmtLike,
     },
 };
 use ast::*;
@@ -32,12 +32,18 @@ mod used_name;
 ///
 /// We use custom helper to handle export defaul class
 pub fn class_properties() -> impl Pass {
-    ClassProperties { mark: Mark::root() }
+    ClassProperties {
+        mark: Mark::root(),
+        method_mark: Mark::root(),
+    }
 }
 
 #[derive(Clone)]
 struct ClassProperties {
     mark: Mark,
+    /// Used for functions of private methods, so they don't collide with
+    /// private fields.
+    method_mark: Mark,
 }
 
 impl<T> Fold<Vec<T>> for ClassProperties
@@ -259,6 +265,7 @@ impl ClassProperties {
     ) -> (Vec<VarDeclarator>, Decl, Vec<Stmt>) {
         // Create one mark per class
         self.mark = Mark::fresh(Mark::root());
+        self.method_mark = Mark::fresh(Mark::root());
 
         let has_super = class.super_class.is_some();
 
@@ -267,11 +274,90 @@ impl ClassProperties {
         let mut used_names = vec![];
         let mut used_key_names = vec![];
         let mut statics = HashSet::default();
+        let mut private_methods = HashSet::default();
+        // Initializers of private methods and accessors, which run before fields.
+        let mut private_method_exprs = vec![];
+        let mut private_method_stmts = vec![];
+        // (storage, getter, setter, is_static)
+        let mut private_accessors: Vec<(Ident, Option<Ident>, Option<Ident>, bool)> = vec![];
 
         for member in class.body {
             match member {
-                ClassMember::PrivateMethod(..) | ClassMember::TsIndexSignature(..) => {
-                    members.push(member)
+                ClassMember::TsIndexSignature(..) => members.push(member),
+
+                ClassMember::PrivateMethod(method) => {
+                    let function = bind_super(&ident, method.is_static, method.function);
+                    let ident = Ident::new(
+                        format!("_{}", method.key.id.sym).into(),
+                        // We use `self.mark` for private variables.
+                        method.key.span.apply_mark(self.mark),
+                    );
+                    if method.is_static {
+                        statics.insert(method.key.id.sym.clone());
+                    }
+
+                    let fn_name = match method.kind {
+                        MethodKind::Method => {
+                            private_methods.insert(method.key.id.sym.clone());
+
+                            if method.is_static {
+                                ident
+                            } else {
+                                // _foo.add(this)
+                                private_method_exprs.push(box Expr::Call(CallExpr {
+                                    span: DUMMY_SP,
+                                    callee: ident.clone().member(quote_ident!("add")).as_callee(),
+                                    args: vec![ThisExpr { span: DUMMY_SP }.as_arg()],
+                                    type_args: Default::default(),
+                                }));
+                                extra_stmts.push(new_weak_var(ident, "WeakSet"));
+
+                                method_fn_ident(&method.key, self.method_mark)
+                            }
+                        }
+                        MethodKind::Getter | MethodKind::Setter => {
+                            let is_getter = method.kind == MethodKind::Getter;
+                            let fn_name = private_ident!(
+                                method.key.span,
+                                format!(
+                                    "_{}_{}",
+                                    if is_getter { "get" } else { "set" },
+                                    method.key.id.sym
+                                )
+                            );
+
+                            let pos = private_accessors.iter().position(|a| a.0.sym == ident.sym);
+                            let idx = match pos {
+                                Some(idx) => idx,
+                                None => {
+                                    private_accessors.push((ident, None, None, method.is_static));
+                                    private_accessors.len() - 1
+                                }
+                            };
+                            if is_getter {
+                                private_accessors[idx].1 = Some(fn_name.clone());
+                            } else {
+                                private_accessors[idx].2 = Some(fn_name.clone());
+                            }
+
+                            fn_name
+                        }
+                    };
+
+                    private_method_stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                        span: DUMMY_SP,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            definite: false,
+                            name: Pat::Ident(fn_name),
+                            init: Some(box Expr::Fn(FnExpr {
+                                ident: None,
+                                function,
+                            })),
+                        }],
+                    })));
                 }
 
                 ClassMember::Method(method) => {
@@ -476,19 +562,72 @@ impl ClassProperties {
             }
         }
 
+        for (storage, getter, setter, is_static) in private_accessors {
+            let mut props = vec![];
+            if let Some(getter) = getter {
+                props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(quote_ident!("get")),
+                    value: box Expr::Ident(getter),
+                })));
+            }
+            if let Some(setter) = setter {
+                props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(quote_ident!("set")),
+                    value: box Expr::Ident(setter),
+                })));
+            }
+            let desc = box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            });
+
+            if is_static {
+                extra_stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        definite: false,
+                        name: Pat::Ident(storage),
+                        init: Some(desc),
+                    }],
+                })));
+            } else {
+                // _foo.set(this, { get: _get_foo, set: _set_foo })
+                private_method_exprs.push(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: storage.clone().member(quote_ident!("set")).as_callee(),
+                    args: vec![ThisExpr { span: DUMMY_SP }.as_arg(), desc.as_arg()],
+                    type_args: Default::default(),
+                }));
+                extra_stmts.push(new_weak_var(storage, "WeakMap"));
+            }
+        }
+
+        private_method_exprs.extend(constructor_exprs);
         let constructor =
-            self.process_constructor(constructor, has_super, &used_names, constructor_exprs);
+            self.process_constructor(constructor, has_super, &used_names, private_method_exprs);
         if let Some(c) = constructor {
             members.push(ClassMember::Constructor(c));
         }
 
-        let members = members.fold_with(&mut FieldAccessFolder {
+        let mut folder = FieldAccessFolder {
             mark: self.mark,
+            method_mark: self.method_mark,
             statics: &statics,
+            methods: &private_methods,
             vars: vec![],
             class_name: &ident,
             in_assign_pat: false,
-        });
+        };
+        let members = members.fold_with(&mut folder);
+        // Functions are declared first, as static initializers may call them.
+        let extra_stmts: Vec<_> = private_method_stmts
+            .fold_with(&mut folder)
+            .into_iter()
+            .chain(extra_stmts)
+            .collect();
 
         (
             vars,
@@ -539,3 +678,55 @@ impl ClassProperties {
         }
     }
 }
+
+/// Converts `super.foo` in a private method, as the method is moved out of the
+/// class.
+fn bind_super(class_name: &Ident, is_static: bool, function: Function) -> Function {
+    let mut vars = vec![];
+    let mut function = function.fold_with(&mut SuperFieldAccessFolder {
+        class_name,
+        vars: &mut vars,
+        constructor_this_mark: None,
+        is_static,
+        folding_constructor: false,
+        in_injected_define_property_call: false,
+        in_nested_scope: false,
+        this_alias_mark: None,
+    });
+
+    if !vars.is_empty() {
+        if let Some(ref mut body) = function.body {
+            prepend(
+                &mut body.stmts,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vars,
+                })),
+            );
+        }
+    }
+
+    function
+}
+
+/// `var _foo = new WeakSet();`
+fn new_weak_var(ident: Ident, ctor: &str) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            definite: false,
+            name: Pat::Ident(ident),
+            init: Some(box Expr::New(NewExpr {
+                span: DUMMY_SP,
+                callee: box Expr::Ident(quote_ident!(ctor)),
+                args: Some(vec![]),
+                type_args: Default::default(),
+            })),
+        }],
+    }))
+}
@@ -7,12 +7,49 @@ use swc_common::{Fold, FoldWith, Mark, Spanned, DUMMY_SP};
 
 pub(super) struct FieldAccessFolder<'a> {
     pub mark: Mark,
+    /// Mark of functions of private methods.
+    pub method_mark: Mark,
     pub class_name: &'a Ident,
     pub vars: Vec<VarDeclarator>,
     pub statics: &'a HashSet<JsWord>,
+    /// Private methods, excluding getters and setters.
+    pub methods: &'a HashSet<JsWord>,
     pub in_assign_pat: bool,
 }
 
+/// Name of the function which holds the body of a non-static private method.
+///
+/// The private name itself is used for the `WeakSet` which brands instances.
+/// `mark` should differ from the mark of private fields, as `#foo_method` is a
+/// valid name of a field.
+pub(super) fn method_fn_ident(n: &PrivateName, mark: Mark) -> Ident {
+    Ident::new(
+        format!("_{}_method", n.id.sym).into(),
+        n.id.span.apply_mark(mark),
+    )
+}
+
+/// `_classPrivateMethodSet()`, which always throws.
+///
+/// `exprs` are evaluated before the error is thrown.
+fn private_method_set(mut exprs: Vec<Box<Expr>>) -> Expr {
+    let set = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(class_private_method_set, "classPrivateMethodSet"),
+        args: vec![],
+        type_args: Default::default(),
+    });
+    if exprs.is_empty() {
+        return set;
+    }
+
+    exprs.push(box set);
+    Expr::Seq(SeqExpr {
+        span: DUMMY_SP,
+        exprs,
+    })
+}
+
 impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
     fn fold(&mut self, e: Expr) -> Expr {
         match e {
@@ -48,6 +85,14 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     ExprOrSuper::Expr(ref obj) => obj.clone(),
                 };
 
+                if self.methods.contains(&n.id.sym) {
+                    let exprs = match *obj {
+                        Expr::This(..) => vec![],
+                        _ => vec![obj.fold_with(self)],
+                    };
+                    return private_method_set(exprs);
+                }
+
                 let is_static = self.statics.contains(&n.id.sym);
                 let ident = Ident::new(
                     format!("_{}", n.id.sym).into(),
@@ -199,6 +244,16 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     ExprOrSuper::Expr(ref obj) => obj.clone(),
                 };
 
+                // The object and the value are evaluated before the error is thrown.
+                if self.methods.contains(&n.id.sym) {
+                    let mut exprs = match *obj {
+                        Expr::This(..) => vec![],
+                        _ => vec![obj.fold_with(self)],
+                    };
+                    exprs.push(right.fold_with(self));
+                    return private_method_set(exprs);
+                }
+
                 let is_static = self.statics.contains(&n.id.sym);
                 let ident = Ident::new(
                     format!("_{}", n.id.sym).into(),
@@ -396,10 +451,17 @@ impl<'a> FieldAccessFolder<'a> {
         );
 
         if is_static {
-            let get = helper!(
-                class_static_private_field_spec_get,
-                "classStaticPrivateFieldSpecGet"
-            );
+            let get = if self.methods.contains(&n.id.sym) {
+                helper!(
+                    class_static_private_method_get,
+                    "classStaticPrivateMethodGet"
+                )
+            } else {
+                helper!(
+                    class_static_private_field_spec_get,
+                    "classStaticPrivateFieldSpecGet"
+                )
+            };
 
             (
                 Expr::Call(CallExpr {
@@ -438,14 +500,27 @@ impl<'a> FieldAccessFolder<'a> {
                 };
             }
 
-            let get = helper!(class_private_field_get, "classPrivateFieldGet");
+            let (get, method) = if self.methods.contains(&n.id.sym) {
+                (
+                    helper!(class_private_method_get, "classPrivateMethodGet"),
+                    Some(method_fn_ident(&n, self.method_mark)),
+                )
+            } else {
+                (
+                    helper!(class_private_field_get, "classPrivateFieldGet"),
+                    None,
+                )
+            };
 
             match *obj {
                 Expr::This(this) => (
                     CallExpr {
                         span: DUMMY_SP,
                         callee: get,
-                        args: vec![this.as_arg(), ident.as_arg()],
+                        args: vec![this.as_arg(), ident.as_arg()]
+                            .into_iter()
+                            .chain(method.map(|m| m.as_arg()))
+                            .collect(),
 
                         type_args: Default::default(),
                     }
@@ -489,7 +564,10 @@ impl<'a> FieldAccessFolder<'a> {
                                     }
                                 },
                                 ident.as_arg(),
-                            ],
+                            ]
+                            .into_iter()
+                            .chain(method.map(|m| m.as_arg()))
+                            .collect(),
 
                             type_args: Default::default(),
                         }
@@ -21,6 +21,7 @@ fn ts() -> Syntax {
 fn syntax() -> Syntax {
     Syntax::Es(EsConfig {
         class_private_props: true,
+        class_private_methods: true,
         class_props: true,
         ..Default::default()
     })
@@ -4849,3 +4850,207 @@ expect(foo.y).toBe('bar');
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_method,
+    r#"
+class Foo {
+  #foo() {
+    return this.x;
+  }
+
+  test() {
+    return this.#foo();
+  }
+
+  reassign() {
+    this.#foo = 1;
+    getObj().#foo = sideEffect();
+  }
+}
+"#,
+    r#"
+class Foo {
+  test() {
+    return _classPrivateMethodGet(this, _foo, _foo_method).call(this);
+  }
+
+  reassign() {
+    1, _classPrivateMethodSet();
+    getObj(), sideEffect(), _classPrivateMethodSet();
+  }
+
+  constructor() {
+    _foo.add(this);
+  }
+}
+
+var _foo_method = function () {
+  return this.x;
+};
+
+var _foo = new WeakSet();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_static_method_and_accessors,
+    r#"
+class Foo {
+  static #create() {
+    return new Foo();
+  }
+
+  get #value() {
+    return this._v;
+  }
+
+  set #value(v) {
+    this._v = v;
+  }
+
+  static make() {
+    const f = Foo.#create();
+    f.#value = 2;
+    return f.#value;
+  }
+}
+"#,
+    r#"
+class Foo {
+  static make() {
+    const f = _classStaticPrivateMethodGet(Foo, Foo, _create).call(Foo);
+    _classPrivateFieldSet(f, _value, 2);
+    return _classPrivateFieldGet(f, _value);
+  }
+
+  constructor() {
+    _value.set(this, {
+      get: _get_value,
+      set: _set_value
+    });
+  }
+}
+
+var _create = function () {
+  return new Foo();
+};
+
+var _get_value = function () {
+  return this._v;
+};
+
+var _set_value = function (v) {
+  this._v = v;
+};
+
+var _value = new WeakMap();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    private_method_exec,
+    r#"
+class Foo {
+  #x = 1;
+
+  #inc() {
+    return ++this.#x;
+  }
+
+  get #double() {
+    return this.#x * 2;
+  }
+
+  static #make() {
+    return new Foo();
+  }
+
+  static run() {
+    const f = Foo.#make();
+    f.#inc();
+    expect(f.#double).toBe(4);
+    expect(() => {
+      f.#inc = null;
+    }).toThrow();
+    expect(() => Foo.prototype.check.call({})).toThrow();
+  }
+
+  check() {
+    return this.#inc();
+  }
+}
+
+Foo.run();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    private_method_name_collision,
+    r#"
+class Foo {
+  #foo_method = 1;
+  #get_value = 2;
+
+  #foo() {
+    return this.#foo_method;
+  }
+
+  get #value() {
+    return this.#get_value;
+  }
+
+  static run() {
+    const f = new Foo();
+    expect(f.#foo()).toBe(1);
+    expect(f.#value).toBe(2);
+  }
+}
+
+Foo.run();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_method_super,
+    r#"
+class Foo extends Bar {
+  #foo() {
+    return super.foo();
+  }
+
+  static #bar() {
+    return super.bar;
+  }
+}
+"#,
+    r#"
+class Foo extends Bar {
+  constructor(...args) {
+    super(...args);
+
+    _foo.add(this);
+  }
+}
+
+var _foo_method = function () {
+  return _get(_getPrototypeOf(Foo.prototype), "foo", this).call(this);
+};
+
+var _bar = function () {
+  return _get(_getPrototypeOf(Foo), "bar", this);
+};
+
+var _foo = new WeakSet();
+"#
+);
@@ -1,15 +1,16 @@
 use crate::{
-    pass::Pass,
+    pass::{Optional, Pass},
     util::{
         alias_ident_for, constructor::inject_after_super, prop_name_to_expr_value, undefined,
-        ExprFactory, IdentExt,
+        ExprFactory, IdentExt, HANDLER,
     },
 };
 use ast::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::iter;
-use swc_common::{Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
+use swc_common::{chain, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 
+mod legacy;
 #[cfg(test)]
 mod tests;
 
@@ -53,16 +54,43 @@ mod tests;
 ///   }
 /// }
 /// ```
+///
+/// # Legacy mode
+///
+/// If [Config::legacy] is true, decorators are lowered like TypeScript's
+/// `experimentalDecorators` does, using `_tsDecorate`. Decorators of
+/// constructor parameter properties are supported in this mode.
 pub fn decorators(c: Config) -> impl Pass {
-    Decorators {
-        c,
-        is_in_strict: false,
-    }
+    chain!(
+        Optional::new(legacy::new(c.emit_metadata), c.legacy),
+        Optional::new(
+            Decorators {
+                c,
+                is_in_strict: false,
+            },
+            !c.legacy
+        ),
+    )
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Config {
+    /// Use the semantics of TypeScript's `experimentalDecorators`.
+    #[serde(default)]
     pub legacy: bool,
+
+    /// babel: `decoratorsBeforeExport`
+    ///
+    /// This only affects parsing.
+    #[serde(default)]
+    pub decorators_before_export: bool,
+
+    /// TypeScript's `emitDecoratorMetadata`.
+    ///
+    /// Effective only if `legacy` is true.
+    #[serde(default)]
+    pub emit_metadata: bool,
 }
 
 struct Decorators {
@@ -95,6 +123,7 @@ impl Fold<Vec<ModuleItem>> for Decorators {
                         }),
                     ..
                 })) => {
+                    let class = class.fold_children(self);
                     let decorate_call = box self.fold_class(ident.clone(), class);
 
                     buf.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
@@ -214,6 +243,39 @@ impl Fold<Expr> for Decorators {
     }
 }
 
+/// Parameter decorators are not part of the proposal, so they are rejected
+/// instead of being printed as is.
+impl Fold<Param> for Decorators {
+    fn fold(&mut self, mut param: Param) -> Param {
+        for dec in param.decorators.drain(..) {
+            report_param_decorator(&dec);
+        }
+
+        param.fold_children(self)
+    }
+}
+
+impl Fold<TsParamProp> for Decorators {
+    fn fold(&mut self, mut param: TsParamProp) -> TsParamProp {
+        for dec in param.decorators.drain(..) {
+            report_param_decorator(&dec);
+        }
+
+        param.fold_children(self)
+    }
+}
+
+fn report_param_decorator(dec: &Decorator) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                dec.span,
+                "parameter decorators are only supported with `legacy: true`",
+            )
+            .emit()
+    });
+}
+
 impl Decorators {
     fn fold_class(&self, ident: Ident, mut class: Class) -> Expr {
         let initialize = private_ident!("_initialize");
@@ -254,11 +316,14 @@ impl Decorators {
                     is_optional: false,
                     accessibility: Default::default(),
                     params: if super_class_ident.is_some() {
-                        vec![PatOrTsParamProp::Pat(Pat::Rest(RestPat {
-                            dot3_token: DUMMY_SP,
-                            arg: box Pat::Ident(quote_ident!("args")),
-                            type_ann: Default::default(),
-                        }))]
+                        vec![ParamOrTsParamProp::Param(
+                            Pat::Rest(RestPat {
+                                dot3_token: DUMMY_SP,
+                                arg: box Pat::Ident(quote_ident!("args")),
+                                type_ann: Default::default(),
+                            })
+                            .into(),
+                        )]
                     } else {
                         vec![]
                     },
@@ -496,6 +561,7 @@ impl Decorators {
 
                         params: iter::once(Pat::Ident(initialize))
                             .chain(super_class_ident.map(Pat::Ident))
+                            .map(Param::from)
                             .collect(),
 
                         decorators: Default::default(),
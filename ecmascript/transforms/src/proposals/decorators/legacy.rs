@@ -0,0 +1,655 @@
+use super::contains_decorator;
+use crate::util::{alias_ident_for, undefined, ExprFactory, ModuleItemLike, StmtLike};
+use ast::*;
+use std::mem;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+/// TypeScript's `experimentalDecorators`.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```ts
+/// @dec
+/// class Foo {
+///   @method
+///   bar(@param a: string) {}
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// let Foo = class Foo {
+///   bar(a) {}
+/// };
+/// _tsDecorate([method, _tsParam(0, param)], Foo.prototype, "bar", null);
+/// Foo = _tsDecorate([dec], Foo);
+/// ```
+pub(super) fn new(metadata: bool) -> Legacy {
+    Legacy { metadata }
+}
+
+pub(super) struct Legacy {
+    /// Emit `design:type`, `design:paramtypes` and `design:returntype`.
+    metadata: bool,
+}
+
+impl<T> Fold<Vec<T>> for Legacy
+where
+    T: StmtLike + ModuleItemLike + FoldWith<Self>,
+{
+    fn fold(&mut self, items: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(items.len());
+
+        for item in items {
+            let item = item.fold_children(self);
+
+            match T::try_into_stmt(item) {
+                Ok(Stmt::Decl(Decl::Class(ClassDecl {
+                    ident,
+                    class,
+                    declare: false,
+                }))) => {
+                    if !contains_decorator(&class) {
+                        buf.push(T::from_stmt(Stmt::Decl(Decl::Class(ClassDecl {
+                            ident,
+                            class,
+                            declare: false,
+                        }))));
+                        continue;
+                    }
+
+                    let (vars, decl, stmts) = self.fold_class(ident, class);
+                    if !vars.is_empty() {
+                        buf.push(T::from_stmt(var_decl(vars)));
+                    }
+                    buf.push(T::from_stmt(Stmt::Decl(decl)));
+                    buf.extend(stmts.into_iter().map(T::from_stmt));
+                }
+                Ok(stmt) => buf.push(T::from_stmt(stmt)),
+                Err(item) => match item.try_into_module_decl() {
+                    Ok(ModuleDecl::ExportDecl(ExportDecl {
+                        span,
+                        decl:
+                            Decl::Class(ClassDecl {
+                                ident,
+                                class,
+                                declare: false,
+                            }),
+                    })) => {
+                        if !contains_decorator(&class) {
+                            let decl = Decl::Class(ClassDecl {
+                                ident,
+                                class,
+                                declare: false,
+                            });
+                            buf.push(export(ExportDecl { span, decl }));
+                            continue;
+                        }
+
+                        let (vars, decl, stmts) = self.fold_class(ident, class);
+                        if !vars.is_empty() {
+                            buf.push(T::from_stmt(var_decl(vars)));
+                        }
+                        buf.push(export(ExportDecl { span, decl }));
+                        buf.extend(stmts.into_iter().map(T::from_stmt));
+                    }
+                    Ok(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                        span,
+                        decl: DefaultDecl::Class(ClassExpr { ident, class }),
+                    })) => {
+                        if !contains_decorator(&class) {
+                            buf.push(export(ExportDefaultDecl {
+                                span,
+                                decl: DefaultDecl::Class(ClassExpr { ident, class }),
+                            }));
+                            continue;
+                        }
+
+                        let ident = ident.unwrap_or_else(|| private_ident!("_class"));
+                        let (vars, decl, stmts) = self.fold_class(ident.clone(), class);
+                        if !vars.is_empty() {
+                            buf.push(T::from_stmt(var_decl(vars)));
+                        }
+                        buf.push(T::from_stmt(Stmt::Decl(decl)));
+                        buf.extend(stmts.into_iter().map(T::from_stmt));
+                        // export { Foo as default }
+                        buf.push(export(NamedExport {
+                            span,
+                            specifiers: vec![NamedExportSpecifier {
+                                span: DUMMY_SP,
+                                orig: ident,
+                                exported: Some(quote_ident!("default")),
+                            }
+                            .into()],
+                            src: None,
+                        }));
+                    }
+                    Ok(decl) => buf.push(export(decl)),
+                    Err(..) => unreachable!(),
+                },
+            }
+        }
+
+        buf
+    }
+}
+
+impl Fold<Expr> for Legacy {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            Expr::Class(ClassExpr { ident, class }) => {
+                if !contains_decorator(&class) {
+                    return Expr::Class(ClassExpr { ident, class });
+                }
+
+                let ident = ident.unwrap_or_else(|| private_ident!("_class"));
+                let (vars, decl, extra_stmts) = self.fold_class(ident.clone(), class);
+
+                let mut stmts = vec![];
+                if !vars.is_empty() {
+                    stmts.push(var_decl(vars));
+                }
+                stmts.push(Stmt::Decl(decl));
+                stmts.extend(extra_stmts);
+                stmts.push(Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box Expr::Ident(ident)),
+                }));
+
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: FnExpr {
+                        ident: None,
+                        function: Function {
+                            span: DUMMY_SP,
+                            decorators: vec![],
+                            is_async: false,
+                            is_generator: false,
+                            params: vec![],
+                            body: Some(BlockStmt {
+                                span: DUMMY_SP,
+                                stmts,
+                            }),
+                            type_params: Default::default(),
+                            return_type: Default::default(),
+                        },
+                    }
+                    .as_callee(),
+                    args: vec![],
+                    type_args: Default::default(),
+                })
+            }
+            _ => expr,
+        }
+    }
+}
+
+impl Legacy {
+    /// Returns `(vars, class, decorate_calls)`.
+    ///
+    /// If the class itself is decorated, `class` is `let Foo = class Foo {}`.
+    fn fold_class(
+        &mut self,
+        ident: Ident,
+        mut class: Class,
+    ) -> (Vec<VarDeclarator>, Decl, Vec<Stmt>) {
+        let mut vars = vec![];
+        let mut stmts = vec![];
+        let mut class_decorators: Vec<_> = mem::replace(&mut class.decorators, vec![])
+            .into_iter()
+            .map(|dec| dec.expr)
+            .collect();
+        let mut ctor_param_types = None;
+
+        let mut body = Vec::with_capacity(class.body.len());
+        for member in class.body {
+            match member {
+                ClassMember::Method(mut method) => {
+                    let decorators = mem::replace(&mut method.function.decorators, vec![]);
+                    let param_decorators: Vec<_> = method
+                        .function
+                        .params
+                        .iter_mut()
+                        .map(|param| mem::replace(&mut param.decorators, vec![]))
+                        .collect();
+                    if decorators.is_empty() && param_decorators.iter().all(Vec::is_empty) {
+                        body.push(ClassMember::Method(method));
+                        continue;
+                    }
+
+                    let (key, key_expr) = member_key(&mut vars, method.key);
+                    method.key = key;
+
+                    let mut decorators: Vec<_> = decorators.into_iter().map(|d| d.expr).collect();
+                    decorators.extend(param_decorators_of(param_decorators));
+                    if self.metadata {
+                        decorators.extend(method_metadata(&method));
+                    }
+                    stmts.push(decorate(
+                        decorators,
+                        vec![
+                            target(&ident, method.is_static).as_arg(),
+                            key_expr.as_arg(),
+                            Lit::Null(Null { span: DUMMY_SP }).as_arg(),
+                        ],
+                    ));
+
+                    body.push(ClassMember::Method(method));
+                }
+
+                ClassMember::ClassProp(mut prop) => {
+                    let decorators = mem::replace(&mut prop.decorators, vec![]);
+                    if decorators.is_empty() {
+                        body.push(ClassMember::ClassProp(prop));
+                        continue;
+                    }
+
+                    let key_expr = match *prop.key {
+                        Expr::Ident(ref i) if !prop.computed => {
+                            box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym.clone())))
+                        }
+                        Expr::Lit(ref lit) => box Expr::Lit(lit.clone()),
+                        _ => {
+                            let alias = alias_ident_for(&prop.key, "_key");
+                            prop.key = box Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                                op: op!("="),
+                                right: prop.key,
+                            });
+                            vars.push(alias_var(alias.clone()));
+                            box Expr::Ident(alias)
+                        }
+                    };
+
+                    let mut decorators: Vec<_> = decorators.into_iter().map(|d| d.expr).collect();
+                    if self.metadata {
+                        decorators.push(metadata(
+                            "design:type",
+                            serialize_type_ann(prop.type_ann.as_ref()),
+                        ));
+                    }
+                    stmts.push(decorate(
+                        decorators,
+                        vec![
+                            target(&ident, prop.is_static).as_arg(),
+                            key_expr.as_arg(),
+                            undefined(DUMMY_SP).as_arg(),
+                        ],
+                    ));
+
+                    body.push(ClassMember::ClassProp(prop));
+                }
+
+                ClassMember::Constructor(mut c) => {
+                    let param_decorators = c
+                        .params
+                        .iter_mut()
+                        .map(|param| match *param {
+                            ParamOrTsParamProp::Param(ref mut p) => {
+                                mem::replace(&mut p.decorators, vec![])
+                            }
+                            ParamOrTsParamProp::TsParamProp(ref mut p) => {
+                                mem::replace(&mut p.decorators, vec![])
+                            }
+                        })
+                        .collect();
+                    class_decorators.extend(param_decorators_of(param_decorators));
+
+                    ctor_param_types = Some(
+                        c.params
+                            .iter()
+                            .map(|param| match *param {
+                                ParamOrTsParamProp::Param(ref p) => {
+                                    serialize_type_ann(param_type(&p.pat))
+                                }
+                                ParamOrTsParamProp::TsParamProp(TsParamProp {
+                                    param: TsParamPropParam::Ident(ref i),
+                                    ..
+                                }) => serialize_type_ann(i.type_ann.as_ref()),
+                                ParamOrTsParamProp::TsParamProp(TsParamProp {
+                                    param: TsParamPropParam::Assign(ref a),
+                                    ..
+                                }) => serialize_type_ann(param_type(&a.left)),
+                            })
+                            .collect(),
+                    );
+
+                    body.push(ClassMember::Constructor(c));
+                }
+
+                _ => body.push(member),
+            }
+        }
+
+        let class = Class {
+            decorators: vec![],
+            body,
+            ..class
+        };
+
+        if class_decorators.is_empty() {
+            return (
+                vars,
+                Decl::Class(ClassDecl {
+                    ident,
+                    class,
+                    declare: false,
+                }),
+                stmts,
+            );
+        }
+
+        if self.metadata {
+            if let Some(types) = ctor_param_types {
+                class_decorators.push(metadata("design:paramtypes", array(types)));
+            }
+        }
+
+        // Foo = _tsDecorate([dec], Foo);
+        stmts.push(
+            AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                op: op!("="),
+                right: box decorate_call(class_decorators, vec![ident.clone().as_arg()]),
+            }
+            .into_stmt(),
+        );
+
+        (
+            vars,
+            Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Let,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(ident.clone()),
+                    init: Some(box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    })),
+                    definite: false,
+                }],
+            }),
+            stmts,
+        )
+    }
+}
+
+fn export<T, D>(decl: D) -> T
+where
+    T: ModuleItemLike,
+    D: Into<ModuleDecl>,
+{
+    match T::try_from_module_decl(decl.into()) {
+        Ok(t) => t,
+        Err(..) => unreachable!(),
+    }
+}
+
+fn var_decl(decls: Vec<VarDeclarator>) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls,
+    }))
+}
+
+fn alias_var(name: Ident) -> VarDeclarator {
+    VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(name),
+        init: None,
+        definite: false,
+    }
+}
+
+/// Returns `(key, key_expr)`, where `key_expr` is passed to `_tsDecorate`.
+///
+/// Computed keys are stored in a variable to preserve evaluation order.
+fn member_key(vars: &mut Vec<VarDeclarator>, key: PropName) -> (PropName, Box<Expr>) {
+    match key {
+        PropName::Ident(i) => {
+            let expr = box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym.clone())));
+            (PropName::Ident(i), expr)
+        }
+        PropName::Str(s) => (PropName::Str(s.clone()), box Expr::Lit(Lit::Str(s))),
+        PropName::Num(n) => (PropName::Num(n), box Expr::Lit(Lit::Num(n))),
+        PropName::Computed(ComputedPropName {
+            span,
+            expr: box Expr::Lit(lit),
+        }) => (
+            PropName::Computed(ComputedPropName {
+                span,
+                expr: box Expr::Lit(lit.clone()),
+            }),
+            box Expr::Lit(lit),
+        ),
+        PropName::Computed(ComputedPropName { span, expr }) => {
+            let alias = alias_ident_for(&expr, "_key");
+            vars.push(alias_var(alias.clone()));
+
+            (
+                PropName::Computed(ComputedPropName {
+                    span,
+                    expr: box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                        op: op!("="),
+                        right: expr,
+                    }),
+                }),
+                box Expr::Ident(alias),
+            )
+        }
+    }
+}
+
+/// `Foo` for static members and `Foo.prototype` otherwise.
+fn target(class_name: &Ident, is_static: bool) -> Expr {
+    if is_static {
+        Expr::Ident(class_name.clone())
+    } else {
+        class_name.clone().member(quote_ident!("prototype"))
+    }
+}
+
+fn decorate_call(decorators: Vec<Box<Expr>>, args: Vec<ExprOrSpread>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(ts_decorate, "tsDecorate"),
+        args: Some(array(decorators).as_arg())
+            .into_iter()
+            .chain(args)
+            .collect(),
+        type_args: Default::default(),
+    })
+}
+
+fn decorate(decorators: Vec<Box<Expr>>, args: Vec<ExprOrSpread>) -> Stmt {
+    decorate_call(decorators, args).into_stmt()
+}
+
+/// `_tsParam(idx, dec)`
+fn param_decorator(idx: usize, dec: Decorator) -> Box<Expr> {
+    box Expr::Call(CallExpr {
+        span: dec.span,
+        callee: helper!(ts_param, "tsParam"),
+        args: vec![
+            Lit::Num(Number {
+                span: DUMMY_SP,
+                value: idx as f64,
+            })
+            .as_arg(),
+            dec.expr.as_arg(),
+        ],
+        type_args: Default::default(),
+    })
+}
+
+/// `_tsParam(idx, dec)` for each decorator of each parameter.
+fn param_decorators_of(param_decorators: Vec<Vec<Decorator>>) -> impl Iterator<Item = Box<Expr>> {
+    param_decorators
+        .into_iter()
+        .enumerate()
+        .flat_map(|(idx, decorators)| {
+            decorators
+                .into_iter()
+                .map(move |dec| param_decorator(idx, dec))
+        })
+}
+
+/// `_tsMetadata(key, value)`
+fn metadata(key: &str, value: Box<Expr>) -> Box<Expr> {
+    box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(ts_metadata, "tsMetadata"),
+        args: vec![Lit::Str(quote_str!(key)).as_arg(), value.as_arg()],
+        type_args: Default::default(),
+    })
+}
+
+fn method_metadata(method: &ClassMethod) -> Vec<Box<Expr>> {
+    let param_types = || {
+        array(
+            method
+                .function
+                .params
+                .iter()
+                .map(|p| serialize_type_ann(param_type(&p.pat)))
+                .collect(),
+        )
+    };
+
+    match method.kind {
+        MethodKind::Method => vec![
+            metadata("design:type", box Expr::Ident(quote_ident!("Function"))),
+            metadata("design:paramtypes", param_types()),
+            metadata(
+                "design:returntype",
+                match method.function.return_type {
+                    Some(ref ty) => serialize_type(&ty.type_ann),
+                    None => undefined(DUMMY_SP),
+                },
+            ),
+        ],
+        MethodKind::Getter => vec![
+            metadata(
+                "design:type",
+                serialize_type_ann(method.function.return_type.as_ref()),
+            ),
+            metadata("design:paramtypes", param_types()),
+        ],
+        MethodKind::Setter => vec![
+            metadata(
+                "design:type",
+                serialize_type_ann(
+                    method
+                        .function
+                        .params
+                        .first()
+                        .and_then(|p| param_type(&p.pat)),
+                ),
+            ),
+            metadata("design:paramtypes", param_types()),
+        ],
+    }
+}
+
+fn array(elems: Vec<Box<Expr>>) -> Box<Expr> {
+    box Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: elems.into_iter().map(|e| Some(e.as_arg())).collect(),
+    })
+}
+
+fn param_type(p: &Pat) -> Option<&TsTypeAnn> {
+    match *p {
+        Pat::Ident(ref i) => i.type_ann.as_ref(),
+        Pat::Array(ArrayPat { ref type_ann, .. })
+        | Pat::Object(ObjectPat { ref type_ann, .. })
+        | Pat::Rest(RestPat { ref type_ann, .. }) => type_ann.as_ref(),
+        Pat::Assign(AssignPat {
+            ref type_ann,
+            ref left,
+            ..
+        }) => type_ann.as_ref().or_else(|| param_type(left)),
+        _ => None,
+    }
+}
+
+fn serialize_type_ann(ty: Option<&TsTypeAnn>) -> Box<Expr> {
+    match ty {
+        Some(ty) => serialize_type(&ty.type_ann),
+        None => box Expr::Ident(quote_ident!("Object")),
+    }
+}
+
+/// Converts a type to the runtime value used by `emitDecoratorMetadata`.
+fn serialize_type(ty: &TsType) -> Box<Expr> {
+    let global = |name: &str| box Expr::Ident(quote_ident!(name));
+
+    match *ty {
+        TsType::TsKeywordType(TsKeywordType { kind, .. }) => match kind {
+            TsKeywordTypeKind::TsNumberKeyword => global("Number"),
+            TsKeywordTypeKind::TsStringKeyword => global("String"),
+            TsKeywordTypeKind::TsBooleanKeyword => global("Boolean"),
+            TsKeywordTypeKind::TsSymbolKeyword => global("Symbol"),
+            TsKeywordTypeKind::TsVoidKeyword
+            | TsKeywordTypeKind::TsUndefinedKeyword
+            | TsKeywordTypeKind::TsNullKeyword
+            | TsKeywordTypeKind::TsNeverKeyword => undefined(DUMMY_SP),
+            _ => global("Object"),
+        },
+        TsType::TsLitType(TsLitType { ref lit, .. }) => match *lit {
+            TsLit::Number(..) => global("Number"),
+            TsLit::Str(..) => global("String"),
+            TsLit::Bool(..) => global("Boolean"),
+        },
+        TsType::TsArrayType(..) | TsType::TsTupleType(..) => global("Array"),
+        TsType::TsFnOrConstructorType(..) => global("Function"),
+        TsType::TsParenthesizedType(TsParenthesizedType { ref type_ann, .. })
+        | TsType::TsOptionalType(TsOptionalType { ref type_ann, .. }) => serialize_type(type_ann),
+        TsType::TsTypeRef(TsTypeRef { ref type_name, .. }) => {
+            // typeof Foo === "undefined" ? Object : Foo
+            box Expr::Cond(CondExpr {
+                span: DUMMY_SP,
+                test: box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("typeof"),
+                        arg: box Expr::Ident(root_ident(type_name).clone()),
+                    }),
+                    op: op!("==="),
+                    right: box Expr::Lit(Lit::Str(quote_str!("undefined"))),
+                }),
+                cons: global("Object"),
+                alt: box entity_to_expr(type_name),
+            })
+        }
+        _ => global("Object"),
+    }
+}
+
+fn root_ident(n: &TsEntityName) -> &Ident {
+    match *n {
+        TsEntityName::Ident(ref i) => i,
+        TsEntityName::TsQualifiedName(ref q) => root_ident(&q.left),
+    }
+}
+
+fn entity_to_expr(n: &TsEntityName) -> Expr {
+    match *n {
+        TsEntityName::Ident(ref i) => Expr::Ident(i.clone()),
+        TsEntityName::TsQualifiedName(ref q) => entity_to_expr(&q.left).member(q.right.clone()),
+    }
+}
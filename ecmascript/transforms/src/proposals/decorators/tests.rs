@@ -2,6 +2,7 @@ use super::*;
 use crate::{
     proposals::{class_properties, decorators},
     resolver::resolver,
+    tests::Tester,
     typescript,
 };
 use swc_common::chain;
@@ -2027,7 +2028,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_constructors_return_new_constructor_exec,
@@ -2056,7 +2060,10 @@ test!(
     syntax(false),
     |_| chain!(
         typescript::strip(),
-        decorators(decorators::Config { legacy: true })
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        })
     ),
     legacy_regression_10264,
     r#"
@@ -2463,7 +2470,10 @@ test!(
     // legacy decorator: https://github.com/swc-project/swc/issues/421
     ignore,
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_decl_to_expression_class_decorators,
     r#"
 export default @dec class A {}
@@ -2487,7 +2497,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_numeric_props_exec,
@@ -2512,7 +2525,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_properties_mutate_descriptor_exec,
@@ -2626,7 +2642,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_string_props_exec,
@@ -2651,7 +2670,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_string_literal_properties_exec,
@@ -2695,7 +2717,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_mutate_descriptor_exec,
@@ -2825,7 +2850,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_numeric_props_exec,
@@ -2849,7 +2877,10 @@ test!(
     // legacy decorator: https://github.com/swc-project/swc/issues/421
     ignore,
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_decl_to_expression_method_decorators,
     r#"
 export default class A {
@@ -2883,7 +2914,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_return_descriptor_exec,
@@ -2997,7 +3031,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_string_props_exec,
@@ -3022,7 +3059,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_return_descriptor_exec,
@@ -3134,7 +3174,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_string_props_exec,
@@ -3159,7 +3202,10 @@ test!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_regression_8041,
@@ -3191,7 +3237,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_return_descriptor_exec,
@@ -3323,7 +3372,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_ordering_reverse_order_exec,
@@ -3364,7 +3416,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_numeric_props_exec,
@@ -3390,7 +3445,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_properties_return_descriptor_exec,
@@ -3504,7 +3562,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_export_default_exec,
@@ -3533,7 +3594,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_ordering_reverse_order_exec,
@@ -3577,7 +3641,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_mutate_descriptor_exec,
@@ -3705,7 +3772,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_return_descriptor_exec,
@@ -3834,7 +3904,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_return_descriptor_exec,
@@ -3962,7 +4035,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_string_props_exec,
@@ -3989,7 +4065,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_child_classes_properties_exec,
@@ -4031,7 +4110,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_mutate_descriptor_exec,
@@ -4158,7 +4240,10 @@ test_exec!(
     // legacy decorator: https://github.com/swc-project/swc/issues/421
     ignore,
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_regression_8512_exec,
     r#"
 function dec(Class, key, desc) {
@@ -4172,3 +4257,215 @@ class Foo {
 
 "#
 );
+
+fn ts_legacy(emit_metadata: bool) -> impl Fold<Module> {
+    chain!(
+        decorators(Config {
+            legacy: true,
+            emit_metadata,
+            ..Default::default()
+        }),
+        typescript::strip(),
+    )
+}
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| ts_legacy(false),
+    ts_legacy_class_and_members,
+    r#"
+@dec
+class Foo {
+  @prop
+  x: string;
+
+  constructor(@inject("a") private a: string) {}
+
+  @method
+  static bar() {}
+}
+"#,
+    r#"
+let Foo = class Foo {
+  constructor(a) {
+    this.a = a;
+  }
+
+  static bar() {}
+};
+
+_tsDecorate([prop], Foo.prototype, "x", void 0);
+_tsDecorate([method], Foo, "bar", null);
+Foo = _tsDecorate([dec, _tsParam(0, inject("a"))], Foo);
+"#
+);
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| ts_legacy(false),
+    ts_legacy_export_and_computed_key,
+    r#"
+@dec
+export default class {
+  @method
+  [key()]() {}
+}
+"#,
+    r#"
+var _key;
+
+let _class = class _class {
+  [_key = key()]() {}
+};
+
+_tsDecorate([method], _class.prototype, _key, null);
+_class = _tsDecorate([dec], _class);
+export { _class as default };
+"#
+);
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| ts_legacy(true),
+    ts_legacy_metadata,
+    r#"
+class Foo {
+  @dec
+  method(a: string, b: Bar): number {
+    return 1;
+  }
+
+  @dec
+  prop: Bar[];
+}
+"#,
+    r#"
+class Foo {
+  method(a, b) {
+    return 1;
+  }
+}
+
+_tsDecorate([
+  dec,
+  _tsMetadata("design:type", Function),
+  _tsMetadata("design:paramtypes", [String, typeof Bar === "undefined" ? Object : Bar]),
+  _tsMetadata("design:returntype", Number)
+], Foo.prototype, "method", null);
+_tsDecorate([dec, _tsMetadata("design:type", Array)], Foo.prototype, "prop", void 0);
+"#
+);
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| ts_legacy(false),
+    ts_legacy_constructor_param,
+    r#"
+class Foo {
+  constructor(@Inject(A) a, b, @Inject(C) private c) {}
+}
+"#,
+    r#"
+let Foo = class Foo {
+  constructor(a, b, c) {
+    this.c = c;
+  }
+};
+
+Foo = _tsDecorate([_tsParam(0, Inject(A)), _tsParam(2, Inject(C))], Foo);
+"#
+);
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| ts_legacy(false),
+    ts_legacy_method_param,
+    r#"
+class Foo {
+  m(a, @Arg() x) {}
+
+  @method
+  static n(@Arg() y) {}
+}
+"#,
+    r#"
+class Foo {
+  m(a, x) {}
+
+  static n(y) {}
+}
+
+_tsDecorate([_tsParam(1, Arg())], Foo.prototype, "m", null);
+_tsDecorate([method, _tsParam(0, Arg())], Foo, "n", null);
+"#
+);
+
+test_exec!(
+    syntax(false),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
+    legacy_order_exec,
+    r#"
+const calls = [];
+
+function dec(name) {
+  return function (target, key, desc) {
+    calls.push(name);
+    expect(key).toBe("method");
+    return desc;
+  };
+}
+
+function cls(C) {
+  C.decorated = true;
+}
+
+@cls
+class Foo {
+  @dec("a")
+  @dec("b")
+  method() {
+    return 1;
+  }
+}
+
+expect(calls).toEqual(["b", "a"]);
+expect(Foo.decorated).toBe(true);
+expect(new Foo().method()).toBe(1);
+"#
+);
+
+#[test]
+fn param_decorator_without_legacy() {
+    Tester::run(|tester| {
+        tester.apply_transform(
+            tr(),
+            "input.js",
+            syntax(false),
+            "class Foo {
+  m(@dec x) {}
+}",
+        )?;
+
+        assert_eq!(tester.handler.err_count(), 1);
+
+        Ok(())
+    })
+}
@@ -1,6 +1,6 @@
 pub use self::{
     display_name::display_name,
-    jsx::{jsx, Options},
+    jsx::{jsx, Options, Runtime},
     jsx_self::jsx_self,
     jsx_src::jsx_src,
 };
@@ -19,10 +19,13 @@ mod jsx_src;
 pub fn react(cm: Arc<SourceMap>, options: Options) -> impl Pass {
     let Options { development, .. } = options;
 
+    // `__source` and `__self` are added before `jsx` because the runtime can be
+    // changed by a `@jsxRuntime` pragma. The automatic runtime passes them as
+    // arguments and the classic runtime passes them as props.
     chain!(
-        jsx(options),
-        display_name(),
         jsx_src(development, cm),
-        jsx_self(development)
+        jsx_self(development),
+        jsx(options),
+        display_name()
     )
 }
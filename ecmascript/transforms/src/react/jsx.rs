@@ -3,7 +3,7 @@ use crate::{
     util::{
         drop_span,
         options::{CM, SESSION},
-        ExprFactory, HANDLER,
+        prepend_stmts, undefined, ExprFactory, COMMENTS, HANDLER,
     },
 };
 use ast::*;
@@ -13,7 +13,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{iter, mem, sync::Arc};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Spanned, DUMMY_SP};
+use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Span, Spanned, DUMMY_SP};
 use swc_ecma_parser::{Parser, SourceFileInput, Syntax};
 
 #[cfg(test)]
@@ -22,6 +22,13 @@ mod tests;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
+    #[serde(default)]
+    pub runtime: Runtime,
+
+    /// Module which provides `jsx-runtime` for the automatic runtime.
+    #[serde(default = "default_import_source")]
+    pub import_source: String,
+
     #[serde(default = "default_pragma")]
     pub pragma: String,
     #[serde(default = "default_pragma_frag")]
@@ -40,6 +47,8 @@ pub struct Options {
 impl Default for Options {
     fn default() -> Self {
         Options {
+            runtime: Default::default(),
+            import_source: default_import_source(),
             pragma: default_pragma(),
             pragma_frag: default_pragma_frag(),
             throw_if_namespace: default_throw_if_namespace(),
@@ -49,6 +58,26 @@ impl Default for Options {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Runtime {
+    /// `React.createElement(...)`
+    #[serde(rename = "classic")]
+    Classic,
+    /// `_jsx(...)` with an import from `<importSource>/jsx-runtime`
+    #[serde(rename = "automatic")]
+    Automatic,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::Classic
+    }
+}
+
+fn default_import_source() -> String {
+    "react".into()
+}
+
 fn default_pragma() -> String {
     "React.createElement".into()
 }
@@ -99,27 +128,181 @@ fn parse_option(name: &str, src: String) -> Box<Expr> {
 /// Turn JSX into React function calls
 pub fn jsx(options: Options) -> impl Pass {
     Jsx {
+        runtime: options.runtime,
+        import_source: options.import_source.into(),
         pragma: ExprOrSuper::Expr(parse_option("pragma", options.pragma)),
         pragma_frag: ExprOrSpread {
             spread: None,
             expr: parse_option("pragmaFrag", options.pragma_frag),
         },
+        development: options.development,
         use_builtins: options.use_builtins,
         throw_if_namespace: options.throw_if_namespace,
+        import_jsx: None,
+        import_jsxs: None,
+        import_fragment: None,
+        import_create_element: None,
     }
 }
 
 struct Jsx {
+    runtime: Runtime,
+    import_source: JsWord,
     pragma: ExprOrSuper,
     pragma_frag: ExprOrSpread,
+    development: bool,
     use_builtins: bool,
     throw_if_namespace: bool,
+
+    /// `jsx` or `jsxDEV` from the jsx runtime.
+    import_jsx: Option<Ident>,
+    import_jsxs: Option<Ident>,
+    import_fragment: Option<Ident>,
+    /// `createElement` from the import source, used if `key` comes after a
+    /// spread.
+    import_create_element: Option<Ident>,
 }
 
 impl Jsx {
+    /// Applies `@jsxRuntime`, `@jsxImportSource`, `@jsx` and `@jsxFrag`
+    /// comments at the start of the file.
+    fn apply_pragmas(&mut self, module: &Module) {
+        lazy_static! {
+            static ref RUNTIME: Regex = Regex::new(r"@jsxRuntime\s+(\S+)").unwrap();
+            static ref IMPORT_SOURCE: Regex = Regex::new(r"@jsxImportSource\s+(\S+)").unwrap();
+            static ref PRAGMA: Regex = Regex::new(r"@jsx\s+(\S+)").unwrap();
+            static ref PRAGMA_FRAG: Regex = Regex::new(r"@jsxFrag\s+(\S+)").unwrap();
+        }
+
+        if !COMMENTS.is_set() {
+            return;
+        }
+
+        let positions = iter::once(module.span.lo())
+            .chain(module.body.first().map(|item| item.span().lo()))
+            .collect::<Vec<_>>();
+
+        COMMENTS.with(|comments| {
+            let mut pragma_span = None;
+
+            for (i, &pos) in positions.iter().enumerate() {
+                if positions[..i].contains(&pos) {
+                    continue;
+                }
+                let cmts = match comments.leading_comments(pos) {
+                    Some(cmts) => cmts,
+                    None => continue,
+                };
+
+                for cmt in cmts.iter() {
+                    if let Some(c) = RUNTIME.captures(&cmt.text) {
+                        match &c[1] {
+                            "classic" => self.runtime = Runtime::Classic,
+                            "automatic" => self.runtime = Runtime::Automatic,
+                            _ => HANDLER.with(|handler| {
+                                handler
+                                    .struct_span_err(
+                                        cmt.span,
+                                        "Runtime must be either `classic` or `automatic`",
+                                    )
+                                    .emit()
+                            }),
+                        }
+                    }
+                    if let Some(c) = IMPORT_SOURCE.captures(&cmt.text) {
+                        self.import_source = c[1].into();
+                    }
+                    if let Some(c) = PRAGMA.captures(&cmt.text) {
+                        pragma_span = Some(cmt.span);
+                        self.pragma = ExprOrSuper::Expr(parse_option("pragma", c[1].into()));
+                    }
+                    if let Some(c) = PRAGMA_FRAG.captures(&cmt.text) {
+                        pragma_span = Some(cmt.span);
+                        self.pragma_frag.expr = parse_option("pragmaFrag", c[1].into());
+                    }
+                }
+            }
+
+            if let Some(span) = pragma_span {
+                if self.runtime == Runtime::Automatic {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                span,
+                                "pragma and pragmaFrag cannot be set when runtime is automatic",
+                            )
+                            .emit()
+                    });
+                }
+            }
+        });
+    }
+
+    /// Import declarations for the automatic runtime.
+    fn runtime_imports(&mut self) -> Vec<ModuleItem> {
+        let mut imports = vec![];
+
+        let runtime_specifiers = vec![
+            (
+                self.import_jsx.take(),
+                if self.development { "jsxDEV" } else { "jsx" },
+            ),
+            (self.import_jsxs.take(), "jsxs"),
+            (self.import_fragment.take(), "Fragment"),
+        ]
+        .into_iter()
+        .filter_map(|(local, imported)| {
+            local.map(|local| {
+                ImportSpecifier::Specific(ImportSpecific {
+                    span: DUMMY_SP,
+                    local,
+                    imported: Some(quote_ident!(imported)),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+        if !runtime_specifiers.is_empty() {
+            let src = if self.development {
+                format!("{}/jsx-dev-runtime", self.import_source)
+            } else {
+                format!("{}/jsx-runtime", self.import_source)
+            };
+
+            imports.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                span: DUMMY_SP,
+                specifiers: runtime_specifiers,
+                src: quote_str!(src),
+            })));
+        }
+
+        if let Some(local) = self.import_create_element.take() {
+            imports.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                span: DUMMY_SP,
+                specifiers: vec![ImportSpecifier::Specific(ImportSpecific {
+                    span: DUMMY_SP,
+                    local,
+                    imported: Some(quote_ident!("createElement")),
+                })],
+                src: quote_str!(self.import_source.clone()),
+            })));
+        }
+
+        imports
+    }
+
     fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
         let span = el.span();
 
+        if self.runtime == Runtime::Automatic {
+            let fragment = self
+                .import_fragment
+                .get_or_insert_with(|| private_ident!("_Fragment"))
+                .clone();
+
+            return self.jsx_runtime_call(span, box Expr::Ident(fragment), vec![], el.children);
+        }
+
         Expr::Call(CallExpr {
             span,
             callee: self.pragma.clone(),
@@ -142,17 +325,59 @@ impl Jsx {
 
         let name = self.jsx_name(el.opening.name);
 
+        if self.runtime == Runtime::Automatic {
+            // `createElement` is required to preserve the evaluation order of `key`.
+            let key_after_spread = el
+                .opening
+                .attrs
+                .iter()
+                .skip_while(|attr| match attr {
+                    JSXAttrOrSpread::SpreadElement(..) => false,
+                    _ => true,
+                })
+                .any(|attr| is_attr_named(attr, "key"));
+
+            if !key_after_spread {
+                return self.jsx_runtime_call(span, name, el.opening.attrs, el.children);
+            }
+
+            let create_element = self
+                .import_create_element
+                .get_or_insert_with(|| private_ident!("_createElement"))
+                .clone();
+
+            return self.create_element_call(
+                span,
+                create_element.as_callee(),
+                name,
+                el.opening.attrs,
+                el.children,
+            );
+        }
+
+        let pragma = self.pragma.clone();
+        self.create_element_call(span, pragma, name, el.opening.attrs, el.children)
+    }
+
+    fn create_element_call(
+        &mut self,
+        span: Span,
+        callee: ExprOrSuper,
+        name: Box<Expr>,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<JSXElementChild>,
+    ) -> Expr {
         Expr::Call(CallExpr {
             span,
-            callee: self.pragma.clone(),
+            callee,
             args: iter::once(name.as_arg())
                 .chain(iter::once({
                     // Attributes
-                    self.fold_attrs(el.opening.attrs).as_arg()
+                    self.fold_attrs(attrs).as_arg()
                 }))
                 .chain({
                     // Children
-                    el.children
+                    children
                         .into_iter()
                         .filter_map(|c| self.jsx_elem_child_to_expr(c))
                 })
@@ -161,6 +386,105 @@ impl Jsx {
         })
     }
 
+    /// `_jsx(name, { ...attrs, children }, key)`
+    ///
+    /// In development mode, `_jsxDEV(name, props, key, isStaticChildren,
+    /// __source, __self)` is used instead.
+    fn jsx_runtime_call(
+        &mut self,
+        span: Span,
+        name: Box<Expr>,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<JSXElementChild>,
+    ) -> Expr {
+        let mut key = None;
+        let mut source = None;
+        let mut this = None;
+        let mut props = Vec::with_capacity(attrs.len() + 1);
+
+        for attr in attrs {
+            if is_attr_named(&attr, "key") {
+                key = Some(attr_value(attr));
+            } else if self.development && is_attr_named(&attr, "__source") {
+                source = Some(attr_value(attr));
+            } else if self.development && is_attr_named(&attr, "__self") {
+                this = Some(attr_value(attr));
+            } else {
+                props.push(attr);
+            }
+        }
+
+        let mut children = children
+            .into_iter()
+            .filter_map(|c| self.jsx_elem_child_to_expr(c))
+            .collect::<Vec<_>>();
+        let is_static = children.len() > 1;
+        let children = match children.len() {
+            0 => None,
+            1 => Some(children.remove(0).expr),
+            _ => Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: children.into_iter().map(Some).collect(),
+            })),
+        };
+        if let Some(children) = children {
+            props.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+                span: DUMMY_SP,
+                name: JSXAttrName::Ident(quote_ident!("children")),
+                value: Some(children),
+            }));
+        }
+
+        let props = if props.is_empty() {
+            box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            })
+        } else {
+            self.fold_attrs(props)
+        };
+
+        let callee = if is_static && !self.development {
+            self.import_jsxs
+                .get_or_insert_with(|| private_ident!("_jsxs"))
+                .clone()
+        } else {
+            let development = self.development;
+            self.import_jsx
+                .get_or_insert_with(|| {
+                    if development {
+                        private_ident!("_jsxDEV")
+                    } else {
+                        private_ident!("_jsx")
+                    }
+                })
+                .clone()
+        };
+
+        let mut args = vec![name.as_arg(), props.as_arg()];
+        if self.development {
+            args.push(key.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+            args.push(
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: is_static,
+                })
+                .as_arg(),
+            );
+            args.push(source.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+            args.push(this.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+        } else if let Some(key) = key {
+            args.push(key.as_arg());
+        }
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args,
+            type_args: None,
+        })
+    }
+
     fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
         Some(match c {
             JSXElementChild::JSXText(text) => {
@@ -260,6 +584,49 @@ impl Jsx {
     }
 }
 
+impl Fold<Module> for Jsx {
+    fn fold(&mut self, module: Module) -> Module {
+        // Pragmas apply only to this module, so options are restored afterwards.
+        let runtime = self.runtime;
+        let import_source = self.import_source.clone();
+        let pragma = self.pragma.clone();
+        let pragma_frag = self.pragma_frag.clone();
+        self.apply_pragmas(&module);
+
+        let mut module = module.fold_children(self);
+        if self.runtime == Runtime::Automatic {
+            let imports = self.runtime_imports();
+            prepend_stmts(&mut module.body, imports.into_iter());
+        }
+
+        self.runtime = runtime;
+        self.import_source = import_source;
+        self.pragma = pragma;
+        self.pragma_frag = pragma_frag;
+
+        module
+    }
+}
+
+impl Fold<Script> for Jsx {
+    fn fold(&mut self, script: Script) -> Script {
+        let script = script.fold_children(self);
+
+        if self.runtime == Runtime::Automatic && !self.runtime_imports().is_empty() {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        script.span,
+                        "The automatic jsx runtime can only be used in modules",
+                    )
+                    .emit()
+            });
+        }
+
+        script
+    }
+}
+
 impl Fold<Expr> for Jsx {
     fn fold(&mut self, expr: Expr) -> Expr {
         let expr = expr.fold_children(self);
@@ -359,14 +726,31 @@ impl Jsx {
     }
 }
 
+fn is_attr_named(attr: &JSXAttrOrSpread, name: &str) -> bool {
+    match *attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(ref i),
+            ..
+        }) => &*i.sym == name,
+        _ => false,
+    }
+}
+
+/// Value of a `JSXAttr`, where `<a b />` is `true`.
+fn attr_value(attr: JSXAttrOrSpread) -> Box<Expr> {
+    match attr {
+        JSXAttrOrSpread::JSXAttr(a) => attr_to_prop_value(a.span, a.value),
+        JSXAttrOrSpread::SpreadElement(..) => unreachable!(),
+    }
+}
+
+fn attr_to_prop_value(span: Span, value: Option<Box<Expr>>) -> Box<Expr> {
+    value.unwrap_or_else(|| box Expr::Lit(Lit::Bool(Bool { span, value: true })))
+}
+
 fn attr_to_prop(a: JSXAttr) -> Prop {
     let key = to_prop_name(a.name);
-    let value = a.value.unwrap_or_else(|| {
-        box Expr::Lit(Lit::Bool(Bool {
-            span: key.span(),
-            value: true,
-        }))
-    });
+    let value = attr_to_prop_value(key.span(), a.value);
     Prop::KeyValue(KeyValueProp { key, value })
 }
 
@@ -5,7 +5,7 @@ use crate::{
         es3::PropertyLiteral,
     },
     modules::common_js::common_js,
-    react::display_name,
+    react::{display_name, jsx_self, react},
 };
 use swc_common::chain;
 
@@ -1129,3 +1129,174 @@ fn jsx_text() {
     assert_eq!(jsx_text_to_str("Hello world".into()), *"Hello world");
     //    assert_eq!(jsx_text_to_str(" \n".into()), *" ");
 }
+
+fn automatic() -> Options {
+    Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| jsx(automatic()),
+    automatic_runtime,
+    r#"
+var x = <div className="a">{a}</div>;
+var y = <><A key="k" /><b /></>;
+"#,
+    r#"
+import { jsx as _jsx, jsxs as _jsxs, Fragment as _Fragment } from "react/jsx-runtime";
+var x = _jsx("div", {
+    className: "a",
+    children: a
+});
+var y = _jsxs(_Fragment, {
+    children: [_jsx(A, {}, "k"), _jsx("b", {})]
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| jsx(automatic()),
+    automatic_runtime_key_after_spread,
+    r#"
+var x = <div {...props} key="k" />;
+var y = <div key="k" {...props} />;
+"#,
+    r#"
+import { jsx as _jsx } from "react/jsx-runtime";
+import { createElement as _createElement } from "react";
+var x = _createElement("div", _extends({}, props, {
+    key: "k"
+}));
+var y = _jsx("div", _extends({}, props), "k");
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| chain!(
+        jsx_self(true),
+        jsx(Options {
+            development: true,
+            ..automatic()
+        })
+    ),
+    automatic_runtime_development,
+    r#"
+var x = <div><a /><b /></div>;
+"#,
+    r#"
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+var x = _jsxDEV("div", {
+    children: [
+        _jsxDEV("a", {}, void 0, false, void 0, this),
+        _jsxDEV("b", {}, void 0, false, void 0, this)
+    ]
+}, void 0, true, void 0, this);
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| jsx(Default::default()),
+    automatic_runtime_pragma,
+    r#"
+/** @jsxRuntime automatic @jsxImportSource preact */
+<div />;
+"#,
+    r#"
+import { jsx as _jsx } from "preact/jsx-runtime";
+_jsx("div", {});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| jsx(Default::default()),
+    classic_runtime_pragma,
+    r#"
+/** @jsx h */
+<div />;
+"#,
+    r#"
+h("div", null);
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| react(
+        t.cm.clone(),
+        Options {
+            development: true,
+            ..Default::default()
+        }
+    ),
+    automatic_runtime_pragma_development,
+    r#"
+/** @jsxRuntime automatic */
+<div />;
+"#,
+    r#"
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+_jsxDEV("div", {}, void 0, false, {
+    fileName: "input.js",
+    lineNumber: 3
+}, this);
+"#
+);
+
+#[test]
+fn pragmas_apply_to_one_file() {
+    crate::tests::Tester::run(|tester| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        let mut tr = jsx(Default::default());
+
+        let first = tester.apply_transform(
+            &mut tr,
+            "a.js",
+            syntax,
+            "/** @jsxRuntime automatic */\n<div />;",
+        )?;
+        let first = tester.print(&first);
+        assert!(first.contains("react/jsx-runtime"), "{}", first);
+
+        let second = tester.apply_transform(&mut tr, "b.js", syntax, "/** @jsx h */\n<div />;")?;
+        let second = tester.print(&second);
+        assert!(second.contains("h(\"div\", null)"), "{}", second);
+
+        let third = tester.apply_transform(&mut tr, "c.js", syntax, "<div />;")?;
+        let third = tester.print(&third);
+        assert!(
+            third.contains("React.createElement(\"div\", null)"),
+            "{}",
+            third
+        );
+
+        Ok(())
+    })
+}
@@ -1,7 +1,7 @@
 use crate::pass::Pass;
 use ast::*;
 use std::sync::Arc;
-use swc_common::{FileName, Fold, SourceMap, DUMMY_SP};
+use swc_common::{Fold, SourceMap, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -38,10 +38,7 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                             key: PropName::Ident(quote_ident!("fileName")),
                             value: box Expr::Lit(Lit::Str(Str {
                                 span: DUMMY_SP,
-                                value: match file_lines.file.name {
-                                    FileName::Real(ref p) => p.display().to_string().into(),
-                                    _ => unimplemented!("file name for other than real files"),
-                                },
+                                value: file_lines.file.name.to_string().into(),
                                 has_escape: false,
                             })),
                         })),
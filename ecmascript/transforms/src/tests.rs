@@ -120,14 +120,20 @@ impl<'a> Tester<'a> {
                 handler: &self.handler,
             };
 
-            let mut p = Parser::new(sess, syntax, SourceFileInput::from(&*fm), None);
+            let mut p = Parser::new(
+                sess,
+                syntax,
+                SourceFileInput::from(&*fm),
+                Some(&self.comments),
+            );
             p.parse_module().map_err(|mut e| {
                 e.emit();
             })?
         };
 
-        let module = validate!(module)
-            .fold_with(&mut tr)
+        let module = validate!(module);
+        let module = crate::util::COMMENTS
+            .set(&self.comments, || module.fold_with(&mut tr))
             .fold_with(&mut ::testing::DropSpan)
             .fold_with(&mut Normalizer);
 
@@ -1,16 +1,17 @@
-pub use self::opt_chaining::optional_chaining;
+pub use self::dts::dts;
 use crate::{
     pass::Pass,
     util::{prepend_stmts, var::VarCollector, ExprFactory},
 };
 use ast::*;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{
-    util::move_map::MoveMap, Fold, FoldWith, Spanned, SyntaxContext, Visit, VisitWith, DUMMY_SP,
+    util::move_map::MoveMap, Fold, FoldWith, Span, Spanned, SyntaxContext, Visit, VisitWith,
+    DUMMY_SP,
 };
 
-mod opt_chaining;
+mod dts;
 #[cfg(test)]
 mod tests;
 
@@ -88,6 +89,13 @@ impl Strip {
                 }
             }
 
+            Decl::TsModule(TsModuleDecl {
+                declare: false,
+                id: TsModuleName::Ident(ref id),
+                body: Some(ref body),
+                ..
+            }) if is_instantiated(body) => store!(id.sym, id.span.ctxt(), true),
+
             Decl::TsEnum(TsEnumDecl { ref id, .. })
             | Decl::TsInterface(TsInterfaceDecl { ref id, .. })
             | Decl::TsModule(TsModuleDecl {
@@ -116,8 +124,8 @@ impl Fold<Constructor> for Strip {
         let mut stmts = vec![];
 
         let params = c.params.move_map(|param| match param {
-            PatOrTsParamProp::Pat(..) => param,
-            PatOrTsParamProp::TsParamProp(param) => {
+            ParamOrTsParamProp::Param(..) => param,
+            ParamOrTsParamProp::TsParamProp(param) => {
                 let (ident, param) = match param.param {
                     TsParamPropParam::Ident(i) => (i.clone(), Pat::Ident(i)),
                     TsParamPropParam::Assign(AssignPat {
@@ -148,7 +156,7 @@ impl Fold<Constructor> for Strip {
                     .into_stmt(),
                 );
 
-                PatOrTsParamProp::Pat(param)
+                ParamOrTsParamProp::Param(param.into())
             }
         });
 
@@ -164,6 +172,18 @@ impl Fold<Constructor> for Strip {
     }
 }
 
+impl Fold<Param> for Strip {
+    fn fold(&mut self, param: Param) -> Param {
+        let param = param.fold_children(self);
+
+        // Decorators of parameters are dropped like the ones of parameter properties.
+        Param {
+            decorators: vec![],
+            ..param
+        }
+    }
+}
+
 impl Fold<Vec<ClassMember>> for Strip {
     fn fold(&mut self, members: Vec<ClassMember>) -> Vec<ClassMember> {
         let members = members.fold_children(self);
@@ -204,11 +224,39 @@ impl Fold<Vec<Pat>> for Strip {
 impl Fold<Vec<ModuleItem>> for Strip {
     fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
         // First pass
-        let items = items.fold_children(self);
+        let items = items.move_map(|item| match item {
+            // Namespaces are lowered below instead of being dropped by `Fold<Stmt>`.
+            ModuleItem::Stmt(Stmt::Decl(decl @ Decl::TsModule(..))) => {
+                ModuleItem::Stmt(Stmt::Decl(decl.fold_with(self)))
+            }
+            _ => item.fold_with(self),
+        });
 
         let old = self.phase;
         self.phase = Phase::DropImports;
 
+        // Classes, functions, enums and namespaces can be merged with a
+        // namespace or an enum of the same name, which should not be declared
+        // again.
+        let mut declared = HashSet::new();
+        for item in &items {
+            match *item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl { ref ident, .. })))
+                | ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. })))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Class(ClassDecl { ref ident, .. }),
+                    ..
+                }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Fn(FnDecl { ref ident, .. }),
+                    ..
+                })) => {
+                    declared.insert((ident.sym.clone(), ident.span.ctxt()));
+                }
+                _ => {}
+            }
+        }
+
         // Second pass
         let mut stmts = Vec::with_capacity(items.len());
         for item in items {
@@ -219,20 +267,12 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     decl: Decl::TsEnum(e),
                     ..
                 })) => {
-                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                        span: e.span,
-                        decl: Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            declare: false,
-                            decls: vec![VarDeclarator {
-                                span: e.span,
-                                name: Pat::Ident(e.id.clone()),
-                                definite: false,
-                                init: None,
-                            }],
-                        }),
-                    })));
+                    if declared.insert((e.id.sym.clone(), e.id.span.ctxt())) {
+                        stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span: e.span,
+                            decl: Decl::Var(declare_var(e.span, e.id.clone())),
+                        })));
+                    }
                     self.handle_enum(e, &mut stmts)
                 }
                 ModuleItem::Stmt(Stmt::Decl(Decl::TsEnum(e))) => {
@@ -241,23 +281,55 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     //     Foo[Foo["a"] = 0] = "a";
                     // })(Foo || (Foo = {}));
 
-                    stmts.push(
-                        Stmt::Decl(Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            declare: false,
-                            decls: vec![VarDeclarator {
-                                span: e.span,
-                                name: Pat::Ident(e.id.clone()),
-                                definite: false,
-                                init: None,
-                            }],
-                        }))
-                        .into(),
-                    );
+                    if declared.insert((e.id.sym.clone(), e.id.span.ctxt())) {
+                        stmts.push(Stmt::Decl(Decl::Var(declare_var(e.span, e.id.clone()))).into());
+                    }
                     self.handle_enum(e, &mut stmts)
                 }
 
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl:
+                        Decl::TsModule(TsModuleDecl {
+                            span,
+                            declare: false,
+                            id: TsModuleName::Ident(id),
+                            body: Some(body),
+                            ..
+                        }),
+                    ..
+                })) => {
+                    if !is_instantiated(&body) {
+                        continue;
+                    }
+                    if declared.insert((id.sym.clone(), id.span.ctxt())) {
+                        stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span,
+                            decl: Decl::Var(declare_var(span, id.clone())),
+                        })));
+                    }
+                    stmts.push(ModuleItem::Stmt(self.handle_ts_module(id, body)))
+                }
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(TsModuleDecl {
+                    span,
+                    declare: false,
+                    id: TsModuleName::Ident(id),
+                    body: Some(body),
+                    ..
+                }))) => {
+                    // var Foo;
+                    // (function (Foo) {
+                    //     Foo.x = 1;
+                    // })(Foo || (Foo = {}));
+
+                    if !is_instantiated(&body) {
+                        continue;
+                    }
+                    if declared.insert((id.sym.clone(), id.span.ctxt())) {
+                        stmts.push(Stmt::Decl(Decl::Var(declare_var(span, id.clone()))).into());
+                    }
+                    stmts.push(ModuleItem::Stmt(self.handle_ts_module(id, body)))
+                }
+
                 ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
                     function: Function { body: None, .. },
                     ..
@@ -365,97 +437,658 @@ impl Fold<Vec<ModuleItem>> for Strip {
 impl Strip {
     fn handle_enum(&mut self, e: TsEnumDecl, stmts: &mut Vec<ModuleItem>) {
         let id = e.id;
-        stmts.push(
-            CallExpr {
-                span: DUMMY_SP,
-                callee: FnExpr {
-                    ident: None,
-                    function: Function {
+        let body = e
+            .members
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let value = match m.id {
+                    TsEnumMemberId::Str(s) => s,
+                    TsEnumMemberId::Ident(i) => Str {
+                        span: i.span,
+                        value: i.sym,
+                        has_escape: false,
+                    },
+                };
+
+                // Foo[Foo["a"] = 0] = "a";
+                AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        obj: id.clone().as_obj(),
                         span: DUMMY_SP,
-                        decorators: Default::default(),
-                        is_async: false,
-                        is_generator: false,
-                        type_params: Default::default(),
-                        params: vec![Pat::Ident(id.clone())],
-                        body: Some(BlockStmt {
+                        computed: true,
+
+                        // Foo["a"] = 0
+                        prop: box Expr::Assign(AssignExpr {
                             span: DUMMY_SP,
-                            stmts: e
-                                .members
-                                .into_iter()
-                                .enumerate()
-                                .map(|(i, m)| {
-                                    let value = match m.id {
-                                        TsEnumMemberId::Str(s) => s,
-                                        TsEnumMemberId::Ident(i) => Str {
-                                            span: i.span,
-                                            value: i.sym,
-                                            has_escape: false,
-                                        },
-                                    };
-
-                                    // Foo[Foo["a"] = 0] = "a";
-                                    AssignExpr {
-                                        span: DUMMY_SP,
-                                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
-                                            obj: id.clone().as_obj(),
-                                            span: DUMMY_SP,
-                                            computed: true,
-
-                                            // Foo["a"] = 0
-                                            prop: box Expr::Assign(AssignExpr {
-                                                span: DUMMY_SP,
-                                                left: PatOrExpr::Expr(box Expr::Member(
-                                                    MemberExpr {
-                                                        span: DUMMY_SP,
-                                                        obj: id.clone().as_obj(),
-                                                        prop: m.init.unwrap_or_else(|| {
-                                                            box Expr::Lit(Lit::Str(value.clone()))
-                                                        }),
-                                                        computed: true,
-                                                    },
-                                                )),
-                                                op: op!("="),
-                                                right: box Expr::Lit(Lit::Num(Number {
-                                                    span: DUMMY_SP,
-                                                    value: i as _,
-                                                })),
-                                            }),
-                                        })),
-                                        op: op!("="),
-                                        right: box Expr::Lit(Lit::Str(Str {
-                                            span: DUMMY_SP,
-                                            value: value.value,
-                                            has_escape: false,
-                                        })),
-                                    }
-                                    .into_stmt()
-                                })
-                                .collect(),
+                            left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: id.clone().as_obj(),
+                                prop: m
+                                    .init
+                                    .unwrap_or_else(|| box Expr::Lit(Lit::Str(value.clone()))),
+                                computed: true,
+                            })),
+                            op: op!("="),
+                            right: box Expr::Lit(Lit::Num(Number {
+                                span: DUMMY_SP,
+                                value: i as _,
+                            })),
                         }),
-                        return_type: Default::default(),
-                    },
+                    })),
+                    op: op!("="),
+                    right: box Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: value.value,
+                        has_escape: false,
+                    })),
+                }
+                .into_stmt()
+            })
+            .collect();
+
+        stmts.push(namespace_iife(id, body).into())
+    }
+
+    /// Lowers a namespace to
+    ///
+    /// ```js
+    /// (function (Foo) {
+    ///     Foo.x = 1;
+    /// })(Foo || (Foo = {}));
+    /// ```
+    ///
+    /// Body of the namespace is already stripped by the first pass.
+    fn handle_ts_module(&mut self, id: Ident, body: TsNamespaceBody) -> Stmt {
+        let items = match body {
+            TsNamespaceBody::TsModuleBlock(block) => block.body,
+            // `namespace A.B {}` is `namespace A { export namespace B {} }`
+            TsNamespaceBody::TsNamespaceDecl(TsNamespaceDecl { span, id, body, .. }) => vec![
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span,
+                    decl: Decl::Var(declare_var(span, id.clone())),
+                })),
+                ModuleItem::Stmt(self.handle_ts_module(id, *body)),
+            ],
+        };
+
+        let mut exported = vec![];
+        let mut stmts = Vec::with_capacity(items.len());
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(stmt) => {
+                    stmts.push(stmt);
+                    continue;
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl,
+                // Other module declarations are not allowed in a namespace.
+                ModuleItem::ModuleDecl(..) => continue,
+            };
+
+            match decl {
+                Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                    // function f() {}
+                    // Foo.f = f;
+                    let assign =
+                        export_to_namespace(&id, ident.clone(), Expr::Ident(ident.clone()));
+                    stmts.push(Stmt::Decl(decl));
+                    stmts.push(assign);
+                }
+
+                Decl::Var(VarDecl {
+                    span,
+                    kind,
+                    declare,
+                    decls,
+                }) => {
+                    for decl in decls {
+                        match decl.name {
+                            // Foo.x = 1;
+                            Pat::Ident(ref name) => {
+                                exported.push((name.sym.clone(), name.span.ctxt()));
+                                if let Some(init) = decl.init {
+                                    stmts.push(export_to_namespace(&id, name.clone(), *init));
+                                }
+                            }
+
+                            // const { a } = obj;
+                            // Foo.a = a;
+                            _ => {
+                                let mut names = vec![];
+                                decl.visit_with(&mut VarCollector { to: &mut names });
+
+                                stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                                    span,
+                                    kind,
+                                    declare,
+                                    decls: vec![decl],
+                                })));
+                                stmts.extend(names.into_iter().map(|(sym, ctxt)| {
+                                    let name = Ident::new(sym, DUMMY_SP.with_ctxt(ctxt));
+                                    export_to_namespace(&id, name.clone(), Expr::Ident(name))
+                                }));
+                            }
+                        }
+                    }
                 }
-                .as_callee(),
-                args: vec![BinExpr {
+
+                _ => stmts.push(Stmt::Decl(decl)),
+            }
+        }
+
+        let stmts = stmts.fold_with(&mut NamespaceExports {
+            namespace: &id,
+            exported,
+        });
+
+        namespace_iife(id, stmts)
+    }
+}
+
+/// `var Foo;`
+fn declare_var(span: Span, id: Ident) -> VarDecl {
+    VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span,
+            name: Pat::Ident(id),
+            definite: false,
+            init: None,
+        }],
+    }
+}
+
+/// `(function (Foo) { ... })(Foo || (Foo = {}));`
+fn namespace_iife(id: Ident, body: Vec<Stmt>) -> Stmt {
+    CallExpr {
+        span: DUMMY_SP,
+        callee: FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                is_async: false,
+                is_generator: false,
+                type_params: Default::default(),
+                params: vec![Pat::Ident(id.clone()).into()],
+                body: Some(BlockStmt {
                     span: DUMMY_SP,
-                    left: box Expr::Ident(id.clone()),
-                    op: op!("||"),
-                    right: box Expr::Assign(AssignExpr {
-                        span: DUMMY_SP,
-                        left: PatOrExpr::Pat(Pat::Ident(id.clone()).into()),
-                        op: op!("="),
-                        right: box Expr::Object(ObjectLit {
-                            span: DUMMY_SP,
-                            props: vec![],
+                    stmts: body,
+                }),
+                return_type: Default::default(),
+            },
+        }
+        .as_callee(),
+        args: vec![BinExpr {
+            span: DUMMY_SP,
+            left: box Expr::Ident(id.clone()),
+            op: op!("||"),
+            right: box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(Pat::Ident(id).into()),
+                op: op!("="),
+                right: box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                }),
+            }),
+        }
+        .as_arg()],
+        type_args: Default::default(),
+    }
+    .into_stmt()
+}
+
+/// `Foo.x = value;`
+fn export_to_namespace(namespace: &Ident, name: Ident, value: Expr) -> Stmt {
+    AssignExpr {
+        span: DUMMY_SP,
+        left: PatOrExpr::Expr(box namespace.clone().member(name)),
+        op: op!("="),
+        right: box value,
+    }
+    .into_stmt()
+}
+
+/// Returns true if a namespace has a value and should be emitted.
+fn is_instantiated(body: &TsNamespaceBody) -> bool {
+    match *body {
+        TsNamespaceBody::TsModuleBlock(ref block) => block.body.iter().any(|item| match *item {
+            ModuleItem::Stmt(Stmt::Decl(ref decl))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                match *decl {
+                    Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+                    Decl::Class(ClassDecl { declare, .. })
+                    | Decl::Fn(FnDecl { declare, .. })
+                    | Decl::Var(VarDecl { declare, .. }) => !declare,
+                    Decl::TsEnum(TsEnumDecl { declare, .. }) => !declare,
+                    Decl::TsModule(TsModuleDecl {
+                        declare, ref body, ..
+                    }) => {
+                        !declare
+                            && match *body {
+                                Some(ref body) => is_instantiated(body),
+                                None => false,
+                            }
+                    }
+                }
+            }
+            ModuleItem::Stmt(Stmt::Empty(..)) => false,
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(ref import)) => import.is_export,
+            _ => true,
+        }),
+        TsNamespaceBody::TsNamespaceDecl(ref decl) => is_instantiated(&decl.body),
+    }
+}
+
+/// Replaces references to exported variables of a namespace, like `x`, with
+/// `Foo.x`.
+///
+/// As this pass runs before the resolver, shadowing is detected by collecting
+/// the names declared in each scope. A name declared by a parameter, a `var`,
+/// a lexical declaration, a catch clause or a named function / class
+/// expression is not replaced inside that scope.
+struct NamespaceExports<'a> {
+    namespace: &'a Ident,
+    exported: Vec<(JsWord, SyntaxContext)>,
+}
+
+impl NamespaceExports<'_> {
+    fn is_exported(&self, i: &Ident) -> bool {
+        self.exported.contains(&(i.sym.clone(), i.span.ctxt()))
+    }
+
+    fn member(&self, i: Ident) -> Expr {
+        Expr::Member(MemberExpr {
+            span: i.span,
+            obj: self.namespace.clone().as_obj(),
+            prop: box Expr::Ident(i),
+            computed: false,
+        })
+    }
+
+    fn fold_shadowed<T>(&mut self, names: Vec<(JsWord, SyntaxContext)>, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        if !names.iter().any(|id| self.exported.contains(id)) {
+            return node.fold_children(self);
+        }
+
+        let old = self.exported.clone();
+        self.exported.retain(|id| !names.contains(id));
+        let node = node.fold_children(self);
+        self.exported = old;
+        node
+    }
+
+    /// Replaces exported identifiers in the target of an assignment, like
+    /// `[x] = arr` or `({ x } = obj)`.
+    fn fold_assign_target(&mut self, pat: Pat) -> Pat {
+        match pat {
+            Pat::Ident(i) => {
+                if self.is_exported(&i) {
+                    Pat::Expr(box self.member(i))
+                } else {
+                    Pat::Ident(i)
+                }
+            }
+            Pat::Array(ArrayPat {
+                span,
+                elems,
+                type_ann,
+            }) => Pat::Array(ArrayPat {
+                span,
+                elems: elems
+                    .into_iter()
+                    .map(|elem| elem.map(|elem| self.fold_assign_target(elem)))
+                    .collect(),
+                type_ann,
+            }),
+            Pat::Object(ObjectPat {
+                span,
+                props,
+                type_ann,
+            }) => Pat::Object(ObjectPat {
+                span,
+                props: props
+                    .into_iter()
+                    .map(|prop| self.fold_assign_target_prop(prop))
+                    .collect(),
+                type_ann,
+            }),
+            Pat::Rest(RestPat {
+                dot3_token,
+                arg,
+                type_ann,
+            }) => Pat::Rest(RestPat {
+                dot3_token,
+                arg: box self.fold_assign_target(*arg),
+                type_ann,
+            }),
+            Pat::Assign(AssignPat {
+                span,
+                left,
+                right,
+                type_ann,
+            }) => Pat::Assign(AssignPat {
+                span,
+                left: box self.fold_assign_target(*left),
+                right: right.fold_with(self),
+                type_ann,
+            }),
+            Pat::Expr(e) => Pat::Expr(e.fold_with(self)),
+            Pat::Invalid(..) => pat,
+        }
+    }
+
+    fn fold_assign_target_prop(&mut self, prop: ObjectPatProp) -> ObjectPatProp {
+        match prop {
+            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: key.fold_with(self),
+                    value: box self.fold_assign_target(*value),
+                })
+            }
+            // `{ x = 1 } = obj` => `{ x: Foo.x = 1 } = obj`
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                if !self.is_exported(&key) {
+                    return ObjectPatProp::Assign(AssignPatProp {
+                        span,
+                        key,
+                        value: value.fold_with(self),
+                    });
+                }
+
+                let target = Pat::Expr(box self.member(key.clone()));
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: match value {
+                        Some(value) => box Pat::Assign(AssignPat {
+                            span,
+                            left: box target,
+                            right: value.fold_with(self),
+                            type_ann: None,
                         }),
-                    }),
+                        None => box target,
+                    },
+                })
+            }
+            ObjectPatProp::Rest(RestPat {
+                dot3_token,
+                arg,
+                type_ann,
+            }) => ObjectPatProp::Rest(RestPat {
+                dot3_token,
+                arg: box self.fold_assign_target(*arg),
+                type_ann,
+            }),
+        }
+    }
+}
+
+/// Collects names declared by `var` in a function body, without visiting
+/// nested functions.
+struct HoistedVarCollector<'a> {
+    to: &'a mut Vec<(JsWord, SyntaxContext)>,
+}
+
+impl Visit<VarDecl> for HoistedVarCollector<'_> {
+    fn visit(&mut self, v: &VarDecl) {
+        if v.kind == VarDeclKind::Var {
+            v.decls.visit_with(&mut VarCollector { to: self.to });
+        }
+    }
+}
+
+impl Visit<Function> for HoistedVarCollector<'_> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for HoistedVarCollector<'_> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+impl Visit<Constructor> for HoistedVarCollector<'_> {
+    fn visit(&mut self, _: &Constructor) {}
+}
+
+/// Returns names declared by `let`, `const`, functions and classes directly in
+/// `stmts`.
+fn lexical_names(stmts: &[Stmt]) -> Vec<(JsWord, SyntaxContext)> {
+    let mut names = vec![];
+    for stmt in stmts {
+        match *stmt {
+            Stmt::Decl(Decl::Var(ref v)) if v.kind != VarDeclKind::Var => {
+                v.decls.visit_with(&mut VarCollector { to: &mut names });
+            }
+            Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. }))
+            | Stmt::Decl(Decl::Class(ClassDecl { ref ident, .. })) => {
+                names.push((ident.sym.clone(), ident.span.ctxt()));
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Returns names declared by `let` or `const` in the head of a loop.
+fn loop_head_names(v: &VarDecl) -> Vec<(JsWord, SyntaxContext)> {
+    let mut names = vec![];
+    if v.kind != VarDeclKind::Var {
+        v.decls.visit_with(&mut VarCollector { to: &mut names });
+    }
+    names
+}
+
+impl Fold<Expr> for NamespaceExports<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) => {
+                if self.is_exported(&i) {
+                    self.member(i)
+                } else {
+                    Expr::Ident(i)
                 }
-                .as_arg()],
-                type_args: Default::default(),
             }
-            .into_stmt()
-            .into(),
-        )
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop: if computed { prop.fold_with(self) } else { prop },
+                computed,
+            }),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PatOrExpr> for NamespaceExports<'_> {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Pat(box Pat::Ident(i)) => {
+                if self.is_exported(&i) {
+                    PatOrExpr::Expr(box self.member(i))
+                } else {
+                    PatOrExpr::Pat(box Pat::Ident(i))
+                }
+            }
+            PatOrExpr::Pat(pat) => PatOrExpr::Pat(box self.fold_assign_target(*pat)),
+            PatOrExpr::Expr(e) => PatOrExpr::Expr(e.fold_with(self)),
+        }
+    }
+}
+
+impl Fold<VarDeclOrPat> for NamespaceExports<'_> {
+    fn fold(&mut self, n: VarDeclOrPat) -> VarDeclOrPat {
+        match n {
+            VarDeclOrPat::Pat(pat) => VarDeclOrPat::Pat(self.fold_assign_target(pat)),
+            VarDeclOrPat::VarDecl(..) => n.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for NamespaceExports<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => {
+                if self.is_exported(&i) {
+                    Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(i.clone()),
+                        value: box self.member(i),
+                    })
+                } else {
+                    Prop::Shorthand(i)
+                }
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for NamespaceExports<'_> {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut names = vec![];
+        f.params.visit_with(&mut VarCollector { to: &mut names });
+        if let Some(ref body) = f.body {
+            body.visit_with(&mut HoistedVarCollector { to: &mut names });
+        }
+
+        self.fold_shadowed(names, f)
+    }
+}
+
+impl Fold<ArrowExpr> for NamespaceExports<'_> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let mut names = vec![];
+        f.params.visit_with(&mut VarCollector { to: &mut names });
+        f.body
+            .visit_with(&mut HoistedVarCollector { to: &mut names });
+
+        self.fold_shadowed(names, f)
+    }
+}
+
+impl Fold<Constructor> for NamespaceExports<'_> {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        let mut names = vec![];
+        for param in &c.params {
+            match *param {
+                ParamOrTsParamProp::Param(ref param) => {
+                    param.pat.visit_with(&mut VarCollector { to: &mut names })
+                }
+                ParamOrTsParamProp::TsParamProp(TsParamProp {
+                    param: TsParamPropParam::Ident(ref i),
+                    ..
+                }) => names.push((i.sym.clone(), i.span.ctxt())),
+                ParamOrTsParamProp::TsParamProp(TsParamProp {
+                    param: TsParamPropParam::Assign(ref pat),
+                    ..
+                }) => pat.left.visit_with(&mut VarCollector { to: &mut names }),
+            }
+        }
+        if let Some(ref body) = c.body {
+            body.visit_with(&mut HoistedVarCollector { to: &mut names });
+        }
+
+        self.fold_shadowed(names, c)
+    }
+}
+
+impl Fold<FnExpr> for NamespaceExports<'_> {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let names = f
+            .ident
+            .iter()
+            .map(|i| (i.sym.clone(), i.span.ctxt()))
+            .collect();
+
+        self.fold_shadowed(names, f)
+    }
+}
+
+impl Fold<ClassExpr> for NamespaceExports<'_> {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        let names = c
+            .ident
+            .iter()
+            .map(|i| (i.sym.clone(), i.span.ctxt()))
+            .collect();
+
+        self.fold_shadowed(names, c)
+    }
+}
+
+impl Fold<BlockStmt> for NamespaceExports<'_> {
+    fn fold(&mut self, b: BlockStmt) -> BlockStmt {
+        let names = lexical_names(&b.stmts);
+
+        self.fold_shadowed(names, b)
+    }
+}
+
+impl Fold<SwitchStmt> for NamespaceExports<'_> {
+    fn fold(&mut self, s: SwitchStmt) -> SwitchStmt {
+        let names = s
+            .cases
+            .iter()
+            .flat_map(|case| lexical_names(&case.cons))
+            .collect();
+
+        SwitchStmt {
+            discriminant: s.discriminant.fold_with(self),
+            cases: self.fold_shadowed(names, s.cases),
+            ..s
+        }
+    }
+}
+
+impl Fold<CatchClause> for NamespaceExports<'_> {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        let mut names = vec![];
+        c.param.visit_with(&mut VarCollector { to: &mut names });
+
+        self.fold_shadowed(names, c)
+    }
+}
+
+impl Fold<ForStmt> for NamespaceExports<'_> {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let names = match s.init {
+            Some(VarDeclOrExpr::VarDecl(ref v)) => loop_head_names(v),
+            _ => vec![],
+        };
+
+        self.fold_shadowed(names, s)
+    }
+}
+
+impl Fold<ForInStmt> for NamespaceExports<'_> {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        let names = match s.left {
+            VarDeclOrPat::VarDecl(ref v) => loop_head_names(v),
+            _ => vec![],
+        };
+
+        self.fold_shadowed(names, s)
+    }
+}
+
+impl Fold<ForOfStmt> for NamespaceExports<'_> {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        let names = match s.left {
+            VarDeclOrPat::VarDecl(ref v) => loop_head_names(v),
+            _ => vec![],
+        };
+
+        self.fold_shadowed(names, s)
     }
 }
 
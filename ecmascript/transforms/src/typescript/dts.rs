@@ -0,0 +1,775 @@
+use crate::{
+    pass::Pass,
+    util::{prop_name_to_expr, HANDLER},
+};
+use ast::*;
+use hashbrown::HashSet;
+use swc_atoms::JsWord;
+use swc_common::{Fold, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// Converts a typescript module into the content of a declaration (`.d.ts`)
+/// file.
+///
+/// Function bodies and initializers are dropped, and class members are
+/// converted to signatures. Functions and variables are kept only if they are
+/// exported or referenced by a `typeof` type, while types, classes, enums and
+/// namespaces are kept so that exported declarations can refer to them.
+///
+/// Types are not inferred (like `isolatedDeclarations` of `tsc`), so exported
+/// declarations must have explicit type annotations unless the type is
+/// obvious from a literal. Missing annotations are reported as errors.
+///
+/// The output should be printed with `swc_ecma_codegen`.
+pub fn dts() -> impl Pass {
+    Dts
+}
+
+struct Dts;
+
+impl Fold<Module> for Dts {
+    fn fold(&mut self, module: Module) -> Module {
+        Module {
+            body: items(module.body, true, true),
+            ..module
+        }
+    }
+}
+
+/// Converts items of a module or a namespace.
+///
+/// `report` is false for items of a namespace which is not exported.
+fn items(items: Vec<ModuleItem>, top_level: bool, report: bool) -> Vec<ModuleItem> {
+    let exported = local_exports(&items);
+    let referenced = typeof_references(&items);
+
+    let mut buf = Vec::with_capacity(items.len());
+    // Name of the last function without a body.
+    let mut overload: Option<JsWord> = None;
+
+    for item in items {
+        // The implementation of an overloaded function is not a part of its
+        // type.
+        let is_impl = match fn_decl(&item) {
+            Some((name, has_body)) => {
+                let is_impl = has_body && overload.as_ref() == Some(name);
+                overload = if has_body { None } else { Some(name.clone()) };
+                is_impl
+            }
+            None => {
+                overload = None;
+                false
+            }
+        };
+        if is_impl {
+            continue;
+        }
+
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl })) => {
+                if let Some(decl) = self::decl(decl, top_level, report) {
+                    buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span,
+                        decl,
+                    })));
+                }
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                span,
+                decl,
+            })) => {
+                let decl = match decl {
+                    DefaultDecl::Class(ClassExpr { ident, class }) => {
+                        DefaultDecl::Class(ClassExpr {
+                            ident,
+                            class: self::class(class, report),
+                        })
+                    }
+                    DefaultDecl::Fn(FnExpr { ident, function }) => DefaultDecl::Fn(FnExpr {
+                        ident,
+                        function: signature(function, report, report),
+                    }),
+                    DefaultDecl::TsInterfaceDecl(..) => decl,
+                };
+
+                buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(
+                    ExportDefaultDecl { span, decl },
+                )));
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr: box Expr::Ident(..),
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                expr: box Expr::Ident(..),
+                ..
+            })) => buf.push(item),
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                span,
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                span,
+                ..
+            })) => {
+                if report {
+                    error(span, "Exported expression must be an identifier");
+                }
+            }
+
+            ModuleItem::ModuleDecl(..) => buf.push(item),
+
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let is_exported = decl_names(&decl)
+                    .into_iter()
+                    .any(|name| exported.contains(name));
+
+                let decl = if is_exported {
+                    self::decl(decl, top_level, report)
+                } else {
+                    local_decl(decl, top_level, &referenced)
+                };
+
+                if let Some(decl) = decl {
+                    buf.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+                }
+            }
+
+            ModuleItem::Stmt(..) => {}
+        }
+    }
+
+    buf
+}
+
+/// Converts an exported declaration.
+fn decl(decl: Decl, top_level: bool, report: bool) -> Option<Decl> {
+    match decl {
+        Decl::Class(ClassDecl { ident, class, .. }) => Some(Decl::Class(ClassDecl {
+            ident,
+            declare: top_level,
+            class: self::class(class, report),
+        })),
+
+        Decl::Fn(FnDecl {
+            ident, function, ..
+        }) => Some(Decl::Fn(FnDecl {
+            ident,
+            declare: top_level,
+            function: signature(function, report, report),
+        })),
+
+        Decl::Var(VarDecl {
+            span, kind, decls, ..
+        }) => {
+            let decls: Vec<_> = decls
+                .into_iter()
+                .filter_map(|d| var_declarator(kind, d, report))
+                .collect();
+            if decls.is_empty() {
+                return None;
+            }
+
+            Some(Decl::Var(VarDecl {
+                span,
+                kind,
+                declare: top_level,
+                decls,
+            }))
+        }
+
+        Decl::TsEnum(e) => Some(Decl::TsEnum(TsEnumDecl {
+            declare: top_level,
+            ..e
+        })),
+
+        // `declare module 'foo' {}` and `declare global {}` are already
+        // declarations.
+        Decl::TsModule(m) => {
+            if m.declare {
+                return Some(Decl::TsModule(m));
+            }
+
+            Some(Decl::TsModule(TsModuleDecl {
+                declare: top_level,
+                body: m.body.map(|body| ns_body(body, report)),
+                ..m
+            }))
+        }
+
+        Decl::TsInterface(..) | Decl::TsTypeAlias(..) => Some(decl),
+    }
+}
+
+/// Converts a declaration which is not exported.
+///
+/// Functions and variables are dropped unless they are referenced by
+/// `typeof`, as exported types cannot refer to them otherwise.
+fn local_decl(d: Decl, top_level: bool, referenced: &HashSet<JsWord>) -> Option<Decl> {
+    match d {
+        Decl::Fn(FnDecl {
+            ref ident, declare, ..
+        }) if !declare && !referenced.contains(&ident.sym) => None,
+        Decl::Var(VarDecl {
+            span,
+            kind,
+            declare,
+            decls,
+        }) if !declare => {
+            // `const a = 1, b = 2;` => `declare const a = 1;`
+            let decls: Vec<_> = decls
+                .into_iter()
+                .filter(|d| match d.name {
+                    Pat::Ident(ref i) => referenced.contains(&i.sym),
+                    _ => false,
+                })
+                .collect();
+            if decls.is_empty() {
+                return None;
+            }
+
+            decl(
+                Decl::Var(VarDecl {
+                    span,
+                    kind,
+                    declare,
+                    decls,
+                }),
+                top_level,
+                false,
+            )
+        }
+        _ => decl(d, top_level, false),
+    }
+}
+
+fn ns_body(body: TsNamespaceBody, report: bool) -> TsNamespaceBody {
+    match body {
+        TsNamespaceBody::TsModuleBlock(TsModuleBlock { span, body }) => {
+            TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+                span,
+                body: items(body, false, report),
+            })
+        }
+        TsNamespaceBody::TsNamespaceDecl(decl) => {
+            TsNamespaceBody::TsNamespaceDecl(TsNamespaceDecl {
+                body: box ns_body(*decl.body, report),
+                ..decl
+            })
+        }
+    }
+}
+
+fn var_declarator(kind: VarDeclKind, d: VarDeclarator, report: bool) -> Option<VarDeclarator> {
+    let mut id = match d.name {
+        Pat::Ident(id) => id,
+        _ => {
+            if report {
+                error(d.span, "Binding patterns can't be exported");
+            }
+            return None;
+        }
+    };
+
+    let mut init = None;
+    if id.type_ann.is_none() {
+        // `declare const a = 1;`
+        if kind == VarDeclKind::Const && d.init.as_ref().map_or(false, |v| is_const_lit(v)) {
+            init = d.init;
+        } else {
+            id.type_ann = d.init.and_then(|v| widened_lit_type(&v));
+            if report && id.type_ann.is_none() {
+                error(d.span, "Variable must have an explicit type annotation");
+            }
+        }
+    }
+
+    Some(VarDeclarator {
+        span: d.span,
+        name: Pat::Ident(id),
+        init,
+        definite: false,
+    })
+}
+
+/// Drops the body of `function`.
+fn signature(function: Function, report_params: bool, report_return: bool) -> Function {
+    if report_return && function.return_type.is_none() {
+        error(
+            function.span,
+            "Function must have an explicit return type annotation",
+        );
+    }
+
+    Function {
+        params: function
+            .params
+            .into_iter()
+            .map(|p| Param {
+                decorators: vec![],
+                pat: param(p.pat, report_params),
+                ..p
+            })
+            .collect(),
+        decorators: vec![],
+        body: None,
+        // Not allowed in an ambient context.
+        is_async: false,
+        is_generator: false,
+        ..function
+    }
+}
+
+fn param(pat: Pat, report: bool) -> Pat {
+    let pat = match pat {
+        // `a = 1` is emitted as `a?: number`.
+        Pat::Assign(AssignPat {
+            left: box Pat::Ident(id),
+            right,
+            type_ann,
+            ..
+        }) => Pat::Ident(Ident {
+            type_ann: id
+                .type_ann
+                .or(type_ann)
+                .or_else(|| widened_lit_type(&right)),
+            optional: true,
+            ..id
+        }),
+        _ => drop_defaults(pat),
+    };
+
+    if report && !has_type_ann(&pat) {
+        error(
+            pat.span(),
+            "Parameter must have an explicit type annotation",
+        );
+    }
+
+    pat
+}
+
+/// Removes default values from a destructuring pattern.
+fn drop_defaults(pat: Pat) -> Pat {
+    match pat {
+        Pat::Assign(AssignPat { left, type_ann, .. }) => match drop_defaults(*left) {
+            Pat::Array(a) => Pat::Array(ArrayPat {
+                type_ann: a.type_ann.or(type_ann),
+                ..a
+            }),
+            Pat::Object(o) => Pat::Object(ObjectPat {
+                type_ann: o.type_ann.or(type_ann),
+                ..o
+            }),
+            left => left,
+        },
+        Pat::Array(a) => Pat::Array(ArrayPat {
+            elems: a
+                .elems
+                .into_iter()
+                .map(|elem| elem.map(drop_defaults))
+                .collect(),
+            ..a
+        }),
+        Pat::Object(o) => Pat::Object(ObjectPat {
+            props: o
+                .props
+                .into_iter()
+                .map(|prop| match prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                        ObjectPatProp::KeyValue(KeyValuePatProp {
+                            key,
+                            value: box drop_defaults(*value),
+                        })
+                    }
+                    ObjectPatProp::Assign(p) => {
+                        ObjectPatProp::Assign(AssignPatProp { value: None, ..p })
+                    }
+                    ObjectPatProp::Rest(..) => prop,
+                })
+                .collect(),
+            ..o
+        }),
+        _ => pat,
+    }
+}
+
+fn has_type_ann(pat: &Pat) -> bool {
+    match *pat {
+        Pat::Ident(Ident { ref type_ann, .. })
+        | Pat::Array(ArrayPat { ref type_ann, .. })
+        | Pat::Object(ObjectPat { ref type_ann, .. })
+        | Pat::Rest(RestPat { ref type_ann, .. })
+        | Pat::Assign(AssignPat { ref type_ann, .. }) => type_ann.is_some(),
+        Pat::Expr(..) | Pat::Invalid(..) => true,
+    }
+}
+
+fn class(class: Class, report: bool) -> Class {
+    let mut body = Vec::with_capacity(class.body.len());
+    let mut has_private_name = false;
+    // `private m;` is emitted once for overloads.
+    let mut private_methods = HashSet::new();
+    let mut overload: Option<JsWord> = None;
+
+    for member in class.body {
+        let is_impl = match method_decl(&member) {
+            Some((name, has_body)) => {
+                let is_impl = has_body && overload.as_ref() == Some(name);
+                overload = if has_body { None } else { Some(name.clone()) };
+                is_impl
+            }
+            None => {
+                overload = None;
+                false
+            }
+        };
+
+        match member {
+            ClassMember::Constructor(c) => {
+                let mut params = Vec::with_capacity(c.params.len());
+                for param in c.params {
+                    match param {
+                        // Parameter properties are emitted as properties.
+                        ParamOrTsParamProp::TsParamProp(TsParamProp {
+                            span,
+                            accessibility,
+                            readonly,
+                            param: p,
+                            ..
+                        }) => {
+                            let p = self::param(
+                                match p {
+                                    TsParamPropParam::Ident(id) => Pat::Ident(id),
+                                    TsParamPropParam::Assign(p) => Pat::Assign(p),
+                                },
+                                report,
+                            );
+
+                            if let Pat::Ident(ref id) = p {
+                                let is_private = accessibility == Some(Accessibility::Private);
+                                body.push(ClassMember::ClassProp(ClassProp {
+                                    span,
+                                    key: box Expr::Ident(Ident::new(id.sym.clone(), id.span)),
+                                    value: None,
+                                    type_ann: if is_private {
+                                        None
+                                    } else {
+                                        id.type_ann.clone()
+                                    },
+                                    is_static: false,
+                                    decorators: vec![],
+                                    computed: false,
+                                    accessibility,
+                                    is_abstract: false,
+                                    is_optional: id.optional,
+                                    readonly,
+                                    definite: false,
+                                }));
+                            }
+                            params.push(ParamOrTsParamProp::Param(p.into()));
+                        }
+                        ParamOrTsParamProp::Param(p) => {
+                            params.push(ParamOrTsParamProp::Param(Param {
+                                decorators: vec![],
+                                pat: self::param(p.pat, report),
+                                ..p
+                            }))
+                        }
+                    }
+                }
+
+                if is_impl {
+                    continue;
+                }
+                // `private constructor();`
+                if c.accessibility == Some(Accessibility::Private) {
+                    params.clear();
+                }
+
+                body.push(ClassMember::Constructor(Constructor {
+                    params,
+                    body: None,
+                    ..c
+                }));
+            }
+
+            ClassMember::Method(..) if is_impl => {}
+
+            ClassMember::Method(m) => {
+                let is_private = m.accessibility == Some(Accessibility::Private);
+
+                if is_private && m.kind == MethodKind::Method {
+                    if let Some(name) = prop_name_sym(&m.key) {
+                        if !private_methods.insert(name.clone()) {
+                            continue;
+                        }
+                    }
+
+                    let computed = match m.key {
+                        PropName::Computed(..) => true,
+                        _ => false,
+                    };
+                    body.push(ClassMember::ClassProp(ClassProp {
+                        span: m.span,
+                        key: box prop_name_to_expr(m.key),
+                        value: None,
+                        type_ann: None,
+                        is_static: m.is_static,
+                        decorators: vec![],
+                        computed,
+                        accessibility: m.accessibility,
+                        is_abstract: false,
+                        is_optional: m.is_optional,
+                        readonly: false,
+                        definite: false,
+                    }));
+                    continue;
+                }
+
+                let report = report && !is_private;
+                // A setter cannot have a return type.
+                let report_return = report && m.kind != MethodKind::Setter;
+
+                body.push(ClassMember::Method(ClassMethod {
+                    function: signature(m.function, report, report_return),
+                    ..m
+                }));
+            }
+
+            ClassMember::PrivateMethod(..) | ClassMember::PrivateProp(..) => {
+                has_private_name = true;
+            }
+
+            ClassMember::ClassProp(p) => {
+                let mut value = None;
+                let mut type_ann = p.type_ann;
+
+                if p.accessibility == Some(Accessibility::Private) {
+                    type_ann = None;
+                } else if type_ann.is_none() {
+                    // `readonly a = 1;`
+                    if p.readonly && p.value.as_ref().map_or(false, |v| is_const_lit(v)) {
+                        value = p.value;
+                    } else {
+                        type_ann = p.value.and_then(|v| widened_lit_type(&v));
+                        if report && type_ann.is_none() {
+                            error(p.span, "Property must have an explicit type annotation");
+                        }
+                    }
+                }
+
+                body.push(ClassMember::ClassProp(ClassProp {
+                    value,
+                    type_ann,
+                    decorators: vec![],
+                    definite: false,
+                    ..p
+                }));
+            }
+
+            ClassMember::TsIndexSignature(..) => body.push(member),
+        }
+    }
+
+    // Private names are replaced with `#private;`, which makes the class
+    // nominal.
+    if has_private_name {
+        body.insert(
+            0,
+            ClassMember::PrivateProp(PrivateProp {
+                span: DUMMY_SP,
+                key: PrivateName {
+                    span: DUMMY_SP,
+                    id: quote_ident!("private"),
+                },
+                value: None,
+                type_ann: None,
+                is_static: false,
+                decorators: vec![],
+                computed: false,
+                accessibility: None,
+                is_abstract: false,
+                is_optional: false,
+                readonly: false,
+                definite: false,
+            }),
+        );
+    }
+
+    Class {
+        decorators: vec![],
+        body,
+        ..class
+    }
+}
+
+/// Returns the name of a function declaration, and whether it has a body.
+fn fn_decl(item: &ModuleItem) -> Option<(&JsWord, bool)> {
+    match *item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+            ref ident,
+            ref function,
+            ..
+        })))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl:
+                Decl::Fn(FnDecl {
+                    ref ident,
+                    ref function,
+                    ..
+                }),
+            ..
+        })) => Some((&ident.sym, function.body.is_some())),
+        _ => None,
+    }
+}
+
+/// Returns the name of a method or a constructor, and whether it has a body.
+fn method_decl(member: &ClassMember) -> Option<(&JsWord, bool)> {
+    match *member {
+        ClassMember::Constructor(ref c) => Some((prop_name_sym(&c.key)?, c.body.is_some())),
+        ClassMember::Method(ref m) => Some((prop_name_sym(&m.key)?, m.function.body.is_some())),
+        _ => None,
+    }
+}
+
+fn prop_name_sym(key: &PropName) -> Option<&JsWord> {
+    match *key {
+        PropName::Ident(ref i) => Some(&i.sym),
+        PropName::Str(ref s) => Some(&s.value),
+        PropName::Num(..) | PropName::Computed(..) => None,
+    }
+}
+
+/// Names exported by `export { a, b as c }` and `export default a`.
+fn local_exports(items: &[ModuleItem]) -> HashSet<JsWord> {
+    let mut names = HashSet::default();
+
+    for item in items {
+        match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                src: None,
+                ..
+            })) => {
+                for s in specifiers {
+                    if let ExportSpecifier::Named(ref s) = *s {
+                        names.insert(s.orig.sym.clone());
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr: box Expr::Ident(ref i),
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                expr: box Expr::Ident(ref i),
+                ..
+            })) => {
+                names.insert(i.sym.clone());
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+/// Names referenced by `typeof a` or `typeof a.b` in `items`.
+fn typeof_references(items: &[ModuleItem]) -> HashSet<JsWord> {
+    struct Collector(HashSet<JsWord>);
+
+    impl Visit<TsTypeQuery> for Collector {
+        fn visit(&mut self, q: &TsTypeQuery) {
+            let mut name = match q.expr_name {
+                TsTypeQueryExpr::TsEntityName(ref name) => name,
+                TsTypeQueryExpr::Import(..) => return,
+            };
+            loop {
+                match *name {
+                    TsEntityName::TsQualifiedName(ref q) => name = &q.left,
+                    TsEntityName::Ident(ref i) => {
+                        self.0.insert(i.sym.clone());
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut v = Collector(Default::default());
+    for item in items {
+        item.visit_with(&mut v);
+    }
+    v.0
+}
+
+fn decl_names(decl: &Decl) -> Vec<&JsWord> {
+    match *decl {
+        Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+            vec![&ident.sym]
+        }
+        Decl::Var(ref v) => v
+            .decls
+            .iter()
+            .filter_map(|d| match d.name {
+                Pat::Ident(ref i) => Some(&i.sym),
+                _ => None,
+            })
+            .collect(),
+        Decl::TsEnum(TsEnumDecl { ref id, .. })
+        | Decl::TsInterface(TsInterfaceDecl { ref id, .. })
+        | Decl::TsTypeAlias(TsTypeAliasDecl { ref id, .. }) => vec![&id.sym],
+        Decl::TsModule(TsModuleDecl {
+            id: TsModuleName::Ident(ref id),
+            ..
+        }) => vec![&id.sym],
+        Decl::TsModule(..) => vec![],
+    }
+}
+
+/// Returns true if `e` can be the initializer of a `const` in a declaration
+/// file.
+fn is_const_lit(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::Str(..)) | Expr::Lit(Lit::Num(..)) | Expr::Lit(Lit::Bool(..)) => true,
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            arg: box Expr::Lit(Lit::Num(..)),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+/// Type of a mutable variable initialized with `e`, if `e` is a literal.
+fn widened_lit_type(e: &Expr) -> Option<TsTypeAnn> {
+    let kind = match *e {
+        Expr::Lit(Lit::Str(..)) => TsKeywordTypeKind::TsStringKeyword,
+        Expr::Tpl(Tpl { ref exprs, .. }) if exprs.is_empty() => TsKeywordTypeKind::TsStringKeyword,
+        Expr::Lit(Lit::Num(..)) => TsKeywordTypeKind::TsNumberKeyword,
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            arg: box Expr::Lit(Lit::Num(..)),
+            ..
+        }) => TsKeywordTypeKind::TsNumberKeyword,
+        Expr::Lit(Lit::Bool(..)) => TsKeywordTypeKind::TsBooleanKeyword,
+        _ => return None,
+    };
+
+    let span = e.span();
+    Some(TsTypeAnn {
+        span,
+        type_ann: box TsType::TsKeywordType(TsKeywordType { span, kind }),
+    })
+}
+
+fn error(span: Span, msg: &str) {
+    HANDLER.with(|handler| handler.struct_span_err(span, msg).emit());
+}
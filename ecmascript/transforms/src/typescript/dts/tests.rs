@@ -0,0 +1,186 @@
+use super::dts;
+use crate::tests::Tester;
+use swc_ecma_parser::{Syntax, TsConfig};
+
+fn syntax() -> Syntax {
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    syntax(),
+    |_| dts(),
+    functions,
+    "export function f(a: number, b = 1, ...c: string[]): number {
+    return a;
+}
+export async function g({ a = 1 }: { a?: number }): Promise<void> {}
+function h(): void {}",
+    "export declare function f(a: number, b?: number, ...c: string[]): number;
+export declare function g({ a }: { a?: number }): Promise<void>;"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    overloads,
+    "export function f(a: string): string;
+export function f(a: number): number;
+export function f(a: any): any {
+    return a;
+}",
+    "export declare function f(a: string): string;
+export declare function f(a: number): number;"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    variables,
+    "export const a = 1, b = 'b';
+export let c = 2, d: string[] = [];
+let e = 3;",
+    "export declare const a = 1, b = 'b';
+export declare let c: number, d: string[];"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    class,
+    "export class A<T> extends B<T> implements C {
+    private x = 1;
+    readonly y = 'y';
+    static z: number = 1;
+    constructor(public a: string, private b: number) {
+        super();
+    }
+    get v(): number {
+        return 1;
+    }
+    set v(value: number) {}
+    @dec
+    m<U>(u: U): T {
+        return null;
+    }
+    private p(a) {}
+}",
+    "export declare class A<T> extends B<T> implements C {
+    private x;
+    readonly y = 'y';
+    static z: number;
+    public a: string;
+    private b;
+    constructor(a: string, b: number);
+    get v(): number;
+    set v(value: number);
+    m<U>(u: U): T;
+    private p;
+}"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    param_decorators,
+    "export class A {
+    constructor(@inject() a: string, @inject() private b: number) {}
+    m(@arg() x: number): void {}
+}",
+    "export declare class A {
+    private b;
+    constructor(a: string, b: number);
+    m(x: number): void;
+}"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    types_and_namespaces,
+    "interface Props {
+    a: string;
+}
+type Id = string;
+enum E {
+    A = 1
+}
+export namespace N {
+    export const x: number = 1;
+    function hidden() {}
+    export function f(): Props {
+        return null;
+    }
+}
+export { Id, E };
+function local() {}
+console.log(local());",
+    "interface Props {
+    a: string;
+}
+type Id = string;
+declare enum E {
+    A = 1
+}
+export declare namespace N {
+    export const x: number;
+    export function f(): Props;
+}
+export { Id, E };"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    export_default,
+    "function f(): void {}
+export default f;",
+    "declare function f(): void;
+export default f;"
+);
+
+test!(
+    syntax(),
+    |_| dts(),
+    typeof_local,
+    "const port = 8080;
+const unused = 1, version: string = '1.0';
+function create(a: number): string {
+    return '';
+}
+function hidden() {}
+export type Port = typeof port;
+export const v: typeof version = version;
+export type Create = typeof create;",
+    "declare const port = 8080;
+declare const version: string;
+declare function create(a: number): string;
+export type Port = typeof port;
+export declare const v: typeof version;
+export type Create = typeof create;"
+);
+
+#[test]
+fn missing_annotations() {
+    Tester::run(|tester| {
+        tester.apply_transform(
+            dts(),
+            "input.ts",
+            syntax(),
+            "export function f(a) {}
+export const { b } = c;
+export let d = [];
+export class E {
+    f = g();
+    private h = i();
+}
+function local(a) {}",
+        )?;
+
+        assert_eq!(tester.handler.err_count(), 5);
+
+        Ok(())
+    })
+}
@@ -307,3 +307,190 @@ test!(
 }));",
     ok_if_code_eq
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_simple,
+    "namespace Foo {
+    export const x = 1;
+    const y = x + 1;
+    export function f(x) {
+        return x + y;
+    }
+}",
+    "var Foo;
+(function (Foo) {
+    Foo.x = 1;
+    const y = Foo.x + 1;
+    function f(x) {
+        return x + y;
+    }
+    Foo.f = f;
+})(Foo || (Foo = {}));",
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_export,
+    "export namespace Foo {
+    export namespace Bar {
+        export let a = 1;
+        a = 2;
+    }
+    export interface T {}
+}
+namespace Types {
+    export type T = string;
+}
+declare namespace Ambient {
+    const x: number;
+}
+export { Types, Ambient };",
+    "export var Foo;
+(function (Foo) {
+    (function (Bar) {
+        Bar.a = 1;
+        Bar.a = 2;
+    })(Foo.Bar || (Foo.Bar = {}));
+})(Foo || (Foo = {}));",
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_dotted,
+    "namespace A.B.C {
+    export const x = 1;
+}",
+    "var A;
+(function (A) {
+    (function (B) {
+        (function (C) {
+            C.x = 1;
+        })(B.C || (B.C = {}));
+    })(A.B || (A.B = {}));
+})(A || (A = {}));",
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_merging,
+    "class Foo {}
+namespace Foo {
+    export const x = 1;
+}
+enum Bar { a }
+namespace Bar {
+    export function f() {}
+}
+namespace Bar {
+    export const { b, c } = obj;
+}",
+    "class Foo {
+}
+(function (Foo) {
+    Foo.x = 1;
+})(Foo || (Foo = {}));
+var Bar;
+(function (Bar) {
+    Bar[Bar['a'] = 0] = 'a';
+})(Bar || (Bar = {}));
+(function (Bar) {
+    function f() {
+    }
+    Bar.f = f;
+})(Bar || (Bar = {}));
+(function (Bar) {
+    const { b, c } = obj;
+    Bar.b = b;
+    Bar.c = c;
+})(Bar || (Bar = {}));",
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_shadowed_by_declarations,
+    "namespace N {
+    export const x = 1;
+    function f() {
+        const x = 2;
+        return x;
+    }
+    function g() {
+        if (x) {
+            var x;
+        }
+        return x;
+    }
+    {
+        let x = 2;
+        x;
+    }
+    try {
+    } catch (x) {
+        x;
+    }
+    for (const x of arr) {
+        x;
+    }
+    x;
+}",
+    "var N;
+(function (N) {
+    N.x = 1;
+    function f() {
+        const x = 2;
+        return x;
+    }
+    function g() {
+        if (x) {
+            var x;
+        }
+        return x;
+    }
+    {
+        let x = 2;
+        x;
+    }
+    try {
+    } catch (x) {
+        x;
+    }
+    for (const x of arr) {
+        x;
+    }
+    N.x;
+})(N || (N = {}));",
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    namespace_pattern_assignment,
+    "namespace N {
+    export let x = 1, y = 2;
+    [x] = arr;
+    ({ x, y = 3 } = obj);
+    for (x of arr) {
+    }
+}",
+    "var N;
+(function (N) {
+    N.x = 1;
+    N.y = 2;
+    [N.x] = arr;
+    ({ x: N.x, y: N.y = 3 } = obj);
+    for (N.x of arr) {
+    }
+})(N || (N = {}));",
+    ok_if_code_eq
+);
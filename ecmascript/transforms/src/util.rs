@@ -21,7 +21,8 @@ use std::{
 };
 use swc_atoms::{js_word, JsWord};
 use swc_common::{
-    errors::Handler, Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith, DUMMY_SP,
+    comments::Comments, errors::Handler, Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith,
+    DUMMY_SP,
 };
 use unicode_xid::UnicodeXID;
 
@@ -664,6 +665,12 @@ pub trait ExprExt {
                 ref right,
                 ..
             })
+            | Expr::Bin(BinExpr {
+                ref left,
+                op: op!("??"),
+                ref right,
+                ..
+            })
             | Expr::Cond(CondExpr {
                 cons: ref left,
                 alt: ref right,
@@ -916,7 +923,8 @@ pub trait ExprExt {
             | Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
             | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
             | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. }) => expr.may_have_side_effects(),
-            Expr::TsOptChain(ref e) => e.expr.may_have_side_effects(),
+
+            Expr::OptChain(ref e) => e.expr.may_have_side_effects(),
 
             Expr::Invalid(..) => unreachable!(),
         }
@@ -1146,7 +1154,7 @@ not_lit!(TsTypeAssertion);
 not_lit!(TsConstAssertion);
 
 not_lit!(PrivateName);
-not_lit!(TsOptChain);
+not_lit!(OptChainExpr);
 
 not_lit!(SpreadElement);
 not_lit!(Invalid);
@@ -1289,11 +1297,14 @@ pub fn default_constructor(has_super: bool) -> Constructor {
         accessibility: Default::default(),
         is_optional: false,
         params: if has_super {
-            vec![PatOrTsParamProp::Pat(Pat::Rest(RestPat {
-                dot3_token: DUMMY_SP,
-                arg: box Pat::Ident(quote_ident!(span, "args")),
-                type_ann: Default::default(),
-            }))]
+            vec![ParamOrTsParamProp::Param(
+                Pat::Rest(RestPat {
+                    dot3_token: DUMMY_SP,
+                    arg: box Pat::Ident(quote_ident!(span, "args")),
+                    type_ann: Default::default(),
+                })
+                .into(),
+            )]
         } else {
             vec![]
         },
@@ -1509,6 +1520,14 @@ impl<'a> UsageFinder<'a> {
 
 scoped_thread_local!(pub static HANDLER: Handler);
 
+scoped_thread_local!(
+    /// Comments of the file being transformed.
+    ///
+    /// Passes which care about annotations like `/*#__PURE__*/` should check
+    /// if this is set.
+    pub static COMMENTS: Comments
+);
+
 /// make a new expression which evaluates `val` preserving side effects, if any.
 pub(crate) fn preserve_effects<I>(span: Span, val: Expr, exprs: I) -> Expr
 where
@@ -1642,7 +1661,7 @@ where
             | Expr::TsTypeCast(TsTypeCastExpr { expr, .. })
             | Expr::TsAs(TsAsExpr { expr, .. })
             | Expr::TsConstAssertion(TsConstAssertion { expr, .. }) => add_effects(v, expr),
-            Expr::TsOptChain(e) => add_effects(v, e.expr),
+            Expr::OptChain(e) => add_effects(v, e.expr),
 
             Expr::Invalid(..) => unreachable!(),
         }
@@ -1,4 +1,4 @@
-use crate::config::{GlobalPassOption, JscTarget, ModuleConfig};
+use crate::config::{GlobalPassOption, JscTarget, MinifyOptions, ModuleConfig};
 use atoms::JsWord;
 use common::{chain, errors::Handler, SourceMap};
 use ecmascript::{
@@ -7,8 +7,9 @@ use ecmascript::{
     preset_env,
     transforms::{
         chain_at, compat, const_modules, fixer, helpers, hygiene, modules,
+        optimization::{compressor, inline_vars, mangler},
         pass::{JoinedPass, Optional, Pass},
-        typescript,
+        typescript, util,
     },
 };
 use either::Either;
@@ -23,6 +24,9 @@ pub struct PassBuilder<'a, 'b, P: Pass> {
     pass: P,
     target: JscTarget,
     loose: bool,
+    minify: Option<MinifyOptions>,
+    inline_vars: Option<inline_vars::Config>,
+    inject_helpers: bool,
 }
 
 impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
@@ -34,6 +38,9 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             target: JscTarget::Es5,
             loose,
             env: None,
+            minify: None,
+            inline_vars: None,
+            inject_helpers: true,
         }
     }
 
@@ -46,6 +53,9 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             target: self.target,
             loose: self.loose,
             env: self.env,
+            minify: self.minify,
+            inline_vars: self.inline_vars,
+            inject_helpers: self.inject_helpers,
         }
     }
 
@@ -75,6 +85,25 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
         self
     }
 
+    /// Enables the compressor and the mangler.
+    pub fn minify(mut self, minify: Option<MinifyOptions>) -> Self {
+        self.minify = minify;
+        self
+    }
+
+    /// Enables inlining of variables.
+    pub fn inline_vars(mut self, config: Option<inline_vars::Config>) -> Self {
+        self.inline_vars = config;
+        self
+    }
+
+    /// Disables the helper injector if `inject` is false, so that helpers
+    /// can be injected by the caller.
+    pub fn inject_helpers(mut self, inject: bool) -> Self {
+        self.inject_helpers = inject;
+        self
+    }
+
     /// # Arguments
     /// ## module
     ///  - Use `None` if you want swc to emit import statements.
@@ -83,23 +112,34 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
     /// Returned pass includes
     ///
     ///  - compatibility helper
+    ///  - variable inliner (if enabled)
     ///  - module handler
-    ///  - helper injector
+    ///  - helper injector (if enabled)
+    ///  - compressor (if minify is enabled)
     ///  - identifier hygiene handler
+    ///  - mangler (if minify is enabled)
     ///  - fixer
     pub fn finalize(self, syntax: Syntax, module: Option<ModuleConfig>) -> impl Pass {
         let need_interop_analysis = match module {
             Some(ModuleConfig::CommonJs(ref c)) => !c.no_interop,
             Some(ModuleConfig::Amd(ref c)) => !c.config.no_interop,
             Some(ModuleConfig::Umd(ref c)) => !c.config.no_interop,
-            None => false,
+            Some(ModuleConfig::SystemJs(..)) | Some(ModuleConfig::Es6(..)) | None => false,
         };
 
+        let enable_minifier = self.minify.is_some();
+        let minify = self.minify.unwrap_or_default();
+        let enable_inline_vars = self.inline_vars.is_some();
+        let inline_vars_config = self.inline_vars.unwrap_or_default();
+
         // compat
         let compat_pass = if let Some(env) = self.env {
-            Either::Left(preset_env::preset_env(env))
+            // Invalid targets are reported through the handler.
+            Either::Left(util::HANDLER.set(self.handler, || preset_env::preset_env(env)))
         } else {
             Either::Right(chain!(
+                Optional::new(compat::es2020(), self.target <= JscTarget::Es2019),
+                Optional::new(compat::es2019(), self.target <= JscTarget::Es2018),
                 Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
                 Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
                 Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
@@ -124,15 +164,19 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             Program,
             self.pass,
             compat_pass,
+            Optional::new(inline_vars(inline_vars_config), enable_inline_vars),
             // module / helper
             Optional::new(
                 modules::import_analysis::import_analyzer(),
                 need_interop_analysis
             ),
-            helpers::InjectHelpers,
+            Optional::new(helpers::InjectHelpers, self.inject_helpers),
             ModuleConfig::build(self.cm.clone(), module),
+            Optional::new(compressor(minify.compress), enable_minifier),
             // hygiene
             hygiene(),
+            // The mangler does not use syntax contexts.
+            Optional::new(mangler(minify.mangle), enable_minifier),
             // fixer
             fixer(),
         )
@@ -0,0 +1,728 @@
+//! Bundles ES modules into a single module.
+
+use self::resolve::resolve;
+use crate::{
+    config::{Options, OutputConfig, PrintConfig, SourceMapsConfig},
+    error::Error,
+    Compiler, TransformOutput,
+};
+use atoms::JsWord;
+use common::{
+    comments::Comments, util::move_map::MoveMap, Fold, FoldWith, Mark, SourceFile, Span,
+    SyntaxContext, DUMMY_SP,
+};
+use ecmascript::{
+    ast::*,
+    transforms::{
+        fixer,
+        helpers::{self, Helpers, InjectHelpers},
+        hygiene, resolver, util,
+    },
+};
+use hashbrown::{HashMap, HashSet};
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+mod resolve;
+
+/// Bundler apis.
+impl Compiler {
+    /// Bundles `entries` and modules imported from them into a single ES
+    /// module.
+    ///
+    /// Imports are resolved like node, preferring `module` over `main` of
+    /// `package.json`. Each module is parsed once and compiled with the config
+    /// for the file, except `module`. Modules are then linked into a single
+    /// scope, and exports of entries become exports of the bundle. Helpers
+    /// used by modules are injected once.
+    pub fn bundle(&self, entries: &[PathBuf], opts: &Options) -> Result<TransformOutput, Error> {
+        self.run(|| {
+            let opts = Options {
+                is_module: true,
+                keep_modules: true,
+                skip_helper_injection: true,
+                ..opts.clone()
+            };
+            let comments = Comments::default();
+
+            let mut bundler = Bundler {
+                compiler: self,
+                opts: &opts,
+                comments: &comments,
+                minify: None,
+                output: None,
+                helpers: None,
+                ids: Default::default(),
+                modules: vec![],
+                order: vec![],
+                namespaces: vec![],
+            };
+
+            let entries = entries
+                .iter()
+                .map(|entry| bundler.load(entry))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let program = bundler.link(&entries)?;
+            let program = program.fold_with(&mut hygiene()).fold_with(&mut fixer());
+            let program = program.fold_with(&mut ExportCleaner);
+
+            let cwd = opts.cwd.canonicalize().unwrap_or_else(|_| opts.cwd.clone());
+            let files = bundler
+                .modules
+                .iter()
+                .map(|m| {
+                    let name = m.path.strip_prefix(&cwd).unwrap_or(&m.path);
+                    (m.fm.clone(), name.display().to_string())
+                })
+                .collect::<Vec<_>>();
+
+            self.print_files(
+                &program,
+                &files,
+                &comments,
+                PrintConfig {
+                    source_maps: &opts
+                        .source_maps
+                        .clone()
+                        .unwrap_or(SourceMapsConfig::Bool(false)),
+                    source_file_name: None,
+                    source_root: opts.source_root.as_ref().map(|s| &**s),
+                    orig: None,
+                    minify: bundler.minify.unwrap_or(false),
+                    output: &bundler.output.unwrap_or_default(),
+                },
+            )
+        })
+    }
+}
+
+struct Bundler<'a> {
+    compiler: &'a Compiler,
+    opts: &'a Options,
+    comments: &'a Comments,
+    /// `minify` of the first entry.
+    minify: Option<bool>,
+    /// `jsc.output` of the first entry.
+    output: Option<OutputConfig>,
+    /// Helpers used by all modules, which are injected once after linking.
+    ///
+    /// `jsc.externalHelpers` of the first entry is used.
+    helpers: Option<Helpers>,
+    ids: HashMap<PathBuf, usize>,
+    modules: Vec<ModuleInfo>,
+    /// Dependencies come before dependants.
+    order: Vec<usize>,
+    /// Namespace objects, which are emitted after the module.
+    namespaces: Vec<(usize, Ident)>,
+}
+
+struct ModuleInfo {
+    path: PathBuf,
+    fm: Arc<SourceFile>,
+    body: Vec<ModuleItem>,
+    /// Used to create identifiers for the module.
+    mark: Mark,
+    deps: HashMap<JsWord, usize>,
+    imports: HashMap<(JsWord, SyntaxContext), (usize, Imported)>,
+    exports: HashMap<JsWord, Export>,
+    star_exports: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Imported {
+    Name(JsWord),
+    Namespace,
+}
+
+#[derive(Debug, Clone)]
+enum Export {
+    Local(Ident),
+    /// `export { orig as name } from 'dep'`
+    Reexport(usize, JsWord),
+    /// `export * as name from 'dep'`
+    Namespace(usize),
+}
+
+impl Bundler<'_> {
+    /// Loads the module at `path` and its dependencies.
+    fn load(&mut self, path: &Path) -> Result<usize, Error> {
+        let path = path
+            .canonicalize()
+            .map_err(|err| Error::FailedToReadModule { err })?;
+        if let Some(&id) = self.ids.get(&path) {
+            return Ok(id);
+        }
+
+        let fm = self
+            .compiler
+            .cm
+            .load_file(&path)
+            .map_err(|err| Error::FailedToReadModule { err })?;
+        let module = self.compile(fm.clone())?;
+
+        let id = self.modules.len();
+        self.ids.insert(path.clone(), id);
+
+        let specifiers = module
+            .body
+            .iter()
+            .filter_map(|item| match *item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { ref src, .. }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(ref src),
+                    ..
+                }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { ref src, .. })) => {
+                    Some(src.value.clone())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.modules.push(ModuleInfo {
+            path: path.clone(),
+            fm,
+            body: module.body,
+            mark: Mark::fresh(Mark::root()),
+            deps: Default::default(),
+            imports: Default::default(),
+            exports: Default::default(),
+            star_exports: Default::default(),
+        });
+
+        for specifier in specifiers {
+            if self.modules[id].deps.contains_key(&specifier) {
+                continue;
+            }
+
+            let dep_path =
+                resolve(&path, &specifier).ok_or_else(|| Error::FailedToResolveModule {
+                    specifier: specifier.to_string(),
+                    base: path.display().to_string(),
+                })?;
+            let dep = self.load(&dep_path)?;
+            self.modules[id].deps.insert(specifier, dep);
+        }
+
+        self.order.push(id);
+        Ok(id)
+    }
+
+    /// Compiles a module with the config for it.
+    fn compile(&mut self, fm: Arc<SourceFile>) -> Result<Module, Error> {
+        let config = self.compiler.config_for_file(self.opts, &fm)?;
+        if self.minify.is_none() {
+            self.minify = Some(config.minify);
+            self.output = Some(config.output.clone());
+            self.helpers = Some(Helpers::new(config.external_helpers));
+        }
+
+        let program =
+            self.compiler
+                .parse_js(fm, config.target, config.syntax, true, Some(self.comments))?;
+
+        let mut pass = config.pass;
+        let helpers = self.helpers.as_ref().unwrap();
+        let program = helpers::HELPERS.set(helpers, || {
+            util::HANDLER.set(&self.compiler.handler, || {
+                util::COMMENTS.set(self.comments, || program.fold_with(&mut pass))
+            })
+        });
+
+        let module = match program {
+            Program::Module(module) => module,
+            Program::Script(..) => unreachable!("bundler parses files as modules"),
+        };
+
+        // Names are unique in the module after hygiene, so we can resolve
+        // them again to give top-level bindings of each module a different
+        // context.
+        Ok(module
+            .fold_with(&mut ContextCleaner)
+            .fold_with(&mut resolver()))
+    }
+
+    /// Links all loaded modules.
+    fn link(&mut self, entries: &[usize]) -> Result<Program, Error> {
+        for id in 0..self.modules.len() {
+            self.extract(id);
+        }
+
+        let mut chunks = vec![];
+        for i in 0..self.order.len() {
+            let id = self.order[i];
+
+            let mut renames = HashMap::default();
+            let imports = self.modules[id]
+                .imports
+                .iter()
+                .map(|(local, import)| (local.clone(), import.clone()))
+                .collect::<Vec<_>>();
+            for (local, (dep, imported)) in imports {
+                let target = match imported {
+                    Imported::Name(name) => self.resolve_export(dep, &name, &mut vec![])?,
+                    Imported::Namespace => self.namespace(dep),
+                };
+                renames.insert(local, target);
+            }
+
+            let body = mem::replace(&mut self.modules[id].body, vec![]);
+            chunks.push((id, body.fold_with(&mut Renamer { renames: &renames })));
+        }
+
+        let mut specifiers = vec![];
+        for &entry in entries {
+            for name in self.export_names(entry, &mut vec![]) {
+                let orig = self.resolve_export(entry, &name, &mut vec![])?;
+                specifiers.push(ExportSpecifier::Named(NamedExportSpecifier {
+                    span: DUMMY_SP,
+                    orig,
+                    exported: Some(Ident::new(name, DUMMY_SP)),
+                }));
+            }
+        }
+
+        // Namespace objects may refer to other namespace objects.
+        let mut namespaces = HashMap::<_, Vec<_>>::default();
+        let mut i = 0;
+        while i < self.namespaces.len() {
+            let (id, ident) = self.namespaces[i].clone();
+            let decl = self.namespace_decl(id, ident)?;
+            namespaces.entry(id).or_default().push(decl);
+            i += 1;
+        }
+
+        // A namespace object is emitted right after the module.
+        let mut body = vec![];
+        for (id, chunk) in chunks {
+            body.extend(chunk);
+            body.extend(namespaces.remove(&id).into_iter().flatten());
+        }
+
+        util::prepend_stmts(&mut body, self.helpers().into_iter());
+
+        if !specifiers.is_empty() {
+            body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                NamedExport {
+                    span: DUMMY_SP,
+                    specifiers,
+                    src: None,
+                },
+            )));
+        }
+
+        Ok(Program::Module(Module {
+            span: DUMMY_SP,
+            body,
+            shebang: None,
+        }))
+    }
+
+    /// Creates helpers used by modules.
+    fn helpers(&self) -> Vec<ModuleItem> {
+        let helpers = match self.helpers {
+            Some(ref helpers) => helpers,
+            None => return vec![],
+        };
+
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![],
+            shebang: None,
+        };
+        // References to helpers lost their context in `compile`.
+        helpers::HELPERS
+            .set(helpers, || module.fold_with(&mut InjectHelpers))
+            .fold_with(&mut ContextCleaner)
+            .body
+    }
+
+    /// Removes import and export statements of a module, recording them.
+    fn extract(&mut self, id: usize) {
+        let body = mem::replace(&mut self.modules[id].body, vec![]);
+        let m = &mut self.modules[id];
+        let mark = m.mark;
+
+        let body = body.move_flat_map(|item| {
+            let decl = match item {
+                ModuleItem::Stmt(..) => return Some(item),
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let dep = m.deps[&import.src.value];
+                    for s in import.specifiers {
+                        let (local, imported) = match s {
+                            ImportSpecifier::Specific(ImportSpecific {
+                                local, imported, ..
+                            }) => {
+                                let name = imported.as_ref().unwrap_or(&local).sym.clone();
+                                (local, Imported::Name(name))
+                            }
+                            ImportSpecifier::Default(ImportDefault { local, .. }) => {
+                                (local, Imported::Name("default".into()))
+                            }
+                            ImportSpecifier::Namespace(ImportStarAs { local, .. }) => {
+                                (local, Imported::Namespace)
+                            }
+                        };
+                        m.imports
+                            .insert((local.sym, local.span.ctxt()), (dep, imported));
+                    }
+
+                    None
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    let mut ids = vec![];
+                    match decl {
+                        Decl::Fn(FnDecl { ref ident, .. })
+                        | Decl::Class(ClassDecl { ref ident, .. }) => ids.push(ident.clone()),
+                        Decl::Var(VarDecl { ref decls, .. }) => {
+                            for decl in decls {
+                                bindings_of(&decl.name, &mut ids);
+                            }
+                        }
+                        _ => {}
+                    }
+                    for i in ids {
+                        m.exports.insert(i.sym.clone(), Export::Local(i));
+                    }
+
+                    Some(ModuleItem::Stmt(Stmt::Decl(decl)))
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => {
+                    let default_ident = || Ident::new("_default".into(), DUMMY_SP.apply_mark(mark));
+
+                    let decl = match decl {
+                        DefaultDecl::Class(ClassExpr { ident, class }) => Decl::Class(ClassDecl {
+                            ident: ident.unwrap_or_else(default_ident),
+                            class,
+                            declare: false,
+                        }),
+                        DefaultDecl::Fn(FnExpr { ident, function }) => Decl::Fn(FnDecl {
+                            ident: ident.unwrap_or_else(default_ident),
+                            function,
+                            declare: false,
+                        }),
+                        DefaultDecl::TsInterfaceDecl(..) => return None,
+                    };
+                    let ident = match decl {
+                        Decl::Fn(FnDecl { ref ident, .. })
+                        | Decl::Class(ClassDecl { ref ident, .. }) => ident.clone(),
+                        _ => unreachable!(),
+                    };
+                    m.exports.insert("default".into(), Export::Local(ident));
+
+                    Some(ModuleItem::Stmt(Stmt::Decl(decl)))
+                }
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { span, expr }) => {
+                    let ident = Ident::new("_default".into(), DUMMY_SP.apply_mark(mark));
+                    m.exports
+                        .insert("default".into(), Export::Local(ident.clone()));
+
+                    Some(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                        span,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls: vec![VarDeclarator {
+                            span,
+                            name: Pat::Ident(ident),
+                            init: Some(expr),
+                            definite: false,
+                        }],
+                    }))))
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    specifiers, src, ..
+                }) => {
+                    let dep = src.map(|src| m.deps[&src.value]);
+                    for s in specifiers {
+                        let (name, export) = match s {
+                            ExportSpecifier::Named(NamedExportSpecifier {
+                                orig, exported, ..
+                            }) => {
+                                let name = exported.as_ref().unwrap_or(&orig).sym.clone();
+                                match dep {
+                                    Some(dep) => (name, Export::Reexport(dep, orig.sym)),
+                                    None => (name, Export::Local(orig)),
+                                }
+                            }
+                            ExportSpecifier::Default(DefaultExportSpecifier { exported }) => {
+                                match dep {
+                                    Some(dep) => {
+                                        (exported.sym, Export::Reexport(dep, "default".into()))
+                                    }
+                                    None => continue,
+                                }
+                            }
+                            ExportSpecifier::Namespace(NamespaceExportSpecifier {
+                                name, ..
+                            }) => match dep {
+                                Some(dep) => (name.sym, Export::Namespace(dep)),
+                                None => continue,
+                            },
+                        };
+                        m.exports.insert(name, export);
+                    }
+
+                    None
+                }
+
+                ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                    m.star_exports.push(m.deps[&src.value]);
+                    None
+                }
+
+                decl => Some(ModuleItem::ModuleDecl(decl)),
+            }
+        });
+
+        self.modules[id].body = body;
+    }
+
+    /// Returns the binding exported from module `id` as `name`.
+    ///
+    /// `stack` is used to detect cycles of `export *`.
+    fn resolve_export(
+        &mut self,
+        id: usize,
+        name: &JsWord,
+        stack: &mut Vec<(usize, JsWord)>,
+    ) -> Result<Ident, Error> {
+        let key = (id, name.clone());
+        if stack.contains(&key) {
+            return Err(self.export_not_found(id, name));
+        }
+        stack.push(key);
+
+        let res = match self.modules[id].exports.get(name).cloned() {
+            Some(Export::Local(ident)) => {
+                let import = self.modules[id]
+                    .imports
+                    .get(&(ident.sym.clone(), ident.span.ctxt()))
+                    .cloned();
+                match import {
+                    // `import { a } from 'b'; export { a };`
+                    Some((dep, Imported::Name(orig))) => self.resolve_export(dep, &orig, stack),
+                    Some((dep, Imported::Namespace)) => Ok(self.namespace(dep)),
+                    None => Ok(ident),
+                }
+            }
+            Some(Export::Reexport(dep, orig)) => self.resolve_export(dep, &orig, stack),
+            Some(Export::Namespace(dep)) => Ok(self.namespace(dep)),
+            None => {
+                let mut found = None;
+                if *name != *"default" {
+                    for dep in self.modules[id].star_exports.clone() {
+                        if let Ok(ident) = self.resolve_export(dep, name, stack) {
+                            found = Some(ident);
+                            break;
+                        }
+                    }
+                }
+
+                found.ok_or_else(|| self.export_not_found(id, name))
+            }
+        };
+
+        stack.pop();
+        res
+    }
+
+    fn export_not_found(&self, id: usize, name: &JsWord) -> Error {
+        Error::ExportNotFound {
+            module: self.modules[id].path.display().to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    /// Returns names exported from module `id`, in a stable order.
+    fn export_names(&self, id: usize, stack: &mut Vec<usize>) -> Vec<JsWord> {
+        if stack.contains(&id) {
+            return vec![];
+        }
+        stack.push(id);
+
+        let mut names = self.modules[id]
+            .exports
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        for &dep in &self.modules[id].star_exports {
+            names.extend(
+                self.export_names(dep, stack)
+                    .into_iter()
+                    .filter(|name| *name != *"default"),
+            );
+        }
+
+        stack.pop();
+
+        let mut names = names.into_iter().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Returns the identifier of the namespace object of module `id`.
+    fn namespace(&mut self, id: usize) -> Ident {
+        if let Some((_, ident)) = self.namespaces.iter().find(|(m, _)| *m == id) {
+            return ident.clone();
+        }
+
+        let ident = Ident::new("_ns".into(), DUMMY_SP.apply_mark(self.modules[id].mark));
+        self.namespaces.push((id, ident.clone()));
+        ident
+    }
+
+    /// Creates `var _ns = { get a() { return a; } };`
+    fn namespace_decl(&mut self, id: usize, ident: Ident) -> Result<ModuleItem, Error> {
+        let mut props = vec![];
+        for name in self.export_names(id, &mut vec![]) {
+            let binding = self.resolve_export(id, &name, &mut vec![])?;
+            props.push(PropOrSpread::Prop(box Prop::Getter(GetterProp {
+                span: DUMMY_SP,
+                key: PropName::Ident(Ident::new(name, DUMMY_SP)),
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(box Expr::Ident(binding)),
+                    })],
+                }),
+            })));
+        }
+
+        Ok(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ident),
+                init: Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                })),
+                definite: false,
+            }],
+        }))))
+    }
+}
+
+fn bindings_of(pat: &Pat, ids: &mut Vec<Ident>) {
+    match *pat {
+        Pat::Ident(ref i) => ids.push(i.clone()),
+        Pat::Array(ArrayPat { ref elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                bindings_of(elem, ids)
+            }
+        }
+        Pat::Rest(RestPat { ref arg, .. }) => bindings_of(arg, ids),
+        Pat::Object(ObjectPat { ref props, .. }) => {
+            for prop in props {
+                match *prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                        bindings_of(value, ids)
+                    }
+                    ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => ids.push(key.clone()),
+                    ObjectPatProp::Rest(RestPat { ref arg, .. }) => bindings_of(arg, ids),
+                }
+            }
+        }
+        Pat::Assign(AssignPat { ref left, .. }) => bindings_of(left, ids),
+        Pat::Invalid(..) | Pat::Expr(..) => {}
+    }
+}
+
+/// Removes syntax contexts of identifiers.
+struct ContextCleaner;
+
+impl Fold<Span> for ContextCleaner {
+    fn fold(&mut self, span: Span) -> Span {
+        span.with_ctxt(SyntaxContext::empty())
+    }
+}
+
+/// Replaces imported bindings with bindings of other modules.
+struct Renamer<'a> {
+    renames: &'a HashMap<(JsWord, SyntaxContext), Ident>,
+}
+
+impl Renamer<'_> {
+    fn rename(&self, i: &Ident) -> Option<Ident> {
+        let target = self.renames.get(&(i.sym.clone(), i.span.ctxt()))?;
+
+        Some(Ident {
+            span: i.span.with_ctxt(target.span.ctxt()),
+            sym: target.sym.clone(),
+            ..i.clone()
+        })
+    }
+}
+
+impl Fold<Ident> for Renamer<'_> {
+    fn fold(&mut self, i: Ident) -> Ident {
+        self.rename(&i).unwrap_or(i)
+    }
+}
+
+impl Fold<Prop> for Renamer<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => match self.rename(&i) {
+                Some(value) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(value),
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Renamer<'_> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        if e.computed {
+            e.fold_children(self)
+        } else {
+            MemberExpr {
+                obj: e.obj.fold_with(self),
+                ..e
+            }
+        }
+    }
+}
+
+impl Fold<PropName> for Renamer<'_> {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(e) => PropName::Computed(e.fold_with(self)),
+            _ => n,
+        }
+    }
+}
+
+/// Converts `export { a as a }` to `export { a }`.
+struct ExportCleaner;
+
+impl Fold<NamedExportSpecifier> for ExportCleaner {
+    fn fold(&mut self, s: NamedExportSpecifier) -> NamedExportSpecifier {
+        match s.exported {
+            Some(ref exported) if exported.sym == s.orig.sym => NamedExportSpecifier {
+                exported: None,
+                ..s
+            },
+            _ => s,
+        }
+    }
+}
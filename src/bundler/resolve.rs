@@ -0,0 +1,77 @@
+//! Node-style module resolution.
+
+use serde_json::Value;
+use std::{
+    ffi::OsString,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// Extensions tried when an import does not have one.
+const EXTENSIONS: &[&str] = &["js", "mjs", "jsx", "ts", "tsx"];
+
+/// Resolves `specifier` imported from the file at `base`.
+pub(super) fn resolve(base: &Path, specifier: &str) -> Option<PathBuf> {
+    let dir = base.parent().unwrap_or_else(|| Path::new(""));
+
+    if is_relative(specifier) {
+        let path = dir.join(specifier);
+        return resolve_as_file(&path).or_else(|| resolve_as_dir(&path));
+    }
+
+    dir.ancestors()
+        .filter(|dir| dir.file_name().map_or(true, |name| name != "node_modules"))
+        .find_map(|dir| {
+            let path = dir.join("node_modules").join(specifier);
+            resolve_as_file(&path).or_else(|| resolve_as_dir(&path))
+        })
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier.starts_with('/')
+}
+
+fn resolve_as_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    EXTENSIONS
+        .iter()
+        .map(|ext| {
+            let mut path = OsString::from(path.as_os_str());
+            path.push(".");
+            path.push(ext);
+            PathBuf::from(path)
+        })
+        .find(|path| path.is_file())
+}
+
+fn resolve_as_dir(path: &Path) -> Option<PathBuf> {
+    if let Some(main) = package_main(&path.join("package.json")) {
+        let main = path.join(main);
+        if let Some(file) = resolve_as_file(&main).or_else(|| resolve_index(&main)) {
+            return Some(file);
+        }
+    }
+
+    resolve_index(path)
+}
+
+fn resolve_index(path: &Path) -> Option<PathBuf> {
+    resolve_as_file(&path.join("index"))
+}
+
+/// Returns `module` or `main` of a `package.json`.
+fn package_main(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let json: Value = serde_json::from_reader(file).ok()?;
+
+    ["module", "main"]
+        .iter()
+        .find_map(|field| json.get(field)?.as_str().map(String::from))
+}
@@ -6,11 +6,14 @@ use common::{errors::Handler, FileName, SourceMap};
 pub use ecmascript::parser::JscTarget;
 use ecmascript::{
     ast::{Expr, ExprStmt, ModuleItem, Program, Stmt},
+    codegen,
     parser::{lexer::Lexer, Parser, Session as ParseSess, SourceFileInput, Syntax},
     preset_env,
     transforms::{
         chain_at, const_modules, modules,
-        optimization::{simplifier, InlineGlobals, JsonParse},
+        optimization::{
+            compressor, inline_vars, mangler, simplifier, tree_shaker, InlineGlobals, JsonParse,
+        },
         pass::{noop, Optional, Pass},
         proposals::{class_properties, decorators, export},
         react, resolver, typescript,
@@ -19,7 +22,7 @@ use ecmascript::{
 use hashbrown::{HashMap, HashSet};
 use lazy_static::lazy_static;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::{
     env,
     path::{Path, PathBuf},
@@ -45,7 +48,7 @@ pub struct ParseOptions {
     pub target: JscTarget,
 }
 
-#[derive(Default, Deserialize)]
+#[derive(Default, Clone, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Options {
     #[serde(flatten, default)]
@@ -75,8 +78,12 @@ pub struct Options {
     #[serde(default)]
     pub swcrc_roots: Option<PathBuf>,
 
-    #[serde(default = "default_env_name")]
-    pub env_name: String,
+    /// Section of browserslist config to use.
+    ///
+    /// Defaults to `SWC_ENV`, or the default of
+    /// [preset_env::Config::browserslist_env] if it's not set.
+    #[serde(default)]
+    pub env_name: Option<String>,
 
     #[serde(default)]
     pub input_source_map: Option<InputSourceMap>,
@@ -92,6 +99,18 @@ pub struct Options {
 
     #[serde(default = "default_is_module")]
     pub is_module: bool,
+
+    /// Keep import and export statements even if `module` is configured.
+    ///
+    /// This is set by the bundler, which links modules by itself.
+    #[serde(skip)]
+    pub keep_modules: bool,
+
+    /// Don't inject helpers into the module.
+    ///
+    /// This is set by the bundler, which injects helpers once for all modules.
+    #[serde(skip)]
+    pub skip_helper_injection: bool,
 }
 
 fn default_is_module() -> bool {
@@ -105,6 +124,34 @@ pub enum SourceMapsConfig {
     Str(String),
 }
 
+impl SourceMapsConfig {
+    /// Returns true if a source map should be generated.
+    pub fn enabled(&self) -> bool {
+        match *self {
+            SourceMapsConfig::Bool(v) => v,
+            SourceMapsConfig::Str(_) => true,
+        }
+    }
+
+    /// Returns true if the source map should be appended to the generated code
+    /// as a `//# sourceMappingURL=` comment.
+    pub fn inline(&self) -> bool {
+        match *self {
+            SourceMapsConfig::Bool(_) => false,
+            SourceMapsConfig::Str(ref s) => s == "inline" || s == "both",
+        }
+    }
+
+    /// Returns true if the source map should be returned as
+    /// `TransformOutput.map`.
+    pub fn separate(&self) -> bool {
+        match *self {
+            SourceMapsConfig::Bool(v) => v,
+            SourceMapsConfig::Str(ref s) => s != "inline",
+        }
+    }
+}
+
 impl Default for SourceMapsConfig {
     fn default() -> Self {
         SourceMapsConfig::Bool(true)
@@ -125,11 +172,14 @@ impl Default for InputSourceMap {
 }
 
 impl Options {
+    /// `filename` is the path of the file being compiled, and it's used to
+    /// find the browserslist config.
     pub fn build(
         &self,
         cm: &Arc<SourceMap>,
         handler: &Handler,
         is_module: bool,
+        filename: Option<&Path>,
         config: Option<Config>,
     ) -> BuiltConfig<impl Pass> {
         let mut config = config.unwrap_or_else(Default::default);
@@ -137,18 +187,47 @@ impl Options {
             config.merge(c)
         }
 
+        let env = config.env.map(|mut env| {
+            if env.config_path.is_none() {
+                env.config_path = filename.map(Path::to_path_buf);
+            }
+            if env.browserslist_env.is_none() {
+                env.browserslist_env = self
+                    .env_name
+                    .clone()
+                    .or_else(|| env::var("SWC_ENV").ok());
+            }
+            env
+        });
+
         let JscConfig {
             transform,
             syntax,
             external_helpers,
             target,
             loose,
+            minify,
+            output,
         } = config.jsc;
 
-        let syntax = syntax.unwrap_or_default();
         let transform = transform.unwrap_or_default();
+        let mut syntax = syntax.unwrap_or_default();
+        if transform.decorators.decorators_before_export {
+            if let Syntax::Es(ref mut c) = syntax {
+                c.decorators_before_export = true;
+            }
+        }
+        let decorator_config = transform.decorators;
         let optimizer = transform.optimizer;
         let enable_optimizer = optimizer.is_some();
+        let inline_vars = optimizer.as_ref().and_then(|o| o.inline_vars);
+
+        let tree_shaking = {
+            let config = optimizer.as_ref().and_then(|o| o.tree_shaking.clone());
+            let enabled = config.is_some();
+
+            Optional::new(tree_shaker(config.unwrap_or_default()), enabled)
+        };
 
         let const_modules = {
             let enabled = transform.const_modules.is_some();
@@ -186,29 +265,63 @@ impl Options {
             Program,
             // handle jsx
             Optional::new(react::react(cm.clone(), transform.react), syntax.jsx()),
+            // Legacy decorators require type annotations for metadata.
+            Optional::new(
+                decorators(decorator_config),
+                syntax.decorators() && decorator_config.legacy
+            ),
             Optional::new(typescript::strip(), syntax.typescript()),
-            Optional::new(typescript::optional_chaining(), syntax.typescript()),
             Optional::new(class_properties(), syntax.typescript()),
             resolver(),
             const_modules,
             optimization,
-            Optional::new(decorators(Default::default()), syntax.decorators()),
+            Optional::new(
+                decorators(decorator_config),
+                syntax.decorators() && !decorator_config.legacy
+            ),
             Optional::new(class_properties(), syntax.class_props()),
             Optional::new(
                 export(),
                 syntax.export_default_from() || syntax.export_namespace_from()
             ),
             Optional::new(simplifier(), enable_optimizer),
+            tree_shaking,
             json_parse_pass
         );
 
+        let enable_minifier = config.minify.unwrap_or(false);
+
+        // Specifiers are rewritten relative to the file being compiled.
+        let module = config.module.map(|module| match module {
+            ModuleConfig::Es6(mut c) => {
+                let cwd = env::current_dir().unwrap_or_default();
+                let root = self.root.clone().unwrap_or_else(|| cwd.clone());
+
+                c.base_url = Some(root.join(c.base_url.unwrap_or_default()));
+                if c.filename.is_none() {
+                    c.filename = filename.map(|f| cwd.join(f));
+                }
+                ModuleConfig::Es6(c)
+            }
+            module => module,
+        });
+
         let pass = PassBuilder::new(&cm, &handler, loose, pass)
             .target(target)
-            .preset_env(config.env)
-            .finalize(syntax, config.module);
+            .preset_env(env)
+            .inline_vars(inline_vars)
+            .minify(if enable_minifier {
+                Some(minify.unwrap_or_default())
+            } else {
+                None
+            })
+            .inject_helpers(!self.skip_helper_injection)
+            .finalize(syntax, if self.keep_modules { None } else { module });
 
         BuiltConfig {
-            minify: config.minify.unwrap_or(false),
+            minify: enable_minifier,
+            output: output.unwrap_or_default(),
+            input_source_map: self.input_source_map.clone().unwrap_or_default(),
             pass,
             external_helpers,
             syntax,
@@ -216,13 +329,10 @@ impl Options {
             is_module,
             source_maps: self
                 .source_maps
-                .as_ref()
-                .map(|s| match s {
-                    SourceMapsConfig::Bool(v) => *v,
-                    // TODO: Handle source map
-                    SourceMapsConfig::Str(_) => true,
-                })
-                .unwrap_or(false),
+                .clone()
+                .unwrap_or(SourceMapsConfig::Bool(false)),
+            source_file_name: self.source_file_name.clone(),
+            source_root: self.source_root.clone(),
         }
     }
 }
@@ -422,11 +532,31 @@ pub struct BuiltConfig<P: Pass> {
     pub syntax: Syntax,
     pub target: JscTarget,
     pub minify: bool,
+    pub output: OutputConfig,
     pub external_helpers: bool,
-    pub source_maps: bool,
+    pub source_maps: SourceMapsConfig,
+    pub source_file_name: Option<String>,
+    pub source_root: Option<String>,
+    pub input_source_map: InputSourceMap,
     pub is_module: bool,
 }
 
+/// Options for printing a program.
+#[derive(Clone, Copy)]
+pub struct PrintConfig<'a> {
+    pub source_maps: &'a SourceMapsConfig,
+    /// Name of the input file in the source map.
+    ///
+    /// Defaults to the file name of the input file.
+    pub source_file_name: Option<&'a str>,
+    pub source_root: Option<&'a str>,
+    /// Source map of the input file, which the generated source map is chained
+    /// with so that it points to the original sources.
+    pub orig: Option<&'a sourcemap::SourceMap>,
+    pub minify: bool,
+    pub output: &'a OutputConfig,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct JscConfig {
@@ -444,6 +574,13 @@ pub struct JscConfig {
 
     #[serde(default)]
     pub loose: bool,
+
+    /// Used only if `minify` is true.
+    #[serde(default)]
+    pub minify: Option<MinifyOptions>,
+
+    #[serde(default)]
+    pub output: Option<OutputConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -456,6 +593,10 @@ pub enum ModuleConfig {
     Umd(modules::umd::Config),
     #[serde(rename = "amd")]
     Amd(modules::amd::Config),
+    #[serde(rename = "systemjs")]
+    SystemJs(modules::system_js::Config),
+    #[serde(rename = "es6")]
+    Es6(modules::es6::Config),
 }
 
 impl ModuleConfig {
@@ -465,6 +606,8 @@ impl ModuleConfig {
             Some(ModuleConfig::CommonJs(config)) => box modules::common_js::common_js(config),
             Some(ModuleConfig::Umd(config)) => box modules::umd::umd(cm, config),
             Some(ModuleConfig::Amd(config)) => box modules::amd::amd(config),
+            Some(ModuleConfig::SystemJs(config)) => box modules::system_js::system_js(config),
+            Some(ModuleConfig::Es6(config)) => box modules::es6::es6(config),
         }
     }
 }
@@ -480,6 +623,9 @@ pub struct TransformConfig {
 
     #[serde(default)]
     pub optimizer: Option<OptimizerConfig>,
+
+    #[serde(default)]
+    pub decorators: decorators::Config,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -497,6 +643,148 @@ pub struct OptimizerConfig {
 
     #[serde(default)]
     pub jsonify: Option<JsonifyOption>,
+
+    /// Inlines variables after the compat passes.
+    #[serde(default)]
+    pub inline_vars: Option<inline_vars::Config>,
+
+    /// Removes unused top-level declarations of a module.
+    #[serde(default)]
+    pub tree_shaking: Option<tree_shaker::Config>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MinifyOptions {
+    #[serde(default)]
+    pub compress: compressor::Config,
+
+    #[serde(default)]
+    pub mangle: mangler::Config,
+}
+
+/// Formatting of the generated code.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OutputConfig {
+    /// String used for one level of indentation, e.g. `"\t"` or `"  "`. It
+    /// should consist of tabs or spaces only.
+    ///
+    /// Defaults to four spaces.
+    #[serde(default, deserialize_with = "deserialize_indent")]
+    pub indent: Option<String>,
+
+    #[serde(default)]
+    pub new_line: NewLine,
+
+    /// Preferred quote for string literals.
+    #[serde(default)]
+    pub quote_style: QuoteStyle,
+
+    /// Escape non-ascii characters in strings, identifiers, templates and
+    /// regular expressions.
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    /// Emit numeric and string literals as they were written in the source.
+    #[serde(default)]
+    pub keep_raw_literals: bool,
+
+    /// Comments to keep in the output.
+    ///
+    /// Defaults to `"some"` if `minify` is true, and `"all"` otherwise.
+    #[serde(default)]
+    pub comments: Option<CommentRetention>,
+}
+
+impl OutputConfig {
+    pub fn codegen_config(&self, minify: bool) -> codegen::Config {
+        codegen::Config {
+            minify,
+            quote_style: match self.quote_style {
+                QuoteStyle::Single => codegen::QuoteStyle::Single,
+                QuoteStyle::Double => codegen::QuoteStyle::Double,
+            },
+            ascii_only: self.ascii_only,
+            keep_raw_literals: self.keep_raw_literals,
+            retain_comments: match self.comments {
+                Some(CommentRetention::All) => codegen::CommentRetention::All,
+                Some(CommentRetention::Some) => codegen::CommentRetention::Some,
+                Some(CommentRetention::None) => codegen::CommentRetention::None,
+                None if minify => codegen::CommentRetention::Some,
+                None => codegen::CommentRetention::All,
+            },
+        }
+    }
+
+    pub fn indent(&self) -> &str {
+        self.indent.as_ref().map(|s| &**s).unwrap_or("    ")
+    }
+}
+
+fn deserialize_indent<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let indent = Option::<String>::deserialize(deserializer)?;
+    if let Some(ref s) = indent {
+        if !s.bytes().all(|b| b == b' ') && !s.bytes().all(|b| b == b'\t') {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Str(s),
+                &"a string of tabs or spaces",
+            ));
+        }
+    }
+
+    Ok(indent)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NewLine {
+    #[serde(rename = "lf")]
+    Lf,
+    #[serde(rename = "crlf")]
+    CrLf,
+}
+
+impl Default for NewLine {
+    fn default() -> Self {
+        NewLine::Lf
+    }
+}
+
+impl NewLine {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NewLine::Lf => "\n",
+            NewLine::CrLf => "\r\n",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuoteStyle {
+    #[serde(rename = "single")]
+    Single,
+    #[serde(rename = "double")]
+    Double,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentRetention {
+    #[serde(rename = "all")]
+    All,
+    /// License comments and annotations like `/*#__PURE__*/`.
+    #[serde(rename = "some")]
+    Some,
+    #[serde(rename = "none")]
+    None,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Single
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
@@ -589,18 +877,6 @@ impl GlobalPassOption {
     }
 }
 
-fn default_env_name() -> String {
-    match env::var("SWC_ENV") {
-        Ok(v) => return v,
-        Err(_) => {}
-    }
-
-    match env::var("NODE_ENV") {
-        Ok(v) => v,
-        Err(_) => "development".into(),
-    }
-}
-
 pub trait Merge {
     /// Apply overrides from `from`
     fn merge(&mut self, from: &Self);
@@ -636,6 +912,8 @@ impl Merge for JscConfig {
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
         self.external_helpers.merge(&from.external_helpers);
+        self.minify.merge(&from.minify);
+        self.output.merge(&from.output);
     }
 }
 
@@ -673,12 +951,27 @@ impl Merge for TransformConfig {
         self.optimizer.merge(&from.optimizer);
         self.const_modules.merge(&from.const_modules);
         self.react.merge(&from.react);
+        self.decorators.merge(&from.decorators);
     }
 }
 
 impl Merge for OptimizerConfig {
     fn merge(&mut self, from: &Self) {
-        self.globals.merge(&from.globals)
+        self.globals.merge(&from.globals);
+        self.inline_vars.merge(&from.inline_vars);
+        self.tree_shaking.merge(&from.tree_shaking);
+    }
+}
+
+impl Merge for inline_vars::Config {
+    fn merge(&mut self, from: &Self) {
+        *self = *from;
+    }
+}
+
+impl Merge for tree_shaker::Config {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone();
     }
 }
 
@@ -694,6 +987,24 @@ impl Merge for react::Options {
     }
 }
 
+impl Merge for decorators::Config {
+    fn merge(&mut self, from: &Self) {
+        *self = *from;
+    }
+}
+
+impl Merge for MinifyOptions {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone()
+    }
+}
+
+impl Merge for OutputConfig {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone()
+    }
+}
+
 impl Merge for ConstModulesConfig {
     fn merge(&mut self, from: &Self) {
         *self = from.clone()
@@ -10,3 +10,45 @@ fn object() {
 fn array() {
     let _: Rc = serde_json::from_str(include_str!("array.json")).expect("failed to parse");
 }
+
+#[test]
+fn decorators() {
+    let _: Rc = serde_json::from_str(include_str!("decorators.json")).expect("failed to parse");
+}
+
+#[test]
+fn minify() {
+    let _: Rc = serde_json::from_str(include_str!("minify.json")).expect("failed to parse");
+}
+
+#[test]
+fn inline_vars() {
+    let _: Rc = serde_json::from_str(include_str!("inline_vars.json")).expect("failed to parse");
+}
+
+#[test]
+fn tree_shaking() {
+    let _: Rc = serde_json::from_str(include_str!("tree_shaking.json")).expect("failed to parse");
+}
+
+#[test]
+fn systemjs() {
+    let _: Rc = serde_json::from_str(include_str!("systemjs.json")).expect("failed to parse");
+}
+
+#[test]
+fn es6() {
+    let _: Rc = serde_json::from_str(include_str!("es6.json")).expect("failed to parse");
+}
+
+#[test]
+fn output() {
+    let _: Rc = serde_json::from_str(include_str!("output.json")).expect("failed to parse");
+}
+
+#[test]
+fn output_invalid_indent() {
+    let res: Result<Rc, _> =
+        serde_json::from_str(r#"{ "jsc": { "output": { "indent": " \t" } } }"#);
+    assert!(res.is_err());
+}
@@ -19,12 +19,24 @@ pub enum Error {
     #[fail(display = "failed to read module: {}", err)]
     FailedToReadModule { err: io::Error },
 
+    #[fail(display = "failed to resolve `{}` from {}", specifier, base)]
+    FailedToResolveModule { specifier: String, base: String },
+
+    #[fail(display = "{} does not export `{}`", module, name)]
+    ExportNotFound { module: String, name: String },
+
     #[fail(display = "failed to emit module: {}", err)]
     FailedToEmitModule { err: io::Error },
 
     #[fail(display = "failed to write sourcemap: {}", err)]
     FailedToWriteSourceMap { err: sourcemap::Error },
 
+    #[fail(display = "failed to read input source map: {}", err)]
+    FailedToReadInputSourceMap { err: io::Error },
+
+    #[fail(display = "failed to parse input source map: {}", err)]
+    FailedToParseInputSourceMap { err: sourcemap::Error },
+
     #[fail(display = "sourcemap is not utf8: {}", err)]
     SourceMapNotUtf8 { err: FromUtf8Error },
 
@@ -6,17 +6,22 @@ pub use ecmascript;
 pub use sourcemap;
 
 mod builder;
+mod bundler;
 pub mod config;
 pub mod error;
 
 pub use crate::builder::PassBuilder;
 use crate::{
-    config::{BuiltConfig, ConfigFile, JscTarget, Merge, Options, Rc, RootMode},
+    config::{
+        BuiltConfig, ConfigFile, InputSourceMap, JscTarget, Merge, Options, PrintConfig, Rc,
+        RootMode,
+    },
     error::Error,
 };
 use common::{
-    comments::Comments, errors::Handler, FileName, FoldWith, Globals, SourceFile, SourceMap,
-    GLOBALS,
+    comments::{Comment, CommentKind, Comments},
+    errors::Handler,
+    FileName, FoldWith, Globals, SourceFile, SourceMap, GLOBALS,
 };
 use ecmascript::{
     ast::Program,
@@ -31,9 +36,17 @@ pub use ecmascript::{
     parser::SourceFileInput,
     transforms::{chain_at, pass::Pass},
 };
+use chashmap::CHashMap;
+use rayon::prelude::*;
 use serde::Serialize;
-use sourcemap::SourceMapBuilder;
-use std::{fs::File, path::Path, sync::Arc};
+use sourcemap::{DecodedMap, SourceMapBuilder};
+use std::{
+    ffi::OsString,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
 
 pub struct Compiler {
     /// swc uses rustc's span interning.
@@ -43,6 +56,8 @@ pub struct Compiler {
     /// CodeMap
     pub cm: Arc<SourceMap>,
     pub handler: Handler,
+    /// Parsed config files and their modification times.
+    config_cache: CHashMap<PathBuf, (SystemTime, Rc)>,
 }
 
 #[derive(Serialize)]
@@ -107,43 +122,115 @@ impl Compiler {
         })
     }
 
+    /// Loads the source map of the input file.
+    ///
+    /// If `input_src_map` is `true`, this looks for a `//# sourceMappingURL=`
+    /// comment (a data url or a path relative to the input file) and falls
+    /// back to a `.map` file next to the input file. As the input source map is
+    /// optional in this case, a failure to load it is reported as a warning.
+    pub fn get_orig_src_map(
+        &self,
+        fm: &SourceFile,
+        input_src_map: &InputSourceMap,
+    ) -> Result<Option<sourcemap::SourceMap>, Error> {
+        match *input_src_map {
+            InputSourceMap::Bool(false) => Ok(None),
+            InputSourceMap::Bool(true) => {
+                // The source map is optional in this mode, so we only warn if it can't
+                // be loaded.
+                match find_orig_src_map(fm) {
+                    Ok(map) => Ok(map),
+                    Err(err) => {
+                        self.handler.warn(&format!(
+                            "failed to load the input source map of {}: {}",
+                            fm.name, err
+                        ));
+                        Ok(None)
+                    }
+                }
+            }
+            InputSourceMap::Str(ref s) => sourcemap::SourceMap::from_slice(s.as_bytes())
+                .map(Some)
+                .map_err(|err| Error::FailedToParseInputSourceMap { err }),
+        }
+    }
+
+    /// Prints `program` parsed from `fm`.
+    ///
+    /// `fm` is named `cfg.source_file_name` in the source map, whatever kind
+    /// of file name it has.
     pub fn print(
         &self,
         program: &Program,
         fm: Arc<SourceFile>,
         comments: &Comments,
-        source_map: bool,
-        minify: bool,
+        cfg: PrintConfig,
+    ) -> Result<TransformOutput, Error> {
+        let name = match cfg.source_file_name {
+            Some(name) => name.to_string(),
+            None => match fm.name {
+                FileName::Real(ref path) => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string()),
+                ref name => name.to_string(),
+            },
+        };
+
+        self.print_files(program, &[(fm, name)], comments, cfg)
+    }
+
+    /// Prints `program` generated from `files`.
+    ///
+    /// Each file is paired with its name in the source map, so
+    /// `cfg.source_file_name` is not used.
+    pub(crate) fn print_files(
+        &self,
+        program: &Program,
+        files: &[(Arc<SourceFile>, String)],
+        comments: &Comments,
+        cfg: PrintConfig,
     ) -> Result<TransformOutput, Error> {
+        let PrintConfig {
+            source_maps: source_map,
+            source_root,
+            orig,
+            minify,
+            output,
+            ..
+        } = cfg;
+
         self.run(|| {
             let mut src_map_builder = SourceMapBuilder::new(None);
 
-            match fm.name {
-                FileName::Real(ref p) => {
-                    let id = src_map_builder.add_source(&p.display().to_string());
+            let src_ids = files
+                .iter()
+                .map(|(fm, name)| {
+                    let id = src_map_builder.add_source(&fm.name.to_string());
                     src_map_builder.set_source_contents(id, Some(&fm.src));
-                }
-                _ => {}
-            }
+                    (id, name)
+                })
+                .collect::<Vec<_>>();
 
             let src = {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: codegen::Config { minify },
-                        comments: if minify { None } else { Some(&comments) },
+                        cfg: output.codegen_config(minify),
+                        comments: Some(&comments),
                         cm: self.cm.clone(),
                         wr: box codegen::text_writer::JsWriter::new(
                             self.cm.clone(),
-                            "\n",
+                            output.new_line.as_str(),
                             &mut buf,
-                            if source_map {
+                            if source_map.enabled() {
                                 Some(&mut src_map_builder)
                             } else {
                                 None
                             },
-                        ),
+                        )
+                        .with_indent(output.indent()),
                         handlers,
                         pos_of_leading_comments: Default::default(),
                     };
@@ -155,20 +242,43 @@ impl Compiler {
                 // Invalid utf8 is valid in javascript world.
                 unsafe { String::from_utf8_unchecked(buf) }
             };
+            if !source_map.enabled() {
+                return Ok(TransformOutput {
+                    code: src,
+                    map: None,
+                });
+            }
+
+            for (id, name) in src_ids {
+                src_map_builder.set_source(id, name);
+            }
+
+            let mut map = src_map_builder.into_sourcemap();
+            if let Some(orig) = orig {
+                map = chain_src_maps(&map, orig);
+            }
+
+            let mut buf = vec![];
+            map.to_writer(&mut buf)
+                .map_err(|err| Error::FailedToWriteSourceMap { err })?;
+            let mut map = String::from_utf8(buf).map_err(|err| Error::SourceMapNotUtf8 { err })?;
+
+            if let Some(source_root) = source_root {
+                map = set_source_root(&map, source_root)?;
+            }
+
+            let mut code = src;
+            if source_map.inline() {
+                if !code.ends_with('\n') {
+                    code.push_str(output.new_line.as_str());
+                }
+                code.push_str("//# sourceMappingURL=data:application/json;base64,");
+                code.push_str(&base64::encode(map.as_bytes()));
+            }
+
             Ok(TransformOutput {
-                code: src,
-                map: if source_map {
-                    let mut buf = vec![];
-                    src_map_builder
-                        .into_sourcemap()
-                        .to_writer(&mut buf)
-                        .map_err(|err| Error::FailedToWriteSourceMap { err })?;
-                    let map =
-                        String::from_utf8(buf).map_err(|err| Error::SourceMapNotUtf8 { err })?;
-                    Some(map)
-                } else {
-                    None
-                },
+                code,
+                map: if source_map.separate() { Some(map) } else { None },
             })
         })
     }
@@ -181,7 +291,37 @@ impl Compiler {
             cm,
             handler,
             globals: Globals::new(),
+            config_cache: Default::default(),
+        }
+    }
+
+    /// Reads and parses a config file.
+    ///
+    /// Parsed config files are cached, so a `.swcrc` shared by multiple input
+    /// files is read only once. A cached config is parsed again if the file is
+    /// modified after it's cached.
+    fn read_config(&self, path: &Path) -> Result<Rc, Error> {
+        let r = File::open(path).map_err(|err| Error::FailedToReadConfigFile { err })?;
+        let modified = r.metadata().and_then(|m| m.modified()).ok();
+
+        if let Some(modified) = modified {
+            if let Some(cached) = self.config_cache.get(path) {
+                if cached.0 == modified {
+                    return Ok(cached.1.clone());
+                }
+            }
+        }
+
+        let config: Rc =
+            serde_json::from_reader(r).map_err(|err| Error::FailedToParseConfigFile { err })?;
+
+        // Without a modification time, there's no way to tell if the cache is
+        // stale.
+        if let Some(modified) = modified {
+            self.config_cache
+                .insert(path.to_path_buf(), (modified, config.clone()));
         }
+        Ok(config)
     }
 
     /// This method handles merging of config.
@@ -204,10 +344,7 @@ impl Compiler {
 
         let config_file = match config_file {
             Some(ConfigFile::Str(ref s)) => {
-                let path = Path::new(s);
-                let r = File::open(&path).map_err(|err| Error::FailedToReadConfigFile { err })?;
-                let config: Rc = serde_json::from_reader(r)
-                    .map_err(|err| Error::FailedToParseConfigFile { err })?;
+                let config = self.read_config(Path::new(s))?;
                 Some(config)
             }
             _ => None,
@@ -221,17 +358,18 @@ impl Compiler {
                         let swcrc = dir.join(".swcrc");
 
                         if swcrc.exists() {
-                            let r = File::open(&swcrc)
-                                .map_err(|err| Error::FailedToReadConfigFile { err })?;
-                            let mut config = serde_json::from_reader(r)
-                                .map_err(|err| Error::FailedToParseConfigFile { err })
-                                .and_then(|rc: Rc| rc.into_config(Some(path)))?;
+                            let mut config = self.read_config(&swcrc)?.into_config(Some(path))?;
 
                             if let Some(config_file) = config_file {
                                 config.merge(&config_file.into_config(Some(path))?)
                             }
-                            let built =
-                                opts.build(&self.cm, &self.handler, *is_module, Some(config));
+                            let built = opts.build(
+                                &self.cm,
+                                &self.handler,
+                                *is_module,
+                                Some(&**path),
+                                Some(config),
+                            );
                             return Ok(built);
                         }
 
@@ -247,6 +385,7 @@ impl Compiler {
                         &self.cm,
                         &self.handler,
                         *is_module,
+                        Some(&**path),
                         Some(config_file.into_config(Some(path))?),
                     );
                     return Ok(built);
@@ -255,10 +394,15 @@ impl Compiler {
             _ => {}
         }
 
+        let filename = match fm.name {
+            FileName::Real(ref path) => Some(&**path),
+            _ => None,
+        };
         let built = opts.build(
             &self.cm,
             &self.handler,
             *is_module,
+            filename,
             match config_file {
                 Some(config_file) => Some(config_file.into_config(None)?),
                 None => None,
@@ -277,6 +421,22 @@ impl Compiler {
         self.process_js(fm, config)
     }
 
+    /// Compiles `files` in parallel.
+    ///
+    /// Results are returned in the order of `files`. All files share
+    /// `self.cm`, so diagnostics emitted to `self.handler` point to the file
+    /// they originate from.
+    pub fn process_js_files(
+        &self,
+        files: Vec<Arc<SourceFile>>,
+        opts: &Options,
+    ) -> Vec<Result<TransformOutput, Error>> {
+        files
+            .into_par_iter()
+            .map(|fm| self.process_js_file(fm, opts))
+            .collect()
+    }
+
     /// You can use custom pass with this method.
     ///
     /// There exists a [PassBuilder] to help building custom passes.
@@ -290,27 +450,164 @@ impl Compiler {
                 eprintln!("processing js file: {:?}", fm)
             }
 
+            // Comments are collected even if minifying, because passes use annotations.
             let comments = Default::default();
             let module = self.parse_js(
                 fm.clone(),
                 config.target,
                 config.syntax,
                 config.is_module,
-                if config.minify { None } else { Some(&comments) },
+                Some(&comments),
             )?;
+            // The comment refers to the source map of the input file.
+            comments.retain(|cmt| !is_source_map_url(cmt));
+
+            let orig = if config.source_maps.enabled() {
+                self.get_orig_src_map(&fm, &config.input_source_map)?
+            } else {
+                None
+            };
+
             let mut pass = config.pass;
             let module = helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
                 util::HANDLER.set(&self.handler, || {
-                    // Fold module
-                    module.fold_with(&mut pass)
+                    util::COMMENTS.set(&comments, || {
+                        // Fold module
+                        module.fold_with(&mut pass)
+                    })
                 })
             });
 
-            self.print(&module, fm, &comments, config.source_maps, config.minify)
+            self.print(
+                &module,
+                fm,
+                &comments,
+                PrintConfig {
+                    source_maps: &config.source_maps,
+                    source_file_name: config.source_file_name.as_ref().map(|s| &**s),
+                    source_root: config.source_root.as_ref().map(|s| &**s),
+                    orig: orig.as_ref(),
+                    minify: config.minify,
+                    output: &config.output,
+                },
+            )
         })
     }
 }
 
+fn load_src_map(path: &Path) -> Result<sourcemap::SourceMap, Error> {
+    let r = File::open(path).map_err(|err| Error::FailedToReadInputSourceMap { err })?;
+    let map = sourcemap::decode(r).map_err(|err| Error::FailedToParseInputSourceMap { err })?;
+
+    into_regular_map(map)
+}
+
+/// `//# sourceMappingURL=...` or `//@ sourceMappingURL=...`
+fn is_source_map_url(cmt: &Comment) -> bool {
+    cmt.kind == CommentKind::Line
+        && (cmt.text.starts_with("# sourceMappingURL=")
+            || cmt.text.starts_with("@ sourceMappingURL="))
+}
+
+/// Loads the source map referenced by `//# sourceMappingURL`, or the
+/// `.map` file next to `fm`.
+fn find_orig_src_map(fm: &SourceFile) -> Result<Option<sourcemap::SourceMap>, Error> {
+    let reference = sourcemap::locate_sourcemap_reference_slice(fm.src.as_bytes())
+        .map_err(|err| Error::FailedToParseInputSourceMap { err })?;
+
+    match reference.get_url() {
+        Some(url) if url.starts_with("data:") => {
+            let map = sourcemap::decode_data_url(url)
+                .map_err(|err| Error::FailedToParseInputSourceMap { err })?;
+            into_regular_map(map).map(Some)
+        }
+        Some(url) => match fm.name {
+            FileName::Real(ref path) => {
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                load_src_map(&dir.join(url)).map(Some)
+            }
+            _ => Ok(None),
+        },
+        None => match fm.name {
+            FileName::Real(ref path) => {
+                let mut map_path = OsString::from(path.as_os_str());
+                map_path.push(".map");
+                let map_path = PathBuf::from(map_path);
+
+                if map_path.exists() {
+                    load_src_map(&map_path).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+            _ => Ok(None),
+        },
+    }
+}
+
+fn into_regular_map(map: DecodedMap) -> Result<sourcemap::SourceMap, Error> {
+    match map {
+        DecodedMap::Regular(map) => Ok(map),
+        DecodedMap::Index(index) => index
+            .flatten()
+            .map_err(|err| Error::FailedToParseInputSourceMap { err }),
+    }
+}
+
+/// `sourcemap` does not support `sourceRoot` on write, so we patch the json.
+fn set_source_root(map: &str, source_root: &str) -> Result<String, Error> {
+    let mut json: serde_json::Value = serde_json::from_str(map).map_err(|err| {
+        Error::FailedToWriteSourceMap {
+            err: sourcemap::Error::BadJson(err),
+        }
+    })?;
+
+    if let serde_json::Value::Object(ref mut obj) = json {
+        obj.insert("sourceRoot".into(), source_root.into());
+    }
+
+    serde_json::to_string(&json).map_err(|err| Error::FailedToWriteSourceMap {
+        err: sourcemap::Error::BadJson(err),
+    })
+}
+
+/// Maps each token of `map` through `orig`.
+///
+/// Tokens which cannot be mapped to an original location are dropped.
+fn chain_src_maps(
+    map: &sourcemap::SourceMap,
+    orig: &sourcemap::SourceMap,
+) -> sourcemap::SourceMap {
+    let mut builder = SourceMapBuilder::new(map.get_file());
+
+    for token in map.tokens() {
+        let orig_token = match orig.lookup_token(token.get_src_line(), token.get_src_col()) {
+            // `lookup_token` returns the closest token before the position, which may be
+            // on a previous line.
+            Some(t) if t.get_dst_line() == token.get_src_line() && t.has_source() => t,
+            _ => continue,
+        };
+
+        let raw = builder.add(
+            token.get_dst_line(),
+            token.get_dst_col(),
+            orig_token.get_src_line(),
+            orig_token.get_src_col(),
+            orig_token.get_source(),
+            orig_token.get_name().or_else(|| token.get_name()),
+        );
+
+        if !builder.has_source_contents(raw.src_id) {
+            let contents = orig.get_source_contents(orig_token.get_src_id());
+            if contents.is_some() {
+                builder.set_source_contents(raw.src_id, contents);
+            }
+        }
+    }
+
+    builder.into_sourcemap()
+}
+
 struct MyHandlers;
 
 impl ecmascript::codegen::Handlers for MyHandlers {}
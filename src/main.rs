@@ -0,0 +1,400 @@
+use clap::{App, Arg, ArgMatches};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+};
+use swc::{
+    common::{
+        errors::{ColorConfig, Handler},
+        FileName, SourceMap,
+    },
+    config::{ConfigFile, Options, SourceMapsConfig},
+    error::Error,
+    Compiler, TransformOutput,
+};
+use walkdir::WalkDir;
+
+/// Files with these extensions are compiled when a directory is given.
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx"];
+
+fn main() {
+    let matches = App::new("swc")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Speedy web compiler")
+        .arg(
+            Arg::with_name("files")
+                .multiple(true)
+                .help("Files or directories to compile. Reads from stdin if omitted."),
+        )
+        .arg(
+            Arg::with_name("out-file")
+                .short("o")
+                .long("out-file")
+                .takes_value(true)
+                .conflicts_with("out-dir")
+                .help("Compile all input files into a single file"),
+        )
+        .arg(
+            Arg::with_name("out-dir")
+                .short("d")
+                .long("out-dir")
+                .takes_value(true)
+                .help("Compile an input directory of modules into an output directory"),
+        )
+        .arg(
+            Arg::with_name("source-maps")
+                .short("s")
+                .long("source-maps")
+                .takes_value(true)
+                .min_values(0)
+                .possible_values(&["true", "false", "inline", "both"])
+                .help("Generate source maps"),
+        )
+        .arg(
+            Arg::with_name("config-file")
+                .long("config-file")
+                .takes_value(true)
+                .help("Path to a .swcrc file to use"),
+        )
+        .arg(
+            Arg::with_name("no-swcrc")
+                .long("no-swcrc")
+                .help("Do not look up .swcrc files"),
+        )
+        .arg(
+            Arg::with_name("ignore")
+                .long("ignore")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Glob of files which should not be compiled"),
+        )
+        .arg(
+            Arg::with_name("filename")
+                .short("f")
+                .long("filename")
+                .takes_value(true)
+                .help("Filename to use when reading from stdin"),
+        )
+        .get_matches();
+
+    let cm = Arc::<SourceMap>::default();
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    let c = Compiler::new(cm, handler);
+
+    let result = run(&c, &matches);
+
+    if result.is_err() || c.handler.has_errors() {
+        process::exit(1)
+    }
+}
+
+fn run(c: &Compiler, matches: &ArgMatches) -> Result<(), ()> {
+    let ignore = build_ignore(c, matches)?;
+
+    let files = match matches.values_of("files") {
+        Some(files) => files.collect::<Vec<_>>(),
+        None => return compile_stdin(c, matches),
+    };
+
+    let mut inputs = vec![];
+    for file in files {
+        let path = Path::new(file);
+
+        if path.is_dir() {
+            for entry in WalkDir::new(path) {
+                let entry = entry.map_err(|err| {
+                    c.handler
+                        .err(&format!("failed to read {}: {}", path.display(), err))
+                })?;
+
+                if !entry.file_type().is_file()
+                    || !has_js_extension(entry.path())
+                    || ignore.is_match(entry.path())
+                {
+                    continue;
+                }
+
+                let rel = entry
+                    .path()
+                    .strip_prefix(path)
+                    .expect("walkdir returned a path outside of the directory")
+                    .to_path_buf();
+                inputs.push((entry.path().to_path_buf(), rel));
+            }
+        } else {
+            if ignore.is_match(path) {
+                continue;
+            }
+
+            let rel = PathBuf::from(path.file_name().unwrap_or_else(|| path.as_os_str()));
+            inputs.push((path.to_path_buf(), rel));
+        }
+    }
+
+    if let Some(out_dir) = matches.value_of("out-dir") {
+        let out_dir = Path::new(out_dir);
+        let mut failed = false;
+
+        for (path, rel) in inputs {
+            let dest = out_dir.join(rel).with_extension("js");
+            let dest_dir = dest.parent().unwrap_or(out_dir);
+
+            let opts = options(matches, Some(&relative_to(dest_dir, &path)));
+            match compile_file(c, &path, &opts) {
+                Ok(Some(output)) => {
+                    fs::create_dir_all(dest_dir).map_err(|err| {
+                        c.handler
+                            .err(&format!("failed to create {}: {}", dest_dir.display(), err))
+                    })?;
+                    write_output(c, &dest, output, &opts)?;
+                }
+                Ok(None) => {}
+                Err(()) => failed = true,
+            }
+        }
+
+        return if failed { Err(()) } else { Ok(()) };
+    }
+
+    if let Some(out_file) = matches.value_of("out-file") {
+        let dest = Path::new(out_file);
+        let dest_dir = dest.parent().unwrap_or_else(|| Path::new(""));
+
+        if inputs.len() == 1 {
+            let (path, _) = inputs.pop().unwrap();
+            let opts = options(matches, Some(&relative_to(dest_dir, &path)));
+            return match compile_file(c, &path, &opts)? {
+                Some(output) => write_output(c, dest, output, &opts),
+                None => Ok(()),
+            };
+        }
+
+        // Source maps of concatenated files are not supported.
+        let opts = options(matches, None);
+        let mut code = String::new();
+        let mut failed = false;
+        for (path, _) in inputs {
+            match compile_file(c, &path, &opts) {
+                Ok(Some(output)) => {
+                    code.push_str(&output.code);
+                    if !code.ends_with('\n') {
+                        code.push('\n');
+                    }
+                }
+                Ok(None) => {}
+                Err(()) => failed = true,
+            }
+        }
+        if failed {
+            return Err(());
+        }
+
+        return write_file(c, dest, &code);
+    }
+
+    let opts = options(matches, None);
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut failed = false;
+    for (path, _) in inputs {
+        match compile_file(c, &path, &opts) {
+            Ok(Some(output)) => print_output(c, &mut stdout, output)?,
+            Ok(None) => {}
+            Err(()) => failed = true,
+        }
+    }
+
+    if failed {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+fn compile_stdin(c: &Compiler, matches: &ArgMatches) -> Result<(), ()> {
+    let mut src = String::new();
+    io::stdin()
+        .read_to_string(&mut src)
+        .map_err(|err| c.handler.err(&format!("failed to read stdin: {}", err)))?;
+
+    let name = match matches.value_of("filename") {
+        Some(filename) => FileName::Real(filename.into()),
+        None => FileName::Anon,
+    };
+    let fm = c.cm.new_source_file(name, src);
+
+    let opts = options(matches, None);
+    let output = c
+        .process_js_file(fm, &opts)
+        .map_err(|err| report(c, "<stdin>", err))?;
+
+    match matches.value_of("out-file") {
+        Some(out_file) => write_output(c, Path::new(out_file), output, &opts),
+        None => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            print_output(c, &mut stdout, output)
+        }
+    }
+}
+
+fn options(matches: &ArgMatches, source_file_name: Option<&Path>) -> Options {
+    Options {
+        swcrc: !matches.is_present("no-swcrc"),
+        config_file: matches
+            .value_of("config-file")
+            .map(|s| ConfigFile::Str(s.into())),
+        source_maps: if matches.is_present("source-maps") {
+            Some(match matches.value_of("source-maps").unwrap_or("true") {
+                "true" => SourceMapsConfig::Bool(true),
+                "false" => SourceMapsConfig::Bool(false),
+                s => SourceMapsConfig::Str(s.into()),
+            })
+        } else {
+            None
+        },
+        source_file_name: source_file_name.map(|p| p.display().to_string()),
+        filename: matches.value_of("filename").unwrap_or_default().into(),
+        ..Default::default()
+    }
+}
+
+fn build_ignore(c: &Compiler, matches: &ArgMatches) -> Result<GlobSet, ()> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in matches.values_of("ignore").into_iter().flatten() {
+        let glob = Glob::new(glob)
+            .map_err(|err| c.handler.err(&format!("invalid ignore pattern: {}", err)))?;
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map_err(|err| c.handler.err(&format!("invalid ignore pattern: {}", err)))
+}
+
+/// Returns `Ok(None)` if the file is skipped because `test` or `exclude` of
+/// the config does not match it, and `Err(())` if compilation failed. Errors
+/// are reported to the handler.
+fn compile_file(c: &Compiler, path: &Path, opts: &Options) -> Result<Option<TransformOutput>, ()> {
+    let fm = c.cm.load_file(path).map_err(|err| {
+        c.handler
+            .err(&format!("failed to read {}: {}", path.display(), err))
+    })?;
+
+    match c.process_js_file(fm, opts) {
+        Ok(output) => Ok(Some(output)),
+        Err(Error::Unmatched) => {
+            c.handler.warn(&format!(
+                "{}: skipped because the config does not match it (see `test` and `exclude`)",
+                path.display()
+            ));
+            Ok(None)
+        }
+        Err(err) => {
+            report(c, &path.display().to_string(), err);
+            Err(())
+        }
+    }
+}
+
+fn report(c: &Compiler, name: &str, err: Error) {
+    match err {
+        // Parser errors are already emitted.
+        Error::FailedToParseModule {} => {}
+        _ => c.handler.err(&format!("{}: {}", name, err)),
+    }
+}
+
+/// Writes `output` to `dest`, along with `dest.map` if a separate source map
+/// is requested.
+fn write_output(
+    c: &Compiler,
+    dest: &Path,
+    output: TransformOutput,
+    opts: &Options,
+) -> Result<(), ()> {
+    let mut code = output.code;
+
+    if let Some(map) = output.map {
+        let inline = opts.source_maps.as_ref().map_or(false, |s| s.inline());
+        let map_file_name = format!(
+            "{}.map",
+            dest.file_name()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        );
+
+        if !inline {
+            if !code.ends_with('\n') {
+                code.push('\n');
+            }
+            code.push_str("//# sourceMappingURL=");
+            code.push_str(&map_file_name);
+        }
+
+        write_file(c, &dest.with_file_name(map_file_name), &map)?;
+    }
+
+    write_file(c, dest, &code)
+}
+
+/// Writes the code of `output` to stdout.
+///
+/// A separate source map cannot be written to stdout, so it's an error.
+fn print_output(c: &Compiler, stdout: &mut impl Write, output: TransformOutput) -> Result<(), ()> {
+    if output.map.is_some() {
+        c.handler.err(
+            "a separate source map requires `--out-file` or `--out-dir` (use `--source-maps \
+             inline` to print it with the code)",
+        );
+        return Err(());
+    }
+
+    writeln!(stdout, "{}", output.code)
+        .map_err(|err| c.handler.err(&format!("failed to write output: {}", err)))
+}
+
+fn write_file(c: &Compiler, dest: &Path, content: &str) -> Result<(), ()> {
+    File::create(dest)
+        .and_then(|mut f| f.write_all(content.as_bytes()))
+        .map_err(|err| {
+            c.handler
+                .err(&format!("failed to write {}: {}", dest.display(), err))
+        })
+}
+
+fn has_js_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| EXTENSIONS.contains(&ext))
+}
+
+/// Computes path to `path` relative to `dir`.
+fn relative_to(dir: &Path, path: &Path) -> PathBuf {
+    let cwd = env::current_dir().unwrap_or_default();
+    let (dir, path) = (cwd.join(dir), cwd.join(path));
+
+    let dir = dir.components().collect::<Vec<_>>();
+    let path = path.components().collect::<Vec<_>>();
+    let common = dir
+        .iter()
+        .zip(path.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut rel = PathBuf::new();
+    for _ in common..dir.len() {
+        rel.push("..");
+    }
+    for c in &path[common..] {
+        rel.push(c.as_os_str());
+    }
+    rel
+}
@@ -0,0 +1,117 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+};
+
+/// Path to the `swc` binary built for this test.
+fn swc() -> PathBuf {
+    let mut dir = env::current_exe().expect("failed to get path of the test binary");
+    dir.pop();
+    if dir.ends_with("deps") {
+        dir.pop();
+    }
+    dir.join(format!("swc{}", env::consts::EXE_SUFFIX))
+}
+
+/// Creates an empty directory for a test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("swc-cli-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(swc())
+        .current_dir(dir)
+        .arg("--no-swcrc")
+        .args(args)
+        .output()
+        .expect("failed to run swc")
+}
+
+#[test]
+fn out_dir() {
+    let dir = temp_dir("out-dir");
+    fs::create_dir_all(dir.join("src/nested")).unwrap();
+    fs::write(dir.join("src/a.js"), "const a = 1;").unwrap();
+    fs::write(dir.join("src/nested/b.ts"), "let b: number = 2;").unwrap();
+    fs::write(dir.join("src/c.json"), "{}").unwrap();
+
+    let out = run(&dir, &["src", "-d", "lib"]);
+
+    assert!(out.status.success(), "{:?}", out);
+    assert!(dir.join("lib/a.js").exists());
+    assert!(dir.join("lib/nested/b.js").exists());
+    assert!(!dir.join("lib/c.js").exists());
+}
+
+#[test]
+fn out_file_with_source_map() {
+    let dir = temp_dir("out-file");
+    fs::write(dir.join("a.js"), "const a = 1;").unwrap();
+
+    let out = run(&dir, &["a.js", "-o", "out.js", "-s"]);
+
+    assert!(out.status.success(), "{:?}", out);
+    let code = fs::read_to_string(dir.join("out.js")).unwrap();
+    assert!(
+        code.ends_with("//# sourceMappingURL=out.js.map"),
+        "{}",
+        code
+    );
+    assert!(dir.join("out.js.map").exists());
+}
+
+#[test]
+fn syntax_error() {
+    let dir = temp_dir("syntax-error");
+    fs::write(dir.join("a.js"), "const = ;").unwrap();
+
+    let out = run(&dir, &["a.js", "-o", "out.js"]);
+
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    assert!(!dir.join("out.js").exists());
+}
+
+#[test]
+fn unmatched_file_is_skipped() {
+    let dir = temp_dir("unmatched");
+    fs::write(dir.join("a.js"), "const a = 1;").unwrap();
+    fs::write(dir.join("config.json"), r#"{ "test": "\\.ts$" }"#).unwrap();
+
+    let out = run(&dir, &["a.js", "-d", "lib", "--config-file", "config.json"]);
+
+    assert!(out.status.success(), "{:?}", out);
+    assert!(!dir.join("lib/a.js").exists());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("a.js: skipped"), "{}", stderr);
+}
+
+#[test]
+fn stdout_with_separate_source_map() {
+    let dir = temp_dir("stdout-source-map");
+    fs::write(dir.join("a.js"), "const a = 1;").unwrap();
+
+    let out = run(&dir, &["a.js", "-s"]);
+
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    assert!(out.stdout.is_empty(), "{:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("requires `--out-file` or `--out-dir`"),
+        "{}",
+        stderr
+    );
+
+    let out = run(&dir, &["a.js", "-s", "inline"]);
+
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("//# sourceMappingURL=data:application/json"),
+        "{}",
+        stdout
+    );
+}
@@ -1,5 +1,10 @@
+use common::FileName;
 use std::path::Path;
-use swc::{config::Options, error::Error, Compiler};
+use swc::{
+    config::{Options, SourceMapsConfig},
+    error::Error,
+    Compiler,
+};
 use testing::{NormalizedOutput, StdErr, Tester};
 use walkdir::WalkDir;
 
@@ -228,3 +233,286 @@ fn issue_467() {
 fn issue_468() {
     file("tests/projects/issue-468/input.ts").expect("failed to parse typescript");
 }
+
+/// should chain the inline input source map
+#[test]
+fn input_source_map_inline() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm
+                .load_file(Path::new("tests/projects/input-source-map/input.js"))
+                .expect("failed to load file");
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+
+            let map = s.map.expect("source map should be generated");
+            println!("{}", map);
+
+            assert!(map.contains("input.ts"));
+            assert!(!map.contains("input.js\""));
+            assert!(!s.code.contains("sourceMappingURL"));
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should ignore a missing input source map
+#[test]
+fn input_source_map_missing() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm
+                .load_file(Path::new("tests/projects/input-source-map-missing/input.js"))
+                .expect("failed to load file");
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+
+            let map = s.map.expect("source map should be generated");
+            assert!(map.contains("input.js"));
+            assert!(!s.code.contains("sourceMappingURL"));
+            assert!(!c.handler.has_errors());
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should append inline source map and respect `sourceRoot` / `sourceFileName`
+#[test]
+fn source_maps_both() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm
+                .load_file(Path::new("tests/projects/source-map-inline/input.js"))
+                .expect("failed to load file");
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        source_maps: Some(SourceMapsConfig::Str("both".into())),
+                        source_file_name: Some("src/input.js".into()),
+                        source_root: Some("/root/".into()),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+            println!("{}", s.code);
+
+            assert!(s
+                .code
+                .contains("//# sourceMappingURL=data:application/json;base64,"));
+
+            let map = s.map.expect("source map should be returned for `both`");
+            assert!(map.contains("\"sourceRoot\":\"/root/\""));
+            assert!(map.contains("\"src/input.js\""));
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should use `sourceFileName` for inputs without a path
+#[test]
+fn source_file_name_anon() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm.new_source_file(FileName::Anon, "let a = 1;".into());
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        source_file_name: Some("input.js".into()),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+
+            let map = s.map.expect("source map should be generated");
+            assert!(map.contains("\"sources\":[\"input.js\"]"), "{}", map);
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should not keep the original text of literals the target does not support
+#[test]
+fn keep_raw_literals_compat() {
+    fn compile(target: &str) -> String {
+        Tester::new()
+            .print_errors(|cm, handler| {
+                let c = Compiler::new(cm.clone(), handler);
+
+                let fm =
+                    cm.new_source_file(FileName::Anon, "var a = 0b11, b = 'a\u{2028}b';".into());
+                let config = serde_json::from_str(&format!(
+                    r#"{{ "jsc": {{ "target": "{}", "output": {{ "keepRawLiterals": true }} }} }}"#,
+                    target
+                ))
+                .expect("failed to parse config");
+                let s = c
+                    .process_js_file(
+                        fm,
+                        &Options {
+                            swcrc: false,
+                            config: Some(config),
+                            ..Default::default()
+                        },
+                    )
+                    .expect("failed to process file");
+
+                Ok(s.code)
+            })
+            .unwrap()
+    }
+
+    let es2019 = compile("es2019");
+    assert!(es2019.contains("0b11"), "{}", es2019);
+    assert!(es2019.contains('\u{2028}'), "{}", es2019);
+
+    let es2018 = compile("es2018");
+    assert!(es2018.contains("0b11"), "{}", es2018);
+    assert!(es2018.contains("a\\u2028b"), "{}", es2018);
+
+    let es5 = compile("es5");
+    assert!(es5.contains("a = 3"), "{}", es5);
+}
+
+/// should compile multiple files in parallel, sharing `.swcrc`, and report a
+/// syntax error only for the file which has it
+#[test]
+fn process_js_files() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let files = vec![
+                cm.load_file(Path::new("tests/projects/issue-414/a.js"))
+                    .expect("failed to load file"),
+                cm.load_file(Path::new("tests/projects/issue-414/b.ts"))
+                    .expect("failed to load file"),
+                cm.new_source_file(
+                    FileName::Real("tests/projects/issue-414/broken.js".into()),
+                    "const = ;".into(),
+                ),
+            ];
+
+            let outputs = c.process_js_files(
+                files,
+                &Options {
+                    swcrc: true,
+                    is_module: true,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(outputs.len(), 3);
+
+            let mut outputs = outputs.into_iter().map(|r| r.map(|v| v.code));
+            let s1 = outputs.next().unwrap().map_err(|_| ())?;
+            let s2 = outputs.next().unwrap().map_err(|_| ())?;
+
+            assert!(s1.contains("require('foo')"));
+            assert!(s2.contains("define(['bar'], function(_bar) {"));
+
+            match outputs.next().unwrap() {
+                Err(Error::FailedToParseModule {}) => {}
+                Err(err) => panic!("unexpected error: {}", err),
+                Ok(code) => panic!("file with a syntax error was compiled:\n{}", code),
+            }
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should bundle modules into a single module
+#[test]
+fn bundle() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let s = c
+                .bundle(
+                    &["tests/projects/bundle/entry.js".into()],
+                    &Options {
+                        swcrc: true,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        ..Default::default()
+                    },
+                )
+                .map_err(|_| ())?;
+            println!("{}", s.code);
+
+            assert!(!s.code.contains("import"));
+            assert!(!s.code.contains("require("));
+            assert!(s.code.contains("function add("));
+            assert!(s.code.contains("function pad("));
+            assert!(s.code.contains("'math'"));
+            assert!(s.code.contains("'utils'"));
+            assert!(s.code.contains("main as default"));
+
+            let map = s.map.expect("source map should be generated");
+            assert!(map.contains("tests/projects/bundle/math.js"));
+            assert!(map.contains("tests/projects/bundle/node_modules/pad/esm/index.js"));
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// should inject helpers used by multiple modules once
+#[test]
+fn bundle_helpers() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let s = c
+                .bundle(
+                    &["tests/projects/bundle-helpers/entry.js".into()],
+                    &Options {
+                        swcrc: true,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|_| ())?;
+            println!("{}", s.code);
+
+            assert_eq!(s.code.matches("function _classCallCheck(").count(), 1);
+            assert!(!s.code.contains("_classCallCheck1"));
+            assert!(s.code.contains("_classCallCheck(this, Point)"));
+            assert!(s.code.contains("_classCallCheck(this, Line)"));
+
+            Ok(())
+        })
+        .unwrap();
+}
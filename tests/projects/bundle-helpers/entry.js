@@ -0,0 +1,8 @@
+import { Point } from './point';
+
+export class Line {
+    constructor(a, b) {
+        this.a = new Point(a);
+        this.b = new Point(b);
+    }
+}
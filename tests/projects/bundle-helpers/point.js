@@ -0,0 +1,5 @@
+export class Point {
+    constructor(x) {
+        this.x = x;
+    }
+}
@@ -0,0 +1,9 @@
+import { add } from './math';
+import greet, * as utils from './utils';
+import { pad } from 'pad';
+
+export const sum = add(1, 2);
+
+export default function main() {
+    return greet(pad(utils.name));
+}
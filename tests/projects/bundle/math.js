@@ -0,0 +1,7 @@
+const name = 'math';
+
+export function add(a, b) {
+    return a + b;
+}
+
+export { name };
@@ -0,0 +1,5 @@
+export const name = 'utils';
+
+export default function greet(n) {
+    return `Hello ${n}`;
+}
@@ -0,0 +1 @@
+const foo = () => 1;